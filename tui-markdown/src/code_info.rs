@@ -0,0 +1,216 @@
+//! Parsed info strings of fenced code blocks.
+//!
+//! The info string follows the opening fence, as in ```` ```rust title="src/main.rs" ignore ````.
+//! Its first word is the language, which alone selects the syntax. The remaining words are
+//! attributes: `key=value` pairs with optional quotes, bare flags, line ranges such as `{3,5-7}`,
+//! and Pandoc-style `{.class #id}` groups.
+
+use std::ops::RangeInclusive;
+
+/// The language and attributes of a fenced code block.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::CodeBlockInfo;
+///
+/// let info = CodeBlockInfo::parse(r#"rust title="src/main.rs" ignore {3,5-7}"#);
+///
+/// assert_eq!(info.language(), "rust");
+/// assert_eq!(info.title(), Some("src/main.rs"));
+/// assert!(info.has_attribute("ignore"));
+/// assert_eq!(info.highlighted_lines(), [3..=3, 5..=7]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CodeBlockInfo {
+    language: String,
+    attributes: Vec<(String, Option<String>)>,
+    highlighted_lines: Vec<RangeInclusive<usize>>,
+}
+
+impl CodeBlockInfo {
+    /// Parses the info string of a fenced code block.
+    ///
+    /// The language ends at the first whitespace or `{`. When the info string starts with an
+    /// attribute group, the first `.class` in it becomes the language, as in `{.rust}`. Line
+    /// ranges, either bare or as `hl_lines="3 5-7"`, select the
+    /// [`highlighted_lines`](Self::highlighted_lines); other words are kept as attributes.
+    /// `.class` and `#id` words are recorded as `class` and `id` attributes.
+    #[must_use]
+    pub fn parse(info: &str) -> Self {
+        let info = info.trim();
+        let language_end = info
+            .find(|c: char| c.is_whitespace() || c == '{')
+            .unwrap_or(info.len());
+        let (language, rest) = info.split_at(language_end);
+        let mut parsed = Self {
+            language: language.to_owned(),
+            ..Self::default()
+        };
+        for word in attribute_words(rest) {
+            if let Some(class) = word.strip_prefix('.') {
+                if parsed.language.is_empty() {
+                    class.clone_into(&mut parsed.language);
+                }
+                parsed.push_attribute("class", Some(class));
+            } else if let Some(id) = word.strip_prefix('#') {
+                parsed.push_attribute("id", Some(id));
+            } else if let Some((key, value)) = word.split_once('=') {
+                let value = unquote(value);
+                if key == "hl_lines" {
+                    parsed.highlighted_lines.extend(line_ranges(value));
+                } else {
+                    parsed.push_attribute(key, Some(value));
+                }
+            } else if word.starts_with(|c: char| c.is_ascii_digit()) {
+                parsed.highlighted_lines.extend(line_ranges(word));
+            } else {
+                parsed.push_attribute(word, None);
+            }
+        }
+        parsed
+    }
+
+    /// Returns the language, or an empty string when the info string names none.
+    #[must_use]
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Returns the value of the first `key=value` attribute named `key`.
+    ///
+    /// Returns `None` for flags without a value, such as `ignore`.
+    #[must_use]
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| name == key)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Returns whether the info string contains the flag or `key=value` attribute `key`.
+    #[must_use]
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.attributes.iter().any(|(name, _)| name == key)
+    }
+
+    /// Returns the attributes after the language in order, with `None` values for flags.
+    ///
+    /// Line ranges are reported by [`highlighted_lines`](Self::highlighted_lines) instead.
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_deref()))
+    }
+
+    /// Returns the `title`, `filename`, or `file` attribute, in that order of preference.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        ["title", "filename", "file"]
+            .into_iter()
+            .find_map(|key| self.attribute(key))
+    }
+
+    /// Returns the one-based numbers of the lines to emphasize.
+    #[must_use]
+    pub fn highlighted_lines(&self) -> &[RangeInclusive<usize>] {
+        &self.highlighted_lines
+    }
+
+    fn push_attribute(&mut self, key: &str, value: Option<&str>) {
+        self.attributes
+            .push((key.to_owned(), value.map(str::to_owned)));
+    }
+}
+
+/// Splits attributes at whitespace and braces, keeping quoted values together.
+fn attribute_words(attributes: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut word_start = None;
+    let mut quote = None;
+    for (index, c) in attributes.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            None if c == '"' || c == '\'' => quote = Some(c),
+            _ => {}
+        }
+        let separates = quote.is_none() && (c.is_whitespace() || c == '{' || c == '}');
+        match (separates, word_start) {
+            (true, Some(start)) => {
+                words.push(&attributes[start..index]);
+                word_start = None;
+            }
+            (false, None) => word_start = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(&attributes[start..]);
+    }
+    words
+}
+
+/// Removes one pair of matching double or single quotes around `value`.
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
+
+/// Parses line numbers and ranges separated by commas or spaces, such as `3,5-7`.
+///
+/// Items that are not a number or an ascending range are skipped.
+fn line_ranges(spec: &str) -> impl Iterator<Item = RangeInclusive<usize>> + '_ {
+    spec.split([',', ' ']).filter_map(|item| {
+        let (start, end) = item.split_once('-').unwrap_or((item, item));
+        let start = start.parse().ok()?;
+        let end = end.parse().ok()?;
+        (start <= end).then_some(start..=end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::language_only("rust", "rust", vec![])]
+    #[case::braces("rust {3,5-7}", "rust", vec![3..=3, 5..=7])]
+    #[case::attached_braces("rust{2}", "rust", vec![2..=2])]
+    #[case::hl_lines("python hl_lines=\"1 3-4\"", "python", vec![1..=1, 3..=4])]
+    #[case::attribute_braces("{.rust hl_lines=\"2\"}", "rust", vec![2..=2])]
+    #[case::invalid_ranges("rust {4-2,3}", "rust", vec![3..=3])]
+    fn parses_language_and_lines(
+        #[case] info: &str,
+        #[case] language: &str,
+        #[case] highlighted_lines: Vec<RangeInclusive<usize>>,
+    ) {
+        let parsed = CodeBlockInfo::parse(info);
+
+        assert_eq!(parsed.language(), language);
+        assert_eq!(parsed.highlighted_lines(), highlighted_lines);
+    }
+
+    #[test]
+    fn parses_attributes() {
+        let info = CodeBlockInfo::parse("sh filename='run me.sh' ignore {#setup .shell}");
+
+        assert_eq!(
+            info.attributes().collect::<Vec<_>>(),
+            [
+                ("filename", Some("run me.sh")),
+                ("ignore", None),
+                ("id", Some("setup")),
+                ("class", Some("shell")),
+            ]
+        );
+        assert_eq!(info.title(), Some("run me.sh"));
+        assert_eq!(info.attribute("ignore"), None);
+        assert!(info.has_attribute("ignore"));
+        assert!(!info.has_attribute("title"));
+    }
+}
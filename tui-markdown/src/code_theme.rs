@@ -15,7 +15,12 @@ use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
-use syntect::highlighting::{Theme, ThemeSet};
+use ratatui_core::style::{Color, Style};
+use syntect::highlighting::{self, Theme, ThemeSet};
+
+pub use self::style_sheet::CodeThemeStyleSheet;
+
+mod style_sheet;
 
 /// An owned syntax-highlighting theme for fenced code blocks.
 ///
@@ -30,6 +35,8 @@ use syntect::highlighting::{Theme, ThemeSet};
 #[derive(Clone, Debug)]
 pub struct CodeTheme {
     theme: Theme,
+    /// Whether colors are mapped to the eight basic ANSI colors.
+    basic_colors: bool,
 }
 
 impl CodeTheme {
@@ -46,7 +53,10 @@ impl CodeTheme {
         let mut reader = Cursor::new(source);
         let theme = ThemeSet::load_from_reader(&mut reader)
             .map_err(|source| CodeThemeLoadError { path: None, source })?;
-        Ok(Self { theme })
+        Ok(Self {
+            theme,
+            basic_colors: false,
+        })
     }
 
     /// Loads a TextMate syntax-highlighting theme from disk.
@@ -75,7 +85,21 @@ impl CodeTheme {
             path: Some(path.to_owned()),
             source,
         })?;
-        Ok(Self { theme })
+        Ok(Self {
+            theme,
+            basic_colors: false,
+        })
+    }
+
+    /// Returns this theme with each color replaced by the nearest of the eight basic ANSI colors.
+    ///
+    /// Use this for terminals without 256-color or true-color support.
+    /// [`Options::preset`](crate::Options::preset) applies it for
+    /// [`StyleSheetPreset::LowColor`](crate::StyleSheetPreset::LowColor).
+    #[must_use]
+    pub fn with_basic_colors(mut self) -> Self {
+        self.basic_colors = true;
+        self
     }
 }
 
@@ -120,7 +144,10 @@ impl BuiltinCodeTheme {
 impl From<BuiltinCodeTheme> for CodeTheme {
     fn from(theme: BuiltinCodeTheme) -> Self {
         let theme = builtin_theme(theme).clone();
-        Self { theme }
+        Self {
+            theme,
+            basic_colors: false,
+        }
     }
 }
 
@@ -166,6 +193,65 @@ pub fn theme(code_theme: &CodeTheme) -> &Theme {
     &code_theme.theme
 }
 
+/// Converts a color of `code_theme` to a terminal color.
+pub fn terminal_color(code_theme: &CodeTheme, color: highlighting::Color) -> Color {
+    let color = Color::Rgb(color.r, color.g, color.b);
+    if code_theme.basic_colors {
+        basic_color(color)
+    } else {
+        color
+    }
+}
+
+/// Converts the colors of a style highlighted with `code_theme` to terminal colors.
+pub fn terminal_style(code_theme: &CodeTheme, style: Style) -> Style {
+    if !code_theme.basic_colors {
+        return style;
+    }
+    Style {
+        fg: style.fg.map(basic_color),
+        bg: style.bg.map(basic_color),
+        ..style
+    }
+}
+
+/// Returns the basic ANSI color nearest to an RGB `color`.
+///
+/// Very dark and very light colors, and colors with little saturation, map to black or gray.
+/// Other colors map to the basic color with the nearest hue.
+fn basic_color(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let chroma = f32::from(max - min);
+    let is_gray = chroma < f32::from(max) * 0.15;
+    if max < 0x60 || (is_gray && max < 0x80) {
+        return Color::Black;
+    }
+    if min > 0xd0 || is_gray {
+        return Color::Gray;
+    }
+    let [red, green, blue] = [r, g, b].map(f32::from);
+    let sector = if max == r {
+        (green - blue) / chroma
+    } else if max == g {
+        (blue - red) / chroma + 2.0
+    } else {
+        (red - green) / chroma + 4.0
+    };
+    let hue = (sector * 60.0).rem_euclid(360.0);
+    match hue {
+        h if h < 30.0 => Color::Red,
+        h if h < 90.0 => Color::Yellow,
+        h if h < 150.0 => Color::Green,
+        h if h < 210.0 => Color::Cyan,
+        h if h < 270.0 => Color::Blue,
+        h if h < 330.0 => Color::Magenta,
+        _ => Color::Red,
+    }
+}
+
 /// Returns the lazily initialized default code theme.
 ///
 /// The renderer calls this only after recognizing a fenced language, so ordinary Markdown and
@@ -270,6 +356,33 @@ mod tests {
             .fg
     }
 
+    #[test]
+    fn basic_color_theme_highlights_with_basic_colors() {
+        let theme = CodeTheme::from(BuiltinCodeTheme::Base16OceanDark).with_basic_colors();
+
+        assert_eq!(rendered_keyword_foreground(theme), Some(Color::Magenta));
+    }
+
+    #[test]
+    fn basic_colors_map_by_hue_and_lightness() {
+        let cases = [
+            (0x2b303b, Color::Black),
+            (0xc0c5ce, Color::Gray),
+            (0xffffff, Color::Gray),
+            (0xbf616a, Color::Red),
+            (0xebcb8b, Color::Yellow),
+            (0xa3be8c, Color::Green),
+            (0x96b5b4, Color::Cyan),
+            (0x8fa1b3, Color::Blue),
+            (0xb48ead, Color::Magenta),
+        ];
+
+        for (hex, expected) in cases {
+            let [_, r, g, b] = u32::to_be_bytes(hex);
+            assert_eq!(basic_color(Color::Rgb(r, g, b)), expected, "{hex:06x}");
+        }
+    }
+
     #[test]
     fn missing_theme_reports_its_path_and_read_error() {
         let path = fixture("missing.tmTheme");
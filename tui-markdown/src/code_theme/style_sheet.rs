@@ -0,0 +1,315 @@
+//! A Markdown style sheet derived from a syntax-highlighting theme.
+//!
+//! TextMate themes style Markdown source through scopes such as `markup.heading` and
+//! `markup.quote`. [`CodeThemeStyleSheet`] resolves those scopes, falling back to common code
+//! scopes when a theme has no Markdown rules, so prose uses the same palette as highlighted code.
+
+use ratatui_core::style::Style;
+use syntect::highlighting::{self, Highlighter};
+use syntect::parsing::Scope;
+
+use super::{terminal_color, theme, CodeTheme};
+use crate::palette::Palette;
+use crate::{DefaultStyleSheet, StyleSheet};
+
+/// Scopes tried for heading colors, in order of preference.
+const HEADING_SCOPES: &[&str] = &[
+    "markup.heading.1.markdown",
+    "entity.name.section.markdown",
+    "entity.name.function",
+    "keyword.control",
+];
+
+/// Scopes tried for link colors, in order of preference.
+const LINK_SCOPES: &[&str] = &[
+    "markup.underline.link.markdown",
+    "string.other.link.markdown",
+    "support.function",
+    "entity.name.tag",
+];
+
+/// Scopes tried for blockquote colors, in order of preference.
+const QUOTE_SCOPES: &[&str] = &["markup.quote.markdown", "comment.line"];
+
+/// Scopes tried for inline code colors, in order of preference.
+const CODE_SCOPES: &[&str] = &[
+    "markup.raw.inline.markdown",
+    "markup.raw",
+    "string.quoted.double",
+];
+
+/// Scopes tried for added diff lines, in order of preference.
+const INSERTED_SCOPES: &[&str] = &["markup.inserted.diff", "markup.inserted"];
+
+/// Scopes tried for removed diff lines, in order of preference.
+const DELETED_SCOPES: &[&str] = &["markup.deleted.diff", "markup.deleted"];
+
+/// Opacity of diff colors blended over the theme's background.
+const DIFF_ALPHA: u8 = 0x40;
+
+/// Scopes tried for secondary text, such as metadata and table borders.
+const MUTED_SCOPES: &[&str] = &["comment.line", "comment.block"];
+
+/// Scopes tried for accents, such as table headers and ordered list numbers.
+const ACCENT_SCOPES: &[&str] = &["keyword.control", "storage.type", "constant.numeric"];
+
+/// A [`StyleSheet`] whose colors come from a [`CodeTheme`].
+///
+/// Headings, links, blockquotes, inline code, table headers and borders, and other secondary text
+/// use colors resolved from the theme's Markdown scopes, such as `markup.heading` and
+/// `markup.quote`. Themes without Markdown rules fall back to common code scopes, such as
+/// `keyword` and `comment`, and then to the theme's foreground. Inline code and code block lines
+/// emphasized by the info string are drawn on the theme's line-highlight color, blended over its
+/// background. Added and removed diff lines are tinted with the theme's `markup.inserted` and
+/// `markup.deleted` colors. Styles the theme cannot inform, such as alert colors, keep the
+/// [`DefaultStyleSheet`](crate::DefaultStyleSheet) choice.
+///
+/// Use [`Options::from_code_theme`](crate::Options::from_code_theme) to select the style sheet and
+/// its code theme together.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{BuiltinCodeTheme, CodeTheme, CodeThemeStyleSheet, Options};
+///
+/// let theme = CodeTheme::from(BuiltinCodeTheme::SolarizedLight);
+/// let options = Options::new(CodeThemeStyleSheet::new(&theme)).code_theme(theme);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeThemeStyleSheet {
+    palette: Palette,
+}
+
+impl CodeThemeStyleSheet {
+    /// Derives a style sheet from the colors of `code_theme`.
+    #[must_use]
+    pub fn new(code_theme: &CodeTheme) -> Self {
+        let theme = theme(code_theme);
+        let highlighter = Highlighter::new(theme);
+        let foreground = theme.settings.foreground;
+        let resolve = |scopes: &[&str]| Style {
+            fg: scopes
+                .iter()
+                .find_map(|scope| scope_color(&highlighter, scope))
+                .or(foreground)
+                .map(|color| terminal_color(code_theme, color)),
+            ..Style::default()
+        };
+        let background = theme.settings.background;
+        let code_background = match (theme.settings.line_highlight, background) {
+            (Some(highlight), Some(background)) => Some(blend(highlight, background)),
+            (highlight, background) => highlight.or(background),
+        }
+        .map(|color| terminal_color(code_theme, color));
+        let diff = |scopes: &[&str], fallback: Style| {
+            let Some(color) = scopes
+                .iter()
+                .find_map(|scope| scope_color(&highlighter, scope))
+            else {
+                return fallback;
+            };
+            match background {
+                Some(background) => Style::new().bg(terminal_color(
+                    code_theme,
+                    blend(
+                        highlighting::Color {
+                            a: DIFF_ALPHA,
+                            ..color
+                        },
+                        background,
+                    ),
+                )),
+                None => Style::new().fg(terminal_color(code_theme, color)),
+            }
+        };
+        let muted = resolve(MUTED_SCOPES);
+        let accent = resolve(ACCENT_SCOPES);
+        Self {
+            palette: Palette {
+                heading: resolve(HEADING_SCOPES),
+                code: Style {
+                    bg: code_background,
+                    ..resolve(CODE_SCOPES)
+                },
+                link: resolve(LINK_SCOPES),
+                blockquote: resolve(QUOTE_SCOPES),
+                muted,
+                metadata: muted,
+                marker: accent,
+                table_header: accent,
+                highlighted_line: Style {
+                    bg: code_background,
+                    ..Style::default()
+                },
+                diff_added: diff(INSERTED_SCOPES, DefaultStyleSheet.diff_added()),
+                diff_removed: diff(DELETED_SCOPES, DefaultStyleSheet.diff_removed()),
+            },
+        }
+    }
+}
+
+impl From<&CodeTheme> for CodeThemeStyleSheet {
+    fn from(code_theme: &CodeTheme) -> Self {
+        Self::new(code_theme)
+    }
+}
+
+impl StyleSheet for CodeThemeStyleSheet {
+    fn heading(&self, level: u8) -> Style {
+        self.palette.heading(level)
+    }
+
+    fn code(&self) -> Style {
+        self.palette.code()
+    }
+
+    fn link(&self) -> Style {
+        self.palette.link()
+    }
+
+    fn blockquote(&self) -> Style {
+        self.palette.blockquote()
+    }
+
+    fn heading_decoration(&self, level: u8) -> Style {
+        self.palette.heading_decoration(level)
+    }
+
+    fn heading_meta(&self) -> Style {
+        self.palette.heading_meta()
+    }
+
+    fn metadata_block(&self) -> Style {
+        self.palette.metadata_block()
+    }
+
+    fn code_block_title_style(&self) -> Style {
+        self.palette.code_block_title_style()
+    }
+
+    fn code_line_number(&self) -> Style {
+        self.palette.code_line_number()
+    }
+
+    fn code_highlighted_line(&self) -> Style {
+        self.palette.code_highlighted_line()
+    }
+
+    fn code_whitespace(&self) -> Style {
+        self.palette.code_whitespace()
+    }
+
+    fn code_overflow(&self) -> Style {
+        self.palette.code_overflow()
+    }
+
+    fn shell_prompt(&self) -> Style {
+        self.palette.shell_prompt()
+    }
+
+    fn diff_added(&self) -> Style {
+        self.palette.diff_added()
+    }
+
+    fn diff_removed(&self) -> Style {
+        self.palette.diff_removed()
+    }
+
+    fn diff_hunk_header(&self) -> Style {
+        self.palette.diff_hunk_header()
+    }
+
+    fn diff_file_header(&self) -> Style {
+        self.palette.diff_file_header()
+    }
+
+    fn mermaid_diagram(&self) -> Style {
+        self.palette.mermaid_diagram()
+    }
+
+    fn list_marker(&self, ordered: bool) -> Style {
+        self.palette.list_marker(ordered)
+    }
+
+    fn html(&self) -> Style {
+        self.palette.html()
+    }
+
+    fn footnote_ref(&self) -> Style {
+        self.palette.footnote_ref()
+    }
+
+    fn footnote_def(&self) -> Style {
+        self.palette.footnote_def()
+    }
+
+    fn table_header(&self) -> Style {
+        self.palette.table_header()
+    }
+
+    fn table_border(&self) -> Style {
+        self.palette.table_border()
+    }
+
+    fn image_alt(&self) -> Style {
+        self.palette.image_alt()
+    }
+}
+
+/// Returns the foreground of the theme rule matching `scope`, if any rule matches.
+fn scope_color(highlighter: &Highlighter<'_>, scope: &str) -> Option<highlighting::Color> {
+    let scope = Scope::new(scope).ok()?;
+    highlighter.style_mod_for_stack(&[scope]).foreground
+}
+
+/// Composites a possibly translucent `color` over an opaque `background`.
+fn blend(color: highlighting::Color, background: highlighting::Color) -> highlighting::Color {
+    let alpha = u16::from(color.a);
+    let channel = |fg: u8, bg: u8| {
+        let mixed = (u16::from(fg) * alpha + u16::from(bg) * (255 - alpha) + 127) / 255;
+        u8::try_from(mixed).unwrap_or(u8::MAX)
+    };
+    highlighting::Color {
+        r: channel(color.r, background.r),
+        g: channel(color.g, background.g),
+        b: channel(color.b, background.b),
+        a: u8::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use ratatui_core::style::Color;
+
+    use super::*;
+    use crate::{AlertKind, BuiltinCodeTheme};
+
+    #[test]
+    fn uses_markdown_scopes_of_theme() {
+        let styles = CodeThemeStyleSheet::new(&BuiltinCodeTheme::SolarizedLight.into());
+
+        assert_eq!(styles.heading(2).fg, Some(Color::Rgb(0xb5, 0x89, 0x00)));
+        assert_eq!(styles.code().bg, Some(Color::Rgb(0xf0, 0xe9, 0xd7)));
+        assert_ne!(styles.diff_added(), DefaultStyleSheet.diff_added());
+        assert_ne!(styles.diff_removed(), DefaultStyleSheet.diff_removed());
+        assert_ne!(styles.blockquote(), DefaultStyleSheet.blockquote());
+        assert_eq!(
+            styles.alert(AlertKind::Note),
+            DefaultStyleSheet.alert(AlertKind::Note)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_code_scopes_and_foreground() {
+        let source = include_str!("fixtures/custom.tmTheme");
+        let styles = CodeThemeStyleSheet::new(&CodeTheme::from_textmate(source).unwrap());
+        let red = Some(Color::Rgb(0xff, 0, 0));
+        let white = Some(Color::Rgb(0xff, 0xff, 0xff));
+
+        assert_eq!(styles.heading(1).fg, red);
+        assert_eq!(styles.table_header().fg, red);
+        assert_eq!(styles.link().fg, white);
+        assert_eq!(styles.code().bg, Some(Color::Rgb(0, 0, 0)));
+    }
+}
@@ -0,0 +1,149 @@
+//! Rendered Markdown with information about its interactive elements.
+//!
+//! [`Document`] pairs the rendered [`Text`] with the positions of elements that an interactive
+//! viewer may want to act on, such as collapsible `<details>` summaries, footnotes, and code
+//! blocks, and a count of the document's tasks. Applications that only need the text can use
+//! [`crate::from_str_with_options`] instead.
+
+use std::ops::Range;
+
+use ratatui_core::text::Text;
+
+use crate::{CodeBlockInfo, DetailsState};
+
+/// Markdown rendered by [`crate::document_from_str`].
+///
+/// The text may borrow from the Markdown input. Line indices reported by the document refer to
+/// [`Document::text`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document<'a> {
+    text: Text<'a>,
+    details: Vec<DetailsSummary>,
+    footnotes: Vec<Footnote>,
+    tasks: TaskCount,
+    code_blocks: Vec<CodeBlock>,
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn new(
+        text: Text<'a>,
+        details: Vec<DetailsSummary>,
+        footnotes: Vec<Footnote>,
+        tasks: TaskCount,
+        code_blocks: Vec<CodeBlock>,
+    ) -> Self {
+        Self {
+            text,
+            details,
+            footnotes,
+            tasks,
+            code_blocks,
+        }
+    }
+
+    /// Returns the rendered text.
+    #[must_use]
+    pub fn text(&self) -> &Text<'a> {
+        &self.text
+    }
+
+    /// Returns the rendered text, discarding the other document information.
+    #[must_use]
+    pub fn into_text(self) -> Text<'a> {
+        self.text
+    }
+
+    /// Returns the visible `<details>` summaries in document order.
+    ///
+    /// Summaries inside a collapsed element are hidden and therefore not listed.
+    #[must_use]
+    pub fn details(&self) -> &[DetailsSummary] {
+        &self.details
+    }
+
+    /// Returns the footnotes in numbering order, followed by references without a definition.
+    ///
+    /// A viewer can jump from a reference line to [`Footnote::definition`] and back to
+    /// [`Footnote::references`].
+    #[must_use]
+    pub fn footnotes(&self) -> &[Footnote] {
+        &self.footnotes
+    }
+
+    /// Returns the number of completed and total task-list items.
+    ///
+    /// Tasks hidden in a collapsed `<details>` element are not counted.
+    #[must_use]
+    pub fn tasks(&self) -> TaskCount {
+        self.tasks
+    }
+
+    /// Returns the fenced and indented code blocks in document order.
+    ///
+    /// Code blocks in collected footnote definitions or in a collapsed `<details>` element are not
+    /// listed.
+    #[must_use]
+    pub fn code_blocks(&self) -> &[CodeBlock] {
+        &self.code_blocks
+    }
+}
+
+impl<'a> From<Document<'a>> for Text<'a> {
+    fn from(document: Document<'a>) -> Self {
+        document.into_text()
+    }
+}
+
+/// The rendered summary line of a `<details>` element.
+///
+/// Pass [`index`](Self::index) to [`Options::details_at`](crate::Options::details_at) with a new
+/// state to toggle the element when rendering again.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DetailsSummary {
+    /// Position of the element among all `<details>` elements in the document, including hidden
+    /// ones.
+    pub index: usize,
+    /// Index of the summary line in [`Document::text`].
+    pub line: usize,
+    /// Whether the element was rendered collapsed or expanded.
+    pub state: DetailsState,
+}
+
+/// A footnote and the lines where it is referenced and defined.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Footnote {
+    /// The label of the first reference or definition, without the `^`.
+    pub label: String,
+    /// The footnote number, starting at 1, whether or not numbered markers are shown.
+    ///
+    /// References without a definition are not numbered.
+    pub number: Option<usize>,
+    /// Index of the first line of the definition in [`Document::text`].
+    ///
+    /// This is `None` when the definition is missing or hidden in a collapsed `<details>` element.
+    pub definition: Option<usize>,
+    /// Indices of the lines containing each reference in [`Document::text`], in document order.
+    pub references: Vec<usize>,
+}
+
+/// The number of completed and total task-list items in a document.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TaskCount {
+    /// Items whose checkbox is checked.
+    pub completed: usize,
+    /// All task items.
+    pub total: usize,
+}
+
+/// A rendered code block and its parsed info string.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CodeBlock {
+    /// The language and attributes of the block, empty for indented code.
+    pub info: CodeBlockInfo,
+    /// Indices of the lines of code in [`Document::text`], excluding fences and the title.
+    pub lines: Range<usize>,
+}
@@ -4,14 +4,20 @@
 //! [`Options`] value for custom [`StyleSheet`] styles and symbols, image fallback mode, and, when
 //! the `highlight-code` feature is enabled, syntax-highlighting theme.
 //!
+//! [`document_from_str`] also reports the positions of interactive elements.
+//!
 //! The returned text may borrow from the Markdown input. It contains terminal text and styles only;
 //! image syntax produces a configurable text fallback and does not read or render image resources.
 //!
 //! # Markdown output
 //!
 //! Tables use Unicode box-drawing borders, terminal display widths, and the alignment declared by
-//! the Markdown delimiter row. Raw HTML stays visible as literal text. Math retains its delimiters,
-//! and images render as `[img]` followed by their description or destination.
+//! the Markdown delimiter row. Raw HTML stays visible as literal text unless
+//! [`InlineHtmlMode::Interpret`] applies common inline tags or [`HtmlBlockMode`] hides, strips, or
+//! summarizes HTML blocks. With [`Options::details`], `<details>` elements render their summary
+//! after a disclosure marker and hide their content when [`DetailsState::Collapsed`]. Math retains
+//! its delimiters unless [`DisplayMath::Layout`] draws display math across rows, and images render
+//! as `[img]` followed by their description or destination.
 //!
 //! # Syntax highlighting
 //!
@@ -47,15 +53,37 @@
 //! # }
 //! ~~~
 
+mod code_info;
 #[cfg(feature = "highlight-code")]
 mod code_theme;
+mod document;
 mod options;
+mod palette;
+mod preset;
 mod renderer;
+mod style_context;
 mod style_sheet;
+#[cfg(feature = "serde")]
+mod theme_style_sheet;
 
+pub use crate::code_info::CodeBlockInfo;
 #[doc(inline)]
 #[cfg(feature = "highlight-code")]
-pub use crate::code_theme::{BuiltinCodeTheme, CodeTheme, CodeThemeLoadError};
-pub use crate::options::{ImageFallback, Options};
-pub use crate::renderer::{from_str, from_str_with_options};
-pub use crate::style_sheet::{AlertKind, DefaultStyleSheet, StyleSheet};
+pub use crate::code_theme::{BuiltinCodeTheme, CodeTheme, CodeThemeLoadError, CodeThemeStyleSheet};
+pub use crate::document::{CodeBlock, DetailsSummary, Document, Footnote, TaskCount};
+pub use crate::options::{
+    BigHeadings, CodeBlockBackground, CodeLineNumbers, CodeOverflow, CodeWhitespace,
+    CompletedTasks, DelimitedBlocks, DetailsState, DisplayMath, FootnoteLayout, FootnoteNumbering,
+    HeadingDecoration, HeadingNumbering, HtmlBlockMode, ImageFallback, InlineHtmlMode,
+    MermaidDiagrams, Options,
+};
+#[cfg(feature = "highlight-code")]
+pub use crate::options::{CodeLanguageDetection, InlineCodeHighlighting};
+pub use crate::preset::StyleSheetPreset;
+pub use crate::renderer::{document_from_str, from_str, from_str_with_options};
+pub use crate::style_context::{BlockKind, InlineElement, StyleContext};
+pub use crate::style_sheet::{
+    AlertKind, DefaultStyleSheet, ListDelimiter, ListNumbering, StyleSheet, TableBorders,
+};
+#[cfg(feature = "serde")]
+pub use crate::theme_style_sheet::{StyleSheetLoadError, ThemeStyleSheet};
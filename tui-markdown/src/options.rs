@@ -1,12 +1,15 @@
 //! Rendering configuration for tui-markdown.
 //!
-//! Options control the renderer's style sheet, image fallback content, and syntax-highlighting
-//! theme. [`Options`] is non-exhaustive, allowing new rendering choices to be added without
-//! breaking existing code.
+//! Options control the renderer's style sheet, available width, image fallback content, HTML
+//! handling, `<details>` state, heading, task, and footnote presentation, display math layout,
+//! code block backgrounds, and syntax-highlighting theme. [`Options`] is non-exhaustive, allowing
+//! new rendering choices to be added without breaking existing code.
+
+use std::collections::BTreeMap;
 
 #[cfg(feature = "highlight-code")]
-use crate::CodeTheme;
-use crate::{DefaultStyleSheet, StyleSheet};
+use crate::{CodeTheme, CodeThemeStyleSheet};
+use crate::{DefaultStyleSheet, StyleSheet, StyleSheetPreset};
 
 /// Text used to represent Markdown images in rendered terminal output.
 ///
@@ -39,6 +42,579 @@ pub enum ImageFallback {
     AltTextAndUrl,
 }
 
+/// Handling of inline HTML tags such as `<kbd>` and `<b>`.
+///
+/// [`Literal`](Self::Literal) is the default and shows every tag as dim text.
+/// [`Interpret`](Self::Interpret) applies the tags that a terminal can represent.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, InlineHtmlMode, Options};
+///
+/// let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+/// let text = from_str_with_options("Press <kbd>Ctrl</kbd>+<kbd>C</kbd>", &options);
+///
+/// assert_eq!(text.to_string(), "Press Ctrl+C");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InlineHtmlMode {
+    /// Show inline tags as literal text styled with [`StyleSheet::html`].
+    #[default]
+    Literal,
+    /// Map common inline tags onto terminal styles and line breaks.
+    ///
+    /// `<b>`, `<strong>`, `<i>`, `<em>`, `<u>`, `<ins>`, `<s>`, `<del>`, `<sub>`, `<sup>`,
+    /// `<mark>`, and `<code>` style their content, `<kbd>` uses [`StyleSheet::keyboard_key`],
+    /// `<br>` starts a new line, and `<a href>` renders like a Markdown link. Unknown tags, and
+    /// end tags that do not match the innermost interpreted start tag, still render literally.
+    Interpret,
+}
+
+/// Presentation of HTML blocks, such as README badge rows and comments.
+///
+/// [`Literal`](Self::Literal) is the default. When [`Options::details`] is set, `<details>` and
+/// `<summary>` tags are recognized in every mode and this setting applies to the remaining HTML.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, HtmlBlockMode, Options};
+///
+/// let badges = r#"<p align="center"><img alt="CI" src="ci.svg"></p>"#;
+/// let markdown = format!("<!-- badges -->\n{badges}\n\n# Demo");
+/// let options = Options::default().html_block_mode(HtmlBlockMode::Summarize);
+///
+/// assert_eq!(from_str_with_options(&markdown, &options).to_string(), "[img] CI\n\n# Demo");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlBlockMode {
+    /// Show each source line of the block as dim literal text.
+    #[default]
+    Literal,
+    /// Omit HTML blocks from the output.
+    Hide,
+    /// Show the block literally after removing `<!-- … -->` comments and the blank lines they
+    /// leave. A block that contains only comments is omitted.
+    CommentFree,
+    /// Replace the block with its visible text.
+    ///
+    /// Images render like Markdown images according to [`ImageFallback`], links without content
+    /// show their `href`, and block-level elements such as `<p>` and `<br>` start new lines.
+    /// Comments and other tags are removed, and a block without visible content is omitted.
+    Summarize,
+}
+
+/// Whether the content of a `<details>` element is shown.
+///
+/// `<details>` elements render as HTML blocks unless [`Options::details`] selects the state of
+/// elements without an explicit state. The `open` attribute expands such an element.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, DetailsState, Options};
+///
+/// let markdown = "<details>\n<summary>Logs</summary>\n\nLong output.\n\n</details>";
+/// let options = Options::default().details(DetailsState::Collapsed);
+///
+/// assert_eq!(from_str_with_options(markdown, &options).to_string(), "▶ Logs");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetailsState {
+    /// Show only the summary.
+    Collapsed,
+    /// Show the summary followed by the content.
+    #[default]
+    Expanded,
+}
+
+impl DetailsState {
+    /// Returns the opposite state.
+    #[must_use]
+    pub const fn toggled(self) -> Self {
+        match self {
+            Self::Collapsed => Self::Expanded,
+            Self::Expanded => Self::Collapsed,
+        }
+    }
+}
+
+/// Section numbers shown before heading text.
+///
+/// [`Off`](Self::Off) is the default. The numbered variants count headings hierarchically, so a
+/// heading shows the numbers of its enclosing sections followed by its own, as in `1.2.3.`. A level
+/// that is skipped counts as `0`.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, HeadingNumbering, Options};
+///
+/// let markdown = "# Guide\n\n## Install\n\n### Linux\n\n## Usage";
+/// let options = Options::default().heading_numbering(HeadingNumbering::FromH2);
+///
+/// assert_eq!(
+///     from_str_with_options(markdown, &options).to_string(),
+///     "# Guide\n\n## 1. Install\n\n### 1.1. Linux\n\n## 2. Usage"
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingNumbering {
+    /// Show headings without numbers.
+    #[default]
+    Off,
+    /// Number every heading, starting with H1 sections.
+    FromH1,
+    /// Number H2 and deeper headings, leaving H1 titles unnumbered.
+    FromH2,
+}
+
+/// Headings drawn as large block letters.
+///
+/// [`Off`](Self::Off) is the default. Big headings are four rows of half-block letters in the
+/// heading style, preceded by the section number but not the heading marker. Letters are shown as
+/// capitals. A heading falls back to its normal line when it contains a character outside the
+/// bundled font, or when it is wider than [`Options::width`].
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, BigHeadings, Options};
+///
+/// let options = Options::default().big_headings(BigHeadings::H1).width(40);
+///
+/// assert_eq!(
+///     from_str_with_options("# Hi", &options).to_string(),
+///     "█   █  ▀█▀\n█▄▄▄█   █ \n█   █   █ \n▀   ▀  ▀▀▀"
+/// );
+///
+/// let narrow = Options::default().big_headings(BigHeadings::H1).width(8);
+/// assert_eq!(from_str_with_options("# Hi", &narrow).to_string(), "# Hi");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BigHeadings {
+    /// Draw every heading as a normal line.
+    #[default]
+    Off,
+    /// Draw H1 headings in block letters.
+    H1,
+    /// Draw H1 and H2 headings in block letters.
+    H1AndH2,
+}
+
+/// Decoration drawn around a heading.
+///
+/// Select a decoration for each level with [`Options::heading_decoration`]. Rules and box borders
+/// use [`StyleSheet::heading_decoration`](crate::StyleSheet::heading_decoration).
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, HeadingDecoration, Options};
+///
+/// let options = Options::default().heading_decoration(1, HeadingDecoration::Boxed);
+///
+/// assert_eq!(
+///     from_str_with_options("# Title", &options).to_string(),
+///     "┌─────────┐\n│ # Title │\n└─────────┘"
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingDecoration {
+    /// Show the heading line alone.
+    #[default]
+    Plain,
+    /// Draw a rule under the heading, `═` for H1 and `─` for other levels.
+    ///
+    /// The rule spans [`Options::width`] when it is set, and the heading text otherwise.
+    Underline,
+    /// Center the heading line with [`Line`](ratatui_core::text::Line) alignment.
+    Centered,
+    /// Draw a box around the heading text.
+    Boxed,
+}
+
+/// Presentation of the text of completed task-list items.
+///
+/// [`Unchanged`](Self::Unchanged) is the default. The other variants add a style to the content of
+/// items whose checkbox is checked, including nested content. The checkbox itself uses
+/// [`StyleSheet::task_marker_style`](crate::StyleSheet::task_marker_style).
+///
+/// # Example
+///
+/// ```
+/// use ratatui_core::style::{Style, Stylize};
+/// use tui_markdown::{from_str_with_options, CompletedTasks, Options};
+///
+/// let options = Options::default().completed_tasks(CompletedTasks::CrossedOut);
+/// let text = from_str_with_options("- [x] Done", &options);
+///
+/// assert_eq!(text.lines[0].spans[1].style, Style::new().crossed_out());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompletedTasks {
+    /// Render completed items like other items.
+    #[default]
+    Unchanged,
+    /// Dim the text of completed items.
+    Dim,
+    /// Strike through the text of completed items.
+    CrossedOut,
+}
+
+/// Placement of footnote definitions in rendered terminal output.
+///
+/// [`InPlace`](Self::InPlace) is the default and renders each definition where it appears in the
+/// source. [`Collected`](Self::Collected) moves every definition into a trailing section, as GitHub
+/// does.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, FootnoteLayout, Options};
+///
+/// let markdown = "[^note]: Defined first.\n\nText[^note].";
+/// let options = Options::default().footnote_layout(FootnoteLayout::Collected);
+///
+/// assert_eq!(
+///     from_str_with_options(markdown, &options).to_string(),
+///     "Text[note].\n\nFootnotes\n\n[note]: Defined first."
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FootnoteLayout {
+    /// Render each definition where it appears in the source.
+    #[default]
+    InPlace,
+    /// Render all definitions after the document under a "Footnotes" title.
+    ///
+    /// Definitions are ordered by their first reference. Definitions that are never referenced
+    /// follow in source order.
+    Collected,
+}
+
+/// Markers used for footnote references and definitions.
+///
+/// [`Label`](Self::Label) is the default and repeats the label written in the source. The numbered
+/// variants number footnotes in order of first appearance, or in order of first reference with
+/// [`FootnoteLayout::Collected`].
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, FootnoteNumbering, Options};
+///
+/// let markdown = "Text[^note].\n\n[^note]: Details.";
+/// let options = Options::default().footnote_numbering(FootnoteNumbering::Superscript);
+///
+/// assert_eq!(
+///     from_str_with_options(markdown, &options).to_string(),
+///     "Text¹.\n\n¹ Details."
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FootnoteNumbering {
+    /// Show the source label, as in `[note]` and `[note]: `.
+    #[default]
+    Label,
+    /// Show a bracketed number, as in `[1]` and `[1]: `.
+    Bracketed,
+    /// Show a superscript number, as in `¹` and `¹ `.
+    Superscript,
+}
+
+/// Presentation of display math (`$$...$$`) in rendered terminal output.
+///
+/// [`Source`](Self::Source) is the default and keeps the TeX source visible.
+/// [`Layout`](Self::Layout) draws a supported subset of TeX across several terminal rows.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, DisplayMath, Options};
+///
+/// let options = Options::default().display_math(DisplayMath::Layout);
+/// let text = from_str_with_options(r"$$\frac{a}{b}$$", &options);
+///
+/// assert_eq!(text.to_string(), "a\n─\nb");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMath {
+    /// Show `$$`, the TeX source, and `$$`, keeping each source line as a physical line.
+    #[default]
+    Source,
+    /// Lay out fractions, roots, scripts, large operators with limits, and matrices across rows.
+    ///
+    /// Greek letters and common operators become Unicode symbols. Math that uses a command or
+    /// environment outside this subset renders as [`Source`](Self::Source) instead.
+    Layout,
+}
+
+/// Background of fenced and indented code blocks in rendered terminal output.
+///
+/// [`Text`](Self::Text) is the default and colors only the code itself. [`Panel`](Self::Panel)
+/// pads every line of the block to a common width, so the background forms a solid rectangle.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, CodeBlockBackground, Options};
+///
+/// let options = Options::default()
+///     .code_block_background(CodeBlockBackground::Panel)
+///     .code_block_padding(1);
+/// let text = from_str_with_options("```\nlet x;\nx\n```", &options);
+///
+/// assert_eq!(text.to_string(), "```\n let x; \n x      \n```");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeBlockBackground {
+    /// Color the characters of each line only.
+    #[default]
+    Text,
+    /// Fill each line to the width of the block.
+    ///
+    /// The block is as wide as its longest line plus [`Options::code_block_padding`] on both
+    /// sides, or [`Options::width`] when that is wider. Highlighted code uses the background of
+    /// the code theme; other code uses [`StyleSheet::code`].
+    Panel,
+}
+
+/// Line numbers in the gutter of fenced and indented code blocks.
+///
+/// Numbers are right-aligned to the widest number in the block and styled with
+/// [`StyleSheet::code_line_number`]. [`Hidden`](Self::Hidden) is the default.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, CodeLineNumbers, Options};
+///
+/// let options = Options::default().code_line_numbers(CodeLineNumbers::Shown);
+/// let text = from_str_with_options("```\nlet x;\nx\n```", &options);
+///
+/// assert_eq!(text.to_string(), "```\n1 │ let x;\n2 │ x\n```");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLineNumbers {
+    /// Show code without a gutter.
+    #[default]
+    Hidden,
+    /// Number every line of code, starting at one.
+    Shown,
+}
+
+/// Presentation of tabs and trailing whitespace in fenced and indented code blocks.
+///
+/// Tabs are always expanded to spaces at [`Options::tab_width`] stops. [`Hidden`](Self::Hidden) is
+/// the default and leaves the expanded whitespace blank. [`Visible`](Self::Visible) marks it with
+/// [`StyleSheet::code_whitespace`], as a code review tool would.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, CodeWhitespace, Options};
+///
+/// let options = Options::default().code_whitespace(CodeWhitespace::Visible);
+/// let text = from_str_with_options("```\nif x {\n\tgo();  \n}\n```", &options);
+///
+/// assert_eq!(text.to_string(), "```\nif x {\n→   go();··\n}\n```");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeWhitespace {
+    /// Show expanded tabs and trailing spaces as blank columns.
+    #[default]
+    Hidden,
+    /// Start each expanded tab with `→` and replace trailing spaces with `·`.
+    Visible,
+}
+
+/// Handling of code block lines wider than [`Options::width`].
+///
+/// [`Unwrapped`](Self::Unwrapped) is the default and leaves long lines whole, for a viewer that
+/// scrolls horizontally or a `Paragraph` that wraps them. [`Wrap`](Self::Wrap) and
+/// [`Truncate`](Self::Truncate) fit each line to the width left after line prefixes, the gutter,
+/// and panel padding, marking the change with [`StyleSheet::code_overflow`]. Without a width,
+/// every line is left whole.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, CodeOverflow, Options};
+///
+/// let markdown = "```\nlet total = first + second;\n```";
+/// let options = Options::default().width(12).code_overflow(CodeOverflow::Wrap);
+/// let text = from_str_with_options(markdown, &options);
+///
+/// assert_eq!(text.to_string(), "```\nlet total =\n↪ first +\n↪ second;\n```");
+///
+/// let options = Options::default().width(12).code_overflow(CodeOverflow::Truncate);
+/// let text = from_str_with_options(markdown, &options);
+///
+/// assert_eq!(text.to_string(), "```\nlet total =…\n```");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeOverflow {
+    /// Leave long lines whole.
+    #[default]
+    Unwrapped,
+    /// Continue long lines on following rows that repeat the indentation and start with `↪`.
+    ///
+    /// Lines break at the last space that fits, or within a word when there is none. Line
+    /// numbers, emphasis, and diff styles apply to every row of a line.
+    Wrap,
+    /// Cut long lines to fit and end them with `…`.
+    Truncate,
+}
+
+/// Presentation of fenced blocks of delimited data, such as ` ```csv ` and ` ```tsv `.
+///
+/// [`Code`](Self::Code) is the default and renders the data like any other code block.
+/// [`Table`](Self::Table) parses comma- or tab-separated values, including quoted fields, and
+/// renders them like a Markdown table whose first record is the header.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, DelimitedBlocks, Options};
+///
+/// let options = Options::default().delimited_blocks(DelimitedBlocks::Table);
+/// let text = from_str_with_options("```csv\nname,qty\n\"Smith, J\",12\n```", &options);
+///
+/// assert_eq!(
+///     text.to_string(),
+///     "┌──────────┬─────┐\n\
+///      │ name     │ qty │\n\
+///      ├──────────┼─────┤\n\
+///      │ Smith, J │  12 │\n\
+///      └──────────┴─────┘"
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DelimitedBlocks {
+    /// Render delimited data as code.
+    #[default]
+    Code,
+    /// Render `csv` blocks as comma-separated and `tsv` blocks as tab-separated tables.
+    ///
+    /// Columns whose body values are all numbers are right-aligned.
+    Table,
+}
+
+/// Detection of the language of code blocks that do not name one.
+///
+/// Detection applies to indented code and to fenced code without a language. A fenced language,
+/// including an unrecognized one, is never overridden. [`Disabled`](Self::Disabled) is the default.
+///
+/// # Example
+///
+/// ```
+/// use ratatui_core::style::Color;
+/// use tui_markdown::{from_str_with_options, CodeLanguageDetection, Options};
+///
+/// let options = Options::default().code_language_detection(CodeLanguageDetection::Content);
+/// let text = from_str_with_options("```\n#!/usr/bin/env python\nprint(1)\n```", &options);
+///
+/// let first_line = &text.lines[1];
+/// assert_eq!(first_line.to_string(), "#!/usr/bin/env python");
+/// assert!(first_line.spans.iter().all(|span| matches!(span.style.fg, Some(Color::Rgb(..)))));
+/// ```
+#[cfg(feature = "highlight-code")]
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguageDetection {
+    /// Render code without a language unhighlighted.
+    #[default]
+    Disabled,
+    /// Select the syntax from the first line of code.
+    ///
+    /// Shebangs such as `#!/bin/sh`, editor mode lines, XML and HTML declarations, shell prompts,
+    /// JSON documents, and unified diffs are recognized.
+    Content,
+}
+
+/// Highlighting of inline code that names its language.
+///
+/// [`Disabled`](Self::Disabled) is the default and styles all inline code with
+/// [`StyleSheet::code`]. [`Tagged`](Self::Tagged) highlights inline code followed by a
+/// Pandoc-style attribute, as in `` `let x = 1`{.rust} ``, or starting with a language and a colon,
+/// as in `` `rs: let x = 1` ``, using the syntax-highlighting theme of fenced code blocks.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, InlineCodeHighlighting, Options};
+///
+/// let options = Options::default().inline_code_highlighting(InlineCodeHighlighting::Tagged);
+/// let text = from_str_with_options("Use `let x = 1`{.rust} or `rs: x + 1`.", &options);
+///
+/// assert_eq!(text.to_string(), "Use let x = 1 or x + 1.");
+/// ```
+#[cfg(feature = "highlight-code")]
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InlineCodeHighlighting {
+    /// Render inline code unhighlighted, keeping any attribute or prefix as text.
+    #[default]
+    Disabled,
+    /// Highlight inline code whose language is given by an attribute or a prefix.
+    ///
+    /// Languages resolve like those of fenced code blocks, including configured aliases. The
+    /// attribute is removed even when its language is unknown. A prefix is only removed when it
+    /// names a known language, so code such as `` `note: x` `` is left as it is.
+    Tagged,
+}
+
+/// Presentation of ` ```mermaid ` code blocks in rendered terminal output.
+///
+/// [`Source`](Self::Source) is the default and renders the diagram definition like any other code
+/// block. [`Layout`](Self::Layout) draws simple flowcharts and sequence diagrams with box-drawing
+/// characters.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, MermaidDiagrams, Options};
+///
+/// let options = Options::default().mermaid_diagrams(MermaidDiagrams::Layout);
+/// let text = from_str_with_options("```mermaid\ngraph LR\nA --> B\n```", &options);
+///
+/// assert_eq!(
+///     text.to_string(),
+///     "┌───┐    ┌───┐\n\
+///      │ A ├───▶│ B │\n\
+///      └───┘    └───┘"
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MermaidDiagrams {
+    /// Render diagram definitions as code.
+    #[default]
+    Source,
+    /// Lay out `graph` and `flowchart` definitions as boxes joined by arrows, and
+    /// `sequenceDiagram` definitions as participants with lifelines and messages.
+    ///
+    /// Diagrams that use syntax outside this subset, such as subgraphs, cycles, or `loop` blocks,
+    /// or that are wider than [`Options::width`], render as [`Source`](Self::Source) instead.
+    Layout,
+}
+
 /// Rendering options for [`crate::from_str_with_options`].
 ///
 /// `S` is the style sheet consulted while Markdown events are rendered. [`Options::default`] uses
@@ -75,25 +651,101 @@ pub struct Options<S: StyleSheet = DefaultStyleSheet> {
     /// The [`StyleSheet`] implementation that will be consulted every time the renderer needs a
     /// style or symbol choice.
     pub(crate) styles: S,
+    /// The number of terminal columns available to the text, if known.
+    pub(crate) width: Option<u16>,
     /// The content to render in place of images.
     pub(crate) image_fallback: ImageFallback,
+    /// The handling of inline HTML tags.
+    pub(crate) inline_html_mode: InlineHtmlMode,
+    /// The presentation of HTML blocks.
+    pub(crate) html_block_mode: HtmlBlockMode,
+    /// The state of `<details>` elements without an explicit state or `open` attribute, or `None`
+    /// to render the elements as HTML blocks.
+    pub(crate) details: Option<DetailsState>,
+    /// Explicit `<details>` states by document index.
+    pub(crate) details_states: BTreeMap<usize, DetailsState>,
+    /// The section numbers shown before heading text.
+    pub(crate) heading_numbering: HeadingNumbering,
+    /// The heading levels drawn in block letters.
+    pub(crate) big_headings: BigHeadings,
+    /// The decoration of each heading level, starting with H1.
+    pub(crate) heading_decorations: [HeadingDecoration; 6],
+    /// The presentation of completed task-list items.
+    pub(crate) completed_tasks: CompletedTasks,
+    /// The placement of footnote definitions.
+    pub(crate) footnote_layout: FootnoteLayout,
+    /// The markers used for footnote references and definitions.
+    pub(crate) footnote_numbering: FootnoteNumbering,
+    /// The presentation of display math.
+    pub(crate) display_math: DisplayMath,
+    /// The background of code blocks.
+    pub(crate) code_block_background: CodeBlockBackground,
+    /// The columns of padding on each side of code block panels.
+    pub(crate) code_block_padding: u16,
+    /// The line numbers shown beside code blocks.
+    pub(crate) code_line_numbers: CodeLineNumbers,
+    /// The columns between tab stops.
+    pub(crate) tab_width: u16,
+    /// The presentation of tabs and trailing whitespace in code blocks.
+    pub(crate) code_whitespace: CodeWhitespace,
+    /// The handling of code lines wider than the width.
+    pub(crate) code_overflow: CodeOverflow,
+    /// The presentation of `csv` and `tsv` code blocks.
+    pub(crate) delimited_blocks: DelimitedBlocks,
+    /// The presentation of `mermaid` code blocks.
+    pub(crate) mermaid_diagrams: MermaidDiagrams,
     /// Explicit syntax-highlighting theme for fenced code blocks.
     ///
     /// When absent, the renderer uses the shared built-in default.
     #[cfg(feature = "highlight-code")]
     code_theme: Option<CodeTheme>,
+    /// Additional fenced languages by lowercase alias, consulted before the built-in aliases.
+    #[cfg(feature = "highlight-code")]
+    pub(crate) code_language_aliases: BTreeMap<String, String>,
+    /// The detection of the language of code blocks that do not name one.
+    #[cfg(feature = "highlight-code")]
+    pub(crate) code_language_detection: CodeLanguageDetection,
+    /// The highlighting of inline code that names its language.
+    #[cfg(feature = "highlight-code")]
+    pub(crate) inline_code_highlighting: InlineCodeHighlighting,
 }
 
 impl<S: StyleSheet> Options<S> {
     /// Creates rendering options that use `styles`.
     ///
-    /// Image fallback and syntax-highlighting settings retain their defaults.
+    /// All other settings retain their defaults.
     pub fn new(styles: S) -> Self {
         Self {
             styles,
+            width: None,
             image_fallback: ImageFallback::default(),
+            inline_html_mode: InlineHtmlMode::default(),
+            html_block_mode: HtmlBlockMode::default(),
+            details: None,
+            details_states: BTreeMap::new(),
+            heading_numbering: HeadingNumbering::default(),
+            big_headings: BigHeadings::default(),
+            heading_decorations: [HeadingDecoration::default(); 6],
+            completed_tasks: CompletedTasks::default(),
+            footnote_layout: FootnoteLayout::default(),
+            footnote_numbering: FootnoteNumbering::default(),
+            display_math: DisplayMath::default(),
+            code_block_background: CodeBlockBackground::default(),
+            code_block_padding: 1,
+            code_line_numbers: CodeLineNumbers::default(),
+            tab_width: 4,
+            code_whitespace: CodeWhitespace::default(),
+            code_overflow: CodeOverflow::default(),
+            delimited_blocks: DelimitedBlocks::default(),
+            mermaid_diagrams: MermaidDiagrams::default(),
             #[cfg(feature = "highlight-code")]
             code_theme: None,
+            #[cfg(feature = "highlight-code")]
+            code_language_aliases: BTreeMap::new(),
+            #[cfg(feature = "highlight-code")]
+            code_language_detection: CodeLanguageDetection::default(),
+            #[cfg(feature = "highlight-code")]
+            inline_code_highlighting: InlineCodeHighlighting::default(),
         }
     }
 
@@ -106,6 +758,189 @@ impl<S: StyleSheet> Options<S> {
         self
     }
 
+    /// Selects whether inline HTML tags render literally or are interpreted.
+    ///
+    /// See [`InlineHtmlMode`] for the recognized tags.
+    #[must_use]
+    pub fn inline_html_mode(mut self, inline_html_mode: InlineHtmlMode) -> Self {
+        self.inline_html_mode = inline_html_mode;
+        self
+    }
+
+    /// Selects how HTML blocks are presented.
+    ///
+    /// See [`HtmlBlockMode`] for the output of each mode.
+    #[must_use]
+    pub fn html_block_mode(mut self, html_block_mode: HtmlBlockMode) -> Self {
+        self.html_block_mode = html_block_mode;
+        self
+    }
+
+    /// Renders `<details>` elements as disclosure controls, collapsed or expanded by default.
+    ///
+    /// Without this setting, the elements render as HTML blocks according to
+    /// [`Self::html_block_mode`]. Elements with the `open` attribute are expanded unless
+    /// [`Self::details_at`] sets their state.
+    #[must_use]
+    pub fn details(mut self, state: DetailsState) -> Self {
+        self.details = Some(state);
+        self
+    }
+
+    /// Sets the state of the `<details>` element at `index` in document order.
+    ///
+    /// Indices count every element, including nested and hidden ones, and match
+    /// [`DetailsSummary::index`](crate::DetailsSummary::index) reported by
+    /// [`crate::document_from_str`]. An interactive viewer toggles an element by rendering again
+    /// with its new state. Explicit states apply only when [`Self::details`] is set.
+    #[must_use]
+    pub fn details_at(mut self, index: usize, state: DetailsState) -> Self {
+        self.details_states.insert(index, state);
+        self
+    }
+
+    /// Sets the number of terminal columns available to the rendered text.
+    ///
+    /// Layouts that fill the available width, such as [`HeadingDecoration::Underline`], use it,
+    /// and [`BigHeadings`] checks that block letters fit. Without a width, layouts fit their
+    /// content instead.
+    #[must_use]
+    pub fn width(mut self, width: u16) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Selects the section numbers shown before heading text.
+    #[must_use]
+    pub fn heading_numbering(mut self, heading_numbering: HeadingNumbering) -> Self {
+        self.heading_numbering = heading_numbering;
+        self
+    }
+
+    /// Selects the heading levels drawn in block letters.
+    ///
+    /// Set [`Options::width`] so that headings too wide for the terminal keep their normal line.
+    #[must_use]
+    pub fn big_headings(mut self, big_headings: BigHeadings) -> Self {
+        self.big_headings = big_headings;
+        self
+    }
+
+    /// Selects the decoration of headings at `level`.
+    ///
+    /// `level` is one-based (`1` for `# H1`, …); levels above 6 select H6.
+    #[must_use]
+    pub fn heading_decoration(mut self, level: u8, decoration: HeadingDecoration) -> Self {
+        let index = usize::from(level.clamp(1, 6)) - 1;
+        self.heading_decorations[index] = decoration;
+        self
+    }
+
+    /// Selects how the text of completed task-list items is presented.
+    #[must_use]
+    pub fn completed_tasks(mut self, completed_tasks: CompletedTasks) -> Self {
+        self.completed_tasks = completed_tasks;
+        self
+    }
+
+    /// Selects where footnote definitions are rendered.
+    ///
+    /// See [`FootnoteLayout`] for the order of collected definitions.
+    #[must_use]
+    pub fn footnote_layout(mut self, footnote_layout: FootnoteLayout) -> Self {
+        self.footnote_layout = footnote_layout;
+        self
+    }
+
+    /// Selects the markers used for footnote references and definitions.
+    #[must_use]
+    pub fn footnote_numbering(mut self, footnote_numbering: FootnoteNumbering) -> Self {
+        self.footnote_numbering = footnote_numbering;
+        self
+    }
+
+    /// Selects how display math is presented.
+    ///
+    /// See [`DisplayMath`] for the supported layouts and the fallback for unsupported TeX.
+    #[must_use]
+    pub fn display_math(mut self, display_math: DisplayMath) -> Self {
+        self.display_math = display_math;
+        self
+    }
+
+    /// Selects whether code block backgrounds cover the code only or fill the block's width.
+    #[must_use]
+    pub fn code_block_background(mut self, code_block_background: CodeBlockBackground) -> Self {
+        self.code_block_background = code_block_background;
+        self
+    }
+
+    /// Sets the columns of padding on each side of code in
+    /// [`CodeBlockBackground::Panel`] blocks.
+    ///
+    /// The default is one column.
+    #[must_use]
+    pub fn code_block_padding(mut self, code_block_padding: u16) -> Self {
+        self.code_block_padding = code_block_padding;
+        self
+    }
+
+    /// Selects whether code blocks show line numbers in a gutter.
+    ///
+    /// Independently of this setting, the info string of a fenced code block can emphasize lines
+    /// with [`StyleSheet::code_highlighted_line`], using either `{3,5-7}` or `hl_lines="3 5-7"`.
+    #[must_use]
+    pub fn code_line_numbers(mut self, code_line_numbers: CodeLineNumbers) -> Self {
+        self.code_line_numbers = code_line_numbers;
+        self
+    }
+
+    /// Sets the columns between the tab stops that tabs in code and text expand to.
+    ///
+    /// Tabs are replaced with spaces, so every rendered span is as wide as it is displayed,
+    /// regardless of the terminal's own tab stops. The default is four columns, and zero is treated
+    /// as one.
+    #[must_use]
+    pub fn tab_width(mut self, tab_width: u16) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Selects whether code blocks mark tabs and trailing spaces.
+    #[must_use]
+    pub fn code_whitespace(mut self, code_whitespace: CodeWhitespace) -> Self {
+        self.code_whitespace = code_whitespace;
+        self
+    }
+
+    /// Selects whether code lines wider than [`Options::width`] are left whole, wrapped, or
+    /// truncated.
+    #[must_use]
+    pub fn code_overflow(mut self, code_overflow: CodeOverflow) -> Self {
+        self.code_overflow = code_overflow;
+        self
+    }
+
+    /// Selects whether `csv` and `tsv` code blocks render as code or as tables.
+    ///
+    /// Tables use the same borders and styles as Markdown tables, including
+    /// [`StyleSheet::table_header`] and [`StyleSheet::table_cell`].
+    #[must_use]
+    pub fn delimited_blocks(mut self, delimited_blocks: DelimitedBlocks) -> Self {
+        self.delimited_blocks = delimited_blocks;
+        self
+    }
+
+    /// Selects whether `mermaid` code blocks render as their source or as diagrams.
+    ///
+    /// Diagrams are styled with [`StyleSheet::mermaid_diagram`]. Source that cannot be laid out
+    /// keeps the code block styles and syntax highlighting.
+    #[must_use]
+    pub fn mermaid_diagrams(mut self, mermaid_diagrams: MermaidDiagrams) -> Self {
+        self.mermaid_diagrams = mermaid_diagrams;
+        self
+    }
+
     /// Selects the syntax-highlighting theme for fenced code blocks.
     ///
     /// By default, no explicit theme is stored and the renderer borrows its shared
@@ -130,6 +965,49 @@ impl<S: StyleSheet> Options<S> {
         self
     }
 
+    /// Highlights fenced code labeled `alias` as `language`.
+    ///
+    /// `language` is a language name or file extension known to the highlighter, such as `bash` or
+    /// `py`. Aliases are matched case-insensitively and take precedence over the built-in aliases,
+    /// which cover common labels such as `shell`, `jsonc`, `python3`, and `golang`. An alias to
+    /// `console` renders the code as a shell session.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_markdown::Options;
+    ///
+    /// let options = Options::default().code_language_alias("nushell", "bash");
+    /// ```
+    #[cfg(feature = "highlight-code")]
+    #[must_use]
+    pub fn code_language_alias(
+        mut self,
+        alias: impl Into<String>,
+        language: impl Into<String>,
+    ) -> Self {
+        let alias = alias.into().to_lowercase();
+        self.code_language_aliases.insert(alias, language.into());
+        self
+    }
+
+    /// Selects whether code blocks without a language are highlighted by their content.
+    #[cfg(feature = "highlight-code")]
+    #[must_use]
+    pub fn code_language_detection(mut self, detection: CodeLanguageDetection) -> Self {
+        self.code_language_detection = detection;
+        self
+    }
+
+    /// Selects whether inline code tagged with a language, such as `` `x`{.rust} ``, is
+    /// highlighted.
+    #[cfg(feature = "highlight-code")]
+    #[must_use]
+    pub fn inline_code_highlighting(mut self, highlighting: InlineCodeHighlighting) -> Self {
+        self.inline_code_highlighting = highlighting;
+        self
+    }
+
     /// Returns the explicitly configured syntax-highlighting theme.
     ///
     /// Returns `None` when the renderer will use the shared
@@ -141,6 +1019,48 @@ impl<S: StyleSheet> Options<S> {
     }
 }
 
+impl Options<StyleSheetPreset> {
+    /// Creates rendering options that use a built-in style sheet preset.
+    ///
+    /// With the `highlight-code` feature, the preset's matching
+    /// [`code_theme`](StyleSheetPreset::code_theme) is selected too. For
+    /// [`StyleSheetPreset::LowColor`], the theme's colors are mapped to the eight basic ANSI colors
+    /// with [`CodeTheme::with_basic_colors`]. All other settings retain their defaults.
+    #[must_use]
+    pub fn preset(preset: StyleSheetPreset) -> Self {
+        let options = Self::new(preset);
+        #[cfg(feature = "highlight-code")]
+        let options = match preset {
+            StyleSheetPreset::LowColor => {
+                options.code_theme(CodeTheme::from(preset.code_theme()).with_basic_colors())
+            }
+            _ => options.code_theme(preset.code_theme()),
+        };
+        options
+    }
+}
+
+#[cfg(feature = "highlight-code")]
+impl Options<CodeThemeStyleSheet> {
+    /// Creates rendering options whose style sheet is derived from a syntax-highlighting theme.
+    ///
+    /// The theme highlights fenced code and also supplies the Markdown colors through
+    /// [`CodeThemeStyleSheet`]. All other settings retain their defaults.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tui_markdown::{BuiltinCodeTheme, Options};
+    ///
+    /// let options = Options::from_code_theme(BuiltinCodeTheme::InspiredGitHub);
+    /// ```
+    #[must_use]
+    pub fn from_code_theme(code_theme: impl Into<CodeTheme>) -> Self {
+        let code_theme = code_theme.into();
+        Self::new(CodeThemeStyleSheet::new(&code_theme)).code_theme(code_theme)
+    }
+}
+
 impl Default for Options<DefaultStyleSheet> {
     fn default() -> Self {
         Self::new(DefaultStyleSheet)
@@ -162,6 +1082,29 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "highlight-code")]
+    #[test]
+    fn preset_selects_matching_code_theme() {
+        let options = Options::preset(StyleSheetPreset::SolarizedLight);
+        let expected = Options::default().code_theme(crate::BuiltinCodeTheme::SolarizedLight);
+
+        assert_eq!(options.styles, StyleSheetPreset::SolarizedLight);
+        assert_eq!(
+            format!("{:?}", options.selected_code_theme()),
+            format!("{:?}", expected.selected_code_theme())
+        );
+    }
+
+    #[cfg(feature = "highlight-code")]
+    #[test]
+    fn from_code_theme_derives_style_sheet() {
+        let theme = crate::BuiltinCodeTheme::SolarizedDark;
+        let options = Options::from_code_theme(theme);
+
+        assert_eq!(options.styles, CodeThemeStyleSheet::new(&theme.into()));
+        assert!(options.selected_code_theme().is_some());
+    }
+
     #[test]
     fn custom_style_sheet() {
         #[derive(Debug, Clone)]
@@ -178,9 +1121,35 @@ mod tests {
 
         let options = Options {
             styles: CustomStyleSheet,
+            width: None,
             image_fallback: ImageFallback::default(),
+            inline_html_mode: InlineHtmlMode::default(),
+            html_block_mode: HtmlBlockMode::default(),
+            details: None,
+            details_states: BTreeMap::new(),
+            heading_numbering: HeadingNumbering::default(),
+            big_headings: BigHeadings::default(),
+            heading_decorations: [HeadingDecoration::default(); 6],
+            completed_tasks: CompletedTasks::default(),
+            footnote_layout: FootnoteLayout::default(),
+            footnote_numbering: FootnoteNumbering::default(),
+            display_math: DisplayMath::default(),
+            code_block_background: CodeBlockBackground::default(),
+            code_block_padding: 1,
+            code_line_numbers: CodeLineNumbers::default(),
+            tab_width: 4,
+            code_whitespace: CodeWhitespace::default(),
+            code_overflow: CodeOverflow::default(),
+            delimited_blocks: DelimitedBlocks::default(),
+            mermaid_diagrams: MermaidDiagrams::default(),
             #[cfg(feature = "highlight-code")]
             code_theme: None,
+            #[cfg(feature = "highlight-code")]
+            code_language_aliases: BTreeMap::new(),
+            #[cfg(feature = "highlight-code")]
+            code_language_detection: CodeLanguageDetection::default(),
+            #[cfg(feature = "highlight-code")]
+            inline_code_highlighting: InlineCodeHighlighting::default(),
         };
 
         assert_eq!(options.styles.heading(1), Style::new().red().bold());
@@ -207,6 +1176,93 @@ mod tests {
         assert_eq!(options.image_fallback, ImageFallback::AltTextAndUrl);
     }
 
+    #[test]
+    fn html_block_mode_setter_updates_mode() {
+        let options = Options::default().html_block_mode(HtmlBlockMode::Summarize);
+
+        assert_eq!(options.html_block_mode, HtmlBlockMode::Summarize);
+    }
+
+    #[test]
+    fn details_setters_update_states() {
+        let options = Options::default()
+            .details(DetailsState::Collapsed)
+            .details_at(2, DetailsState::Expanded);
+
+        assert_eq!(options.details, Some(DetailsState::Collapsed));
+        assert_eq!(
+            options.details_states.get(&2),
+            Some(&DetailsState::Expanded)
+        );
+    }
+
+    #[test]
+    fn heading_setters_update_layout() {
+        let options = Options::default()
+            .width(80)
+            .heading_numbering(HeadingNumbering::FromH1)
+            .heading_decoration(2, HeadingDecoration::Underline);
+
+        assert_eq!(options.width, Some(80));
+        assert_eq!(options.heading_numbering, HeadingNumbering::FromH1);
+        assert_eq!(options.heading_decorations[1], HeadingDecoration::Underline);
+        assert_eq!(options.heading_decorations[0], HeadingDecoration::Plain);
+        assert_eq!(
+            Options::default()
+                .big_headings(BigHeadings::H1AndH2)
+                .big_headings,
+            BigHeadings::H1AndH2
+        );
+    }
+
+    #[test]
+    fn completed_tasks_setter_updates_mode() {
+        let options = Options::default().completed_tasks(CompletedTasks::Dim);
+
+        assert_eq!(options.completed_tasks, CompletedTasks::Dim);
+    }
+
+    #[test]
+    fn footnote_setters_update_modes() {
+        let options = Options::default()
+            .footnote_layout(FootnoteLayout::Collected)
+            .footnote_numbering(FootnoteNumbering::Bracketed);
+
+        assert_eq!(options.footnote_layout, FootnoteLayout::Collected);
+        assert_eq!(options.footnote_numbering, FootnoteNumbering::Bracketed);
+    }
+
+    #[test]
+    fn display_math_setter_updates_mode() {
+        let options = Options::default().display_math(DisplayMath::Layout);
+
+        assert_eq!(options.display_math, DisplayMath::Layout);
+    }
+
+    #[test]
+    fn code_block_setters_update_layout() {
+        let options = Options::default()
+            .code_block_background(CodeBlockBackground::Panel)
+            .code_block_padding(2)
+            .code_line_numbers(CodeLineNumbers::Shown)
+            .tab_width(8)
+            .code_whitespace(CodeWhitespace::Visible)
+            .code_overflow(CodeOverflow::Truncate)
+            .delimited_blocks(DelimitedBlocks::Table)
+            .mermaid_diagrams(MermaidDiagrams::Layout);
+
+        assert_eq!(options.code_block_background, CodeBlockBackground::Panel);
+        assert_eq!(options.code_block_padding, 2);
+        assert_eq!(options.code_line_numbers, CodeLineNumbers::Shown);
+        assert_eq!(options.tab_width, 8);
+        assert_eq!(options.code_whitespace, CodeWhitespace::Visible);
+        assert_eq!(options.code_overflow, CodeOverflow::Truncate);
+        assert_eq!(options.delimited_blocks, DelimitedBlocks::Table);
+        assert_eq!(options.mermaid_diagrams, MermaidDiagrams::Layout);
+        assert_eq!(Options::default().code_block_padding, 1);
+        assert_eq!(Options::default().tab_width, 4);
+    }
+
     #[test]
     #[cfg(feature = "highlight-code")]
     fn default_has_no_explicit_code_theme() {
@@ -215,6 +1271,28 @@ mod tests {
         assert!(options.selected_code_theme().is_none());
     }
 
+    #[test]
+    #[cfg(feature = "highlight-code")]
+    fn code_language_setters_update_highlighting() {
+        let options = Options::default()
+            .code_language_alias("NuShell", "bash")
+            .code_language_detection(CodeLanguageDetection::Content)
+            .inline_code_highlighting(InlineCodeHighlighting::Tagged);
+
+        assert_eq!(
+            options.code_language_aliases.get("nushell"),
+            Some(&"bash".to_owned())
+        );
+        assert_eq!(
+            options.code_language_detection,
+            CodeLanguageDetection::Content
+        );
+        assert_eq!(
+            options.inline_code_highlighting,
+            InlineCodeHighlighting::Tagged
+        );
+    }
+
     #[test]
     #[cfg(feature = "highlight-code")]
     fn code_theme_selects_theme() {
@@ -0,0 +1,153 @@
+//! Element styles derived from a small set of base styles.
+//!
+//! [`StyleSheetPreset`](crate::StyleSheetPreset) and, with the `highlight-code` feature,
+//! [`CodeThemeStyleSheet`](crate::CodeThemeStyleSheet) both describe a color scheme with a few
+//! base styles. [`Palette`] turns those into the styles of individual elements, so headings, list
+//! markers, table headers, and other elements look alike whichever of the two picks the colors.
+
+use ratatui_core::style::Style;
+
+use crate::StyleSheet;
+
+/// Base styles of a color scheme.
+///
+/// The [`StyleSheet`] implementation covers the hooks a palette can inform; the rest keep their
+/// default styles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Palette {
+    /// Headings and heading decorations.
+    pub heading: Style,
+    /// Inline code.
+    pub code: Style,
+    /// Links, which are also underlined.
+    pub link: Style,
+    /// Blockquote text.
+    pub blockquote: Style,
+    /// Secondary text, such as heading attributes, footnotes, table borders, and the gutters and
+    /// prompts of code blocks.
+    pub muted: Style,
+    /// Metadata blocks.
+    pub metadata: Style,
+    /// Ordered list numbers, diff hunk headers, and Mermaid diagrams.
+    pub marker: Style,
+    /// Table headers, which are also bold.
+    pub table_header: Style,
+    /// Code block lines emphasized by the info string.
+    pub highlighted_line: Style,
+    /// Added lines of diffs.
+    pub diff_added: Style,
+    /// Removed lines of diffs.
+    pub diff_removed: Style,
+}
+
+impl StyleSheet for Palette {
+    fn heading(&self, level: u8) -> Style {
+        let style = self.heading;
+        match level {
+            1 => style.bold().underlined(),
+            2 => style.bold(),
+            3 => style.bold().italic(),
+            _ => style.italic(),
+        }
+    }
+
+    fn code(&self) -> Style {
+        self.code
+    }
+
+    fn link(&self) -> Style {
+        self.link.underlined()
+    }
+
+    fn blockquote(&self) -> Style {
+        self.blockquote
+    }
+
+    fn heading_decoration(&self, _level: u8) -> Style {
+        self.heading
+    }
+
+    fn heading_meta(&self) -> Style {
+        self.muted
+    }
+
+    fn metadata_block(&self) -> Style {
+        self.metadata
+    }
+
+    fn code_block_title_style(&self) -> Style {
+        self.heading.bold()
+    }
+
+    fn code_line_number(&self) -> Style {
+        self.muted
+    }
+
+    fn code_highlighted_line(&self) -> Style {
+        self.highlighted_line
+    }
+
+    fn code_whitespace(&self) -> Style {
+        self.muted
+    }
+
+    fn code_overflow(&self) -> Style {
+        self.muted
+    }
+
+    fn shell_prompt(&self) -> Style {
+        self.muted
+    }
+
+    fn diff_added(&self) -> Style {
+        self.diff_added
+    }
+
+    fn diff_removed(&self) -> Style {
+        self.diff_removed
+    }
+
+    fn diff_hunk_header(&self) -> Style {
+        self.marker
+    }
+
+    fn diff_file_header(&self) -> Style {
+        self.heading.bold()
+    }
+
+    fn mermaid_diagram(&self) -> Style {
+        self.marker
+    }
+
+    fn list_marker(&self, ordered: bool) -> Style {
+        if ordered {
+            self.marker
+        } else {
+            Style::default()
+        }
+    }
+
+    fn html(&self) -> Style {
+        self.muted
+    }
+
+    fn footnote_ref(&self) -> Style {
+        self.muted.italic()
+    }
+
+    fn footnote_def(&self) -> Style {
+        self.muted
+    }
+
+    fn table_header(&self) -> Style {
+        self.table_header.bold()
+    }
+
+    fn table_border(&self) -> Style {
+        self.muted
+    }
+
+    fn image_alt(&self) -> Style {
+        self.muted.italic()
+    }
+}
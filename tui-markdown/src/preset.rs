@@ -0,0 +1,461 @@
+//! Ready-made style sheets for light and dark terminals.
+//!
+//! [`DefaultStyleSheet`](crate::DefaultStyleSheet) assumes a dark background. Each
+//! [`StyleSheetPreset`] instead targets a terminal color scheme and, with the `highlight-code`
+//! feature, names the bundled syntax-highlighting theme that matches it. Select a preset and its
+//! code theme together with [`Options::preset`](crate::Options::preset).
+
+use ratatui_core::style::{Color, Modifier, Style};
+
+use crate::palette::Palette;
+#[cfg(feature = "highlight-code")]
+use crate::BuiltinCodeTheme;
+use crate::{AlertKind, StyleSheet};
+
+/// A built-in style sheet for a terminal color scheme.
+///
+/// Every preset implements [`StyleSheet`], so it can also be passed to
+/// [`Options::new`](crate::Options::new) directly. Presets change colors and text attributes only;
+/// markers, fences, and other symbols keep their default text.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{from_str_with_options, Options, StyleSheetPreset};
+///
+/// let options = Options::preset(StyleSheetPreset::GitHubLight);
+/// let text = from_str_with_options("# Title", &options);
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StyleSheetPreset {
+    /// GitHub's light color scheme, for light terminal backgrounds.
+    GitHubLight,
+    /// GitHub's dark color scheme, for dark terminal backgrounds.
+    GitHubDark,
+    /// The light Solarized palette.
+    SolarizedLight,
+    /// The dark Solarized palette.
+    SolarizedDark,
+    /// Bright ANSI colors on a dark terminal background, for low-vision use.
+    HighContrast,
+    /// Text attributes and the eight basic ANSI colors only, for terminals without 256-color or
+    /// true-color support.
+    LowColor,
+}
+
+impl StyleSheetPreset {
+    /// All presets, in declaration order.
+    pub const ALL: [Self; 6] = [
+        Self::GitHubLight,
+        Self::GitHubDark,
+        Self::SolarizedLight,
+        Self::SolarizedDark,
+        Self::HighContrast,
+        Self::LowColor,
+    ];
+
+    /// The bundled syntax-highlighting theme that matches this preset.
+    ///
+    /// For [`Self::LowColor`], [`Options::preset`](crate::Options::preset) maps the theme's colors
+    /// to the eight basic ANSI colors.
+    #[cfg(feature = "highlight-code")]
+    #[must_use]
+    pub const fn code_theme(self) -> BuiltinCodeTheme {
+        match self {
+            Self::GitHubLight => BuiltinCodeTheme::InspiredGitHub,
+            Self::GitHubDark | Self::LowColor => BuiltinCodeTheme::Base16OceanDark,
+            Self::SolarizedLight => BuiltinCodeTheme::SolarizedLight,
+            Self::SolarizedDark => BuiltinCodeTheme::SolarizedDark,
+            Self::HighContrast => BuiltinCodeTheme::Base16EightiesDark,
+        }
+    }
+
+    const fn colors(self) -> &'static PresetColors {
+        match self {
+            Self::GitHubLight => &GITHUB_LIGHT,
+            Self::GitHubDark => &GITHUB_DARK,
+            Self::SolarizedLight => &SOLARIZED_LIGHT,
+            Self::SolarizedDark => &SOLARIZED_DARK,
+            Self::HighContrast => &HIGH_CONTRAST,
+            Self::LowColor => &LOW_COLOR,
+        }
+    }
+}
+
+/// Colors shared by the styles of a preset.
+struct PresetColors {
+    palette: Palette,
+    math: Color,
+    /// Alert colors in `Note`, `Tip`, `Important`, `Warning`, `Caution` order.
+    alerts: [Color; 5],
+}
+
+const fn rgb(hex: u32) -> Color {
+    Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+}
+
+const GITHUB_LIGHT: PresetColors = PresetColors {
+    palette: Palette {
+        heading: Style::new().fg(rgb(0x0550ae)),
+        code: Style::new().fg(rgb(0x1f2328)).bg(rgb(0xeff1f3)),
+        link: Style::new().fg(rgb(0x0969da)),
+        blockquote: Style::new().fg(rgb(0x59636e)),
+        muted: Style::new().fg(rgb(0x6e7781)),
+        metadata: Style::new().fg(rgb(0x953800)),
+        marker: Style::new().fg(rgb(0x0550ae)),
+        table_header: Style::new().fg(rgb(0x0550ae)),
+        highlighted_line: Style::new().bg(rgb(0xfff8c5)),
+        diff_added: Style::new().bg(rgb(0xdafbe1)),
+        diff_removed: Style::new().bg(rgb(0xffebe9)),
+    },
+    math: rgb(0x8250df),
+    alerts: [
+        rgb(0x0969da),
+        rgb(0x1a7f37),
+        rgb(0x8250df),
+        rgb(0x9a6700),
+        rgb(0xd1242f),
+    ],
+};
+
+const GITHUB_DARK: PresetColors = PresetColors {
+    palette: Palette {
+        heading: Style::new().fg(rgb(0x58a6ff)),
+        code: Style::new().fg(rgb(0xe6edf3)).bg(rgb(0x262c36)),
+        link: Style::new().fg(rgb(0x4493f8)),
+        blockquote: Style::new().fg(rgb(0x9198a1)),
+        muted: Style::new().fg(rgb(0x9198a1)),
+        metadata: Style::new().fg(rgb(0xffa657)),
+        marker: Style::new().fg(rgb(0x79c0ff)),
+        table_header: Style::new().fg(rgb(0x58a6ff)),
+        highlighted_line: Style::new().bg(rgb(0x272215)),
+        diff_added: Style::new().bg(rgb(0x12261e)),
+        diff_removed: Style::new().bg(rgb(0x301b1e)),
+    },
+    math: rgb(0xd2a8ff),
+    alerts: [
+        rgb(0x4493f8),
+        rgb(0x3fb950),
+        rgb(0xab7df8),
+        rgb(0xd29922),
+        rgb(0xf85149),
+    ],
+};
+
+const SOLARIZED_LIGHT: PresetColors = PresetColors {
+    palette: Palette {
+        heading: Style::new().fg(rgb(0xcb4b16)),
+        code: Style::new().fg(rgb(0x586e75)).bg(rgb(0xeee8d5)),
+        link: Style::new().fg(rgb(0x268bd2)),
+        blockquote: Style::new().fg(rgb(0x2aa198)),
+        muted: Style::new().fg(rgb(0x93a1a1)),
+        metadata: Style::new().fg(rgb(0xb58900)),
+        marker: Style::new().fg(rgb(0x6c71c4)),
+        table_header: Style::new().fg(rgb(0xcb4b16)),
+        highlighted_line: Style::new().bg(rgb(0xeee8d5)),
+        diff_added: Style::new().bg(rgb(0xe5e3b6)),
+        diff_removed: Style::new().bg(rgb(0xf6cfbf)),
+    },
+    math: rgb(0xd33682),
+    alerts: [
+        rgb(0x268bd2),
+        rgb(0x859900),
+        rgb(0x6c71c4),
+        rgb(0xb58900),
+        rgb(0xdc322f),
+    ],
+};
+
+const SOLARIZED_DARK: PresetColors = PresetColors {
+    palette: Palette {
+        heading: Style::new().fg(rgb(0xcb4b16)),
+        code: Style::new().fg(rgb(0x93a1a1)).bg(rgb(0x073642)),
+        link: Style::new().fg(rgb(0x268bd2)),
+        blockquote: Style::new().fg(rgb(0x2aa198)),
+        muted: Style::new().fg(rgb(0x586e75)),
+        metadata: Style::new().fg(rgb(0xb58900)),
+        marker: Style::new().fg(rgb(0x6c71c4)),
+        table_header: Style::new().fg(rgb(0xcb4b16)),
+        highlighted_line: Style::new().bg(rgb(0x073642)),
+        diff_added: Style::new().bg(rgb(0x214628)),
+        diff_removed: Style::new().bg(rgb(0x372d34)),
+    },
+    math: rgb(0xd33682),
+    alerts: [
+        rgb(0x268bd2),
+        rgb(0x859900),
+        rgb(0x6c71c4),
+        rgb(0xb58900),
+        rgb(0xdc322f),
+    ],
+};
+
+const HIGH_CONTRAST: PresetColors = PresetColors {
+    palette: Palette {
+        heading: Style::new().fg(Color::LightYellow),
+        code: Style::new().fg(Color::Black).bg(Color::White),
+        link: Style::new().fg(Color::LightCyan),
+        blockquote: Style::new().fg(Color::LightGreen),
+        muted: Style::new().fg(Color::White),
+        metadata: Style::new().fg(Color::LightMagenta),
+        marker: Style::new().fg(Color::LightCyan),
+        table_header: Style::new().fg(Color::LightYellow),
+        highlighted_line: Style::new().bg(Color::Blue),
+        diff_added: Style::new().bg(Color::Green),
+        diff_removed: Style::new().bg(Color::Red),
+    },
+    math: Color::LightMagenta,
+    alerts: [
+        Color::LightBlue,
+        Color::LightGreen,
+        Color::LightMagenta,
+        Color::LightYellow,
+        Color::LightRed,
+    ],
+};
+
+const LOW_COLOR: PresetColors = PresetColors {
+    palette: Palette {
+        heading: Style::new(),
+        code: Style::new().fg(Color::Yellow),
+        link: Style::new().fg(Color::Blue),
+        blockquote: Style::new().fg(Color::Green),
+        muted: Style::new().add_modifier(Modifier::DIM),
+        metadata: Style::new().fg(Color::Yellow),
+        marker: Style::new().fg(Color::Blue),
+        table_header: Style::new(),
+        highlighted_line: Style::new().add_modifier(Modifier::REVERSED),
+        diff_added: Style::new().fg(Color::Green),
+        diff_removed: Style::new().fg(Color::Red),
+    },
+    math: Color::Magenta,
+    alerts: [
+        Color::Blue,
+        Color::Green,
+        Color::Magenta,
+        Color::Yellow,
+        Color::Red,
+    ],
+};
+
+impl StyleSheet for StyleSheetPreset {
+    fn heading(&self, level: u8) -> Style {
+        self.colors().palette.heading(level)
+    }
+
+    fn code(&self) -> Style {
+        self.colors().palette.code()
+    }
+
+    fn link(&self) -> Style {
+        self.colors().palette.link()
+    }
+
+    fn blockquote(&self) -> Style {
+        self.colors().palette.blockquote()
+    }
+
+    fn heading_decoration(&self, level: u8) -> Style {
+        self.colors().palette.heading_decoration(level)
+    }
+
+    fn heading_meta(&self) -> Style {
+        self.colors().palette.heading_meta()
+    }
+
+    fn metadata_block(&self) -> Style {
+        self.colors().palette.metadata_block()
+    }
+
+    fn code_block_title_style(&self) -> Style {
+        self.colors().palette.code_block_title_style()
+    }
+
+    fn code_line_number(&self) -> Style {
+        self.colors().palette.code_line_number()
+    }
+
+    fn code_highlighted_line(&self) -> Style {
+        self.colors().palette.code_highlighted_line()
+    }
+
+    fn code_whitespace(&self) -> Style {
+        self.colors().palette.code_whitespace()
+    }
+
+    fn code_overflow(&self) -> Style {
+        self.colors().palette.code_overflow()
+    }
+
+    fn shell_prompt(&self) -> Style {
+        self.colors().palette.shell_prompt()
+    }
+
+    fn diff_added(&self) -> Style {
+        self.colors().palette.diff_added()
+    }
+
+    fn diff_removed(&self) -> Style {
+        self.colors().palette.diff_removed()
+    }
+
+    fn diff_hunk_header(&self) -> Style {
+        self.colors().palette.diff_hunk_header()
+    }
+
+    fn diff_file_header(&self) -> Style {
+        self.colors().palette.diff_file_header()
+    }
+
+    fn mermaid_diagram(&self) -> Style {
+        self.colors().palette.mermaid_diagram()
+    }
+
+    fn list_marker(&self, ordered: bool) -> Style {
+        self.colors().palette.list_marker(ordered)
+    }
+
+    fn html(&self) -> Style {
+        self.colors().palette.html()
+    }
+
+    fn math_inline(&self) -> Style {
+        Style::new().fg(self.colors().math).italic()
+    }
+
+    fn math_display(&self) -> Style {
+        Style::new().fg(self.colors().math)
+    }
+
+    fn footnote_ref(&self) -> Style {
+        self.colors().palette.footnote_ref()
+    }
+
+    fn footnote_def(&self) -> Style {
+        self.colors().palette.footnote_def()
+    }
+
+    fn footnote_missing(&self) -> Style {
+        self.alert(AlertKind::Caution)
+    }
+
+    fn alert(&self, kind: AlertKind) -> Style {
+        let index = match kind {
+            AlertKind::Note => 0,
+            AlertKind::Tip => 1,
+            AlertKind::Important => 2,
+            AlertKind::Warning => 3,
+            AlertKind::Caution => 4,
+        };
+        Style::new().fg(self.colors().alerts[index])
+    }
+
+    fn table_header(&self) -> Style {
+        self.colors().palette.table_header()
+    }
+
+    fn table_border(&self) -> Style {
+        self.colors().palette.table_border()
+    }
+
+    fn image_alt(&self) -> Style {
+        self.colors().palette.image_alt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::DefaultStyleSheet;
+
+    #[test]
+    fn light_presets_do_not_assume_a_dark_background() {
+        for preset in [
+            StyleSheetPreset::GitHubLight,
+            StyleSheetPreset::SolarizedLight,
+        ] {
+            assert_eq!(preset.heading(1).bg, None, "{preset:?}");
+            assert_ne!(preset.code(), DefaultStyleSheet.code(), "{preset:?}");
+            assert_ne!(preset.metadata_block(), DefaultStyleSheet.metadata_block());
+            assert_ne!(
+                preset.code_highlighted_line(),
+                DefaultStyleSheet.code_highlighted_line(),
+                "{preset:?}"
+            );
+            assert_ne!(
+                preset.diff_added(),
+                DefaultStyleSheet.diff_added(),
+                "{preset:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn low_color_uses_basic_colors() {
+        let preset = StyleSheetPreset::LowColor;
+        let styles = [
+            preset.heading(1),
+            preset.code(),
+            preset.link(),
+            preset.blockquote(),
+            preset.metadata_block(),
+            preset.list_marker(true),
+            preset.math_inline(),
+            preset.alert(AlertKind::Warning),
+            preset.table_border(),
+            preset.code_line_number(),
+            preset.code_highlighted_line(),
+            preset.code_whitespace(),
+            preset.shell_prompt(),
+            preset.mermaid_diagram(),
+            preset.diff_added(),
+            preset.diff_removed(),
+        ];
+
+        for style in styles {
+            for color in [style.fg, style.bg].into_iter().flatten() {
+                assert!(
+                    !matches!(color, Color::Rgb(..) | Color::Indexed(_)),
+                    "{style:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "highlight-code")]
+    #[test]
+    fn low_color_highlights_code_with_basic_colors() {
+        let options = crate::Options::preset(StyleSheetPreset::LowColor)
+            .code_block_background(crate::CodeBlockBackground::Panel);
+        let text = crate::from_str_with_options("```rust\nfn main() {}\n```", &options);
+
+        let colors = text
+            .lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .flat_map(|span| [span.style.fg, span.style.bg])
+            .flatten();
+        for color in colors {
+            assert!(
+                !matches!(color, Color::Rgb(..) | Color::Indexed(_)),
+                "{color:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "highlight-code")]
+    #[test]
+    fn presets_pair_with_matching_code_themes() {
+        assert_eq!(
+            StyleSheetPreset::GitHubLight.code_theme(),
+            BuiltinCodeTheme::InspiredGitHub
+        );
+        assert_eq!(
+            StyleSheetPreset::SolarizedDark.code_theme(),
+            BuiltinCodeTheme::SolarizedDark
+        );
+    }
+}
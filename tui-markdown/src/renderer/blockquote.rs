@@ -63,7 +63,7 @@ where
     }
 }
 
-fn alert_kind(kind: BlockQuoteKind) -> AlertKind {
+pub fn alert_kind(kind: BlockQuoteKind) -> AlertKind {
     match kind {
         BlockQuoteKind::Note => AlertKind::Note,
         BlockQuoteKind::Tip => AlertKind::Tip,
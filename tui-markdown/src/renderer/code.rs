@@ -1,7 +1,29 @@
 //! Markdown inline and fenced code rendering.
 //!
 //! Inline code and unrecognized fences use the style sheet's code style. With `highlight-code`
-//! enabled, a recognized fenced language uses the selected syntax-highlighting theme.
+//! enabled, a recognized fenced language uses the selected syntax-highlighting theme. So does
+//! inline code that names its language with a `{.language}` attribute or a `language: ` prefix,
+//! when `InlineCodeHighlighting::Tagged` is selected.
+//!
+//! Languages are resolved through the aliases configured in [`Options`](crate::Options), then the
+//! built-in aliases. Blocks without a language can have one detected from their first line of
+//! code, which arrives after the block starts, so the highlighter is chosen when that text is
+//! written.
+//!
+//! The info string of a fenced block is parsed into a [`CodeBlockInfo`]. Only its language selects
+//! the syntax and follows the opening fence. A title attribute adds a header line, line ranges such
+//! as `{3,5-7}` emphasize lines, and [`CodeLineNumbers::Shown`] adds a gutter with line numbers.
+//!
+//! With [`CodeBlockBackground::Panel`], every line of a code block is padded to a common width once
+//! the block ends, so its background forms a solid rectangle.
+//!
+//! Shell sessions, fenced as `console` or `shell-session`, style their prompts through the style
+//! sheet, highlight the commands after them as Bash, and leave output lines unhighlighted.
+//!
+//! Diffs, fenced as `diff` or `patch`, style added, removed, and header lines through the style
+//! sheet, and `diff-rust` and similar languages highlight the code in each line.
+//!
+//! Each finished block is recorded with its info and lines for [`crate::Document::code_blocks`].
 
 #[cfg(feature = "highlight-code")]
 use std::sync::LazyLock;
@@ -9,13 +31,12 @@ use std::sync::LazyLock;
 #[cfg(feature = "highlight-code")]
 use ansi_to_tui::IntoText;
 use pulldown_cmark::{CodeBlockKind, CowStr, Event};
-#[cfg(feature = "highlight-code")]
-use ratatui_core::text::Text;
+use ratatui_core::style::Style;
 use ratatui_core::text::{Line, Span};
 #[cfg(feature = "highlight-code")]
 use syntect::{
     easy::HighlightLines,
-    parsing::SyntaxSet,
+    parsing::{SyntaxReference, SyntaxSet},
     util::{as_24_bit_terminal_escaped, LinesWithEndings},
 };
 #[cfg(feature = "highlight-code")]
@@ -24,35 +45,245 @@ use tracing::{debug, instrument, warn};
 use super::TextWriter;
 #[cfg(feature = "highlight-code")]
 use crate::code_theme::{self, CodeTheme};
-use crate::StyleSheet;
+use crate::{
+    CodeBlock, CodeBlockBackground, CodeBlockInfo, CodeLineNumbers, InlineElement, StyleSheet,
+};
+#[cfg(feature = "highlight-code")]
+use crate::{CodeLanguageDetection, InlineCodeHighlighting};
 
 #[cfg(feature = "highlight-code")]
 static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
 
+/// Languages of shell sessions, whose commands are highlighted as Bash after a prompt.
+#[cfg(feature = "highlight-code")]
+const SHELL_SESSION_LANGUAGES: &[&str] = &[
+    "console",
+    "shell-session",
+    "shellsession",
+    "sh-session",
+    "terminal",
+];
+
+/// Prompts that start a command in a shell session.
+#[cfg(feature = "highlight-code")]
+const SHELL_PROMPTS: &[&str] = &["$ ", "# ", "> "];
+
+/// Fenced languages the highlighter does not know, with a token it does.
+#[cfg(feature = "highlight-code")]
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("shell", "bash"),
+    ("jsonc", "json"),
+    ("json5", "json"),
+    ("python3", "py"),
+    ("py2", "py"),
+    ("golang", "go"),
+    ("csharp", "cs"),
+    ("typescript", "js"),
+    ("ts", "js"),
+    ("jsx", "js"),
+    ("tsx", "js"),
+    ("psql", "sql"),
+];
+
+/// The state of a shell session code block between lines.
+#[cfg(feature = "highlight-code")]
+#[derive(Debug, Default)]
+pub struct ShellSession {
+    /// Whether the previous command ended with a `\` line continuation.
+    continuation: bool,
+}
+
+#[cfg(feature = "highlight-code")]
+impl ShellSession {
+    /// Splits a command line into its prompt and command, or returns `None` for output.
+    ///
+    /// A line continuing the previous command has an empty prompt.
+    fn command<'l>(&mut self, line: &'l str) -> Option<(&'l str, &'l str)> {
+        let command = if self.continuation {
+            Some(("", line))
+        } else {
+            SHELL_PROMPTS.iter().find_map(|prompt| {
+                let command = line.strip_prefix(prompt)?;
+                Some((&line[..prompt.len()], command))
+            })
+        };
+        self.continuation = command.is_some() && line.trim_end().ends_with('\\');
+        command
+    }
+}
+
+/// How the lines of a highlighted code block are split before highlighting.
+#[cfg(feature = "highlight-code")]
+#[derive(Debug, Default)]
+pub enum CodeLines {
+    /// Every line is code.
+    #[default]
+    Code,
+    /// Lines after a prompt are commands; other lines are output.
+    ShellSession(ShellSession),
+    /// Lines of a diff whose code, after the `+`, `-`, or space marker, is highlighted.
+    Diff,
+}
+
+/// The kind of a line in a unified diff.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffLine {
+    /// A `diff`, `index`, `---`, or `+++` line naming the changed files.
+    FileHeader,
+    /// A `@@` line starting a hunk.
+    HunkHeader,
+    /// A line starting with `+`.
+    Added,
+    /// A line starting with `-`.
+    Removed,
+    /// A line starting with a space.
+    Context,
+    /// Any other line, such as `\ No newline at end of file`.
+    Other,
+}
+
+impl DiffLine {
+    fn of(line: &str) -> Self {
+        const FILE_HEADERS: [&str; 4] = ["diff ", "index ", "--- ", "+++ "];
+        if FILE_HEADERS.iter().any(|header| line.starts_with(header)) {
+            Self::FileHeader
+        } else if line.starts_with("@@") {
+            Self::HunkHeader
+        } else if line.starts_with('+') {
+            Self::Added
+        } else if line.starts_with('-') {
+            Self::Removed
+        } else if line.starts_with(' ') {
+            Self::Context
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// A code block being rendered, laid out when the block ends.
+pub struct OpenCodeBlock {
+    /// Index of the first line of code, after the opening fence.
+    first_line: usize,
+    /// Style patched under the code and used for padding, when the block is a panel.
+    panel: Option<Style>,
+    /// Whether the block is a diff, whose lines are styled by kind.
+    diff: bool,
+    /// The parsed info string.
+    info: CodeBlockInfo,
+}
+
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
     I: Iterator<Item = Event<'a>>,
     S: StyleSheet,
 {
     pub fn code(&mut self, code: CowStr<'a>) {
+        let code_style = self.element_style(InlineElement::Code, self.styles.code());
         let style = if self.images.is_empty() {
-            self.styles.code()
+            code_style
         } else {
-            let inline_style = self.inline_styles.last().copied().unwrap_or_default();
-            inline_style.patch(self.styles.code())
+            let inline_style = self.inline_style();
+            inline_style.patch(code_style)
         };
 
+        #[cfg(feature = "highlight-code")]
+        if self.inline_code_highlighting == InlineCodeHighlighting::Tagged {
+            self.tagged_inline_code(code, style);
+            return;
+        }
+        let code = self.expand_tabs(code);
+
         self.push_span(Span::styled(code, style));
     }
 
+    /// Renders inline code, highlighting it when a `{.language}` attribute after it or a
+    /// `language: ` prefix names a known language.
+    ///
+    /// Syntax colors are patched over the inline code style. The attribute is removed from the
+    /// text that follows.
+    #[cfg(feature = "highlight-code")]
+    fn tagged_inline_code(&mut self, code: CowStr<'a>, style: Style) {
+        let attribute = self.inline_code_attribute();
+        let (syntax, code) = if let Some((language, _)) = &attribute {
+            (self.inline_code_syntax(language), code)
+        } else {
+            let prefixed = code.split_once(": ").and_then(|(language, snippet)| {
+                Some((self.inline_code_syntax(language)?, snippet.to_owned()))
+            });
+            match prefixed {
+                Some((syntax, snippet)) => (Some(syntax), CowStr::from(snippet)),
+                None => (None, code),
+            }
+        };
+        let code = self.expand_tabs(code);
+
+        match syntax {
+            Some(syntax) => {
+                let mut highlighter = self.code_highlighter_for(syntax);
+                let spans = highlight_line(&mut highlighter, self.selected_code_theme(), &code)
+                    .into_iter()
+                    .flat_map(|line| line.spans);
+                for span in spans {
+                    self.push_span(Span::styled(span.content, style.patch(span.style)));
+                }
+            }
+            None => self.push_span(Span::styled(code, style)),
+        }
+
+        if let Some((_, length)) = attribute {
+            if let Some(Event::Text(text)) = self.iter.next() {
+                let rest = &text[length..];
+                if !rest.is_empty() {
+                    self.text(CowStr::from(rest.to_owned()));
+                }
+            }
+        }
+    }
+
+    /// Returns the language of a `{.language}` attribute that starts the next text event, and the
+    /// length of the attribute.
+    #[cfg(feature = "highlight-code")]
+    fn inline_code_attribute(&mut self) -> Option<(String, usize)> {
+        let attribute = match self.iter.peek() {
+            Some(Event::Text(text)) => text.strip_prefix("{.").and_then(|rest| {
+                let (language, _) = rest.split_once('}')?;
+                let is_class = !language.is_empty()
+                    && !language.contains(|c: char| c.is_whitespace() || matches!(c, '{' | '.'));
+                is_class.then(|| (language.to_owned(), language.len() + 3))
+            }),
+            _ => None,
+        };
+        self.iter.reset_peek();
+        attribute
+    }
+
+    /// Finds the syntax of an inline code language, resolving aliases as for fenced code.
+    #[cfg(feature = "highlight-code")]
+    fn inline_code_syntax(&self, language: &str) -> Option<&'static SyntaxReference> {
+        if language.is_empty() || language.contains(char::is_whitespace) {
+            return None;
+        }
+        find_syntax(self.resolve_code_language(language)).map(|(syntax, _)| syntax)
+    }
+
     pub fn start_codeblock(&mut self, kind: CodeBlockKind<'_>) {
+        let info = match kind {
+            CodeBlockKind::Fenced(ref info) => CodeBlockInfo::parse(info),
+            CodeBlockKind::Indented => CodeBlockInfo::default(),
+        };
+        if self.start_delimited_block(info.language()) || self.start_mermaid_block(&info) {
+            return;
+        }
+        self.open_code_block(info);
+    }
+
+    /// Writes the opening fence and title of a code block and starts collecting its lines.
+    pub fn open_code_block(&mut self, info: CodeBlockInfo) {
         if !self.text.lines.is_empty() {
             self.push_line(Line::default());
         }
-        let lang = match kind {
-            CodeBlockKind::Fenced(ref lang) => lang.as_ref(),
-            CodeBlockKind::Indented => "",
-        };
+        let lang = info.language();
 
         #[cfg(not(feature = "highlight-code"))]
         self.line_styles.push(self.styles.code());
@@ -65,10 +296,27 @@ where
             let span = Span::from(format!("{fence}{lang}"));
             self.push_line(span.into());
         }
+        if let Some(title) = self.styles.code_block_title(&info) {
+            let span = Span::styled(title.to_owned(), self.styles.code_block_title_style());
+            self.push_line(span.into());
+        }
         self.needs_newline = true;
+
+        let panel = (self.code_block_background == CodeBlockBackground::Panel)
+            .then(|| self.code_panel_style());
+        self.code_block = Some(OpenCodeBlock {
+            first_line: self.text.lines.len(),
+            panel,
+            diff: diff_language(info.language()).is_some(),
+            info,
+        });
     }
 
     pub fn end_codeblock(&mut self) {
+        if self.end_delimited_block() || self.end_mermaid_block() {
+            return;
+        }
+        self.finish_code_block();
         let fence = self.styles.code_block_fence();
         if !fence.is_empty() {
             let span = Span::from(fence.to_owned());
@@ -83,25 +331,48 @@ where
         self.clear_code_highlighter();
     }
 
-    #[cfg(feature = "highlight-code")]
-    pub fn with_code_theme(mut self, theme: Option<&'theme CodeTheme>) -> Self {
-        self.code_theme = theme;
-        self
-    }
-
     #[cfg(feature = "highlight-code")]
     pub fn push_highlighted_text(&mut self, text: &str) -> bool {
+        if std::mem::take(&mut self.code_language_pending) {
+            self.detect_code_language(text);
+        }
+        let code_theme = self.selected_code_theme();
         let Some(highlighter) = &mut self.code_highlighter else {
             return false;
         };
-        let text: Text = LinesWithEndings::from(text)
-            .filter_map(|line| highlighter.highlight_line(line, &SYNTAX_SET).ok())
-            .filter_map(|part| as_24_bit_terminal_escaped(&part, false).into_text().ok())
-            .flatten()
-            .collect();
-
-        for line in text.lines {
-            self.text.push_line(line);
+        let mut lines = vec![];
+        for line in LinesWithEndings::from(text) {
+            let code_line = match &mut self.code_lines {
+                CodeLines::Code => {
+                    lines.extend(highlight_line(highlighter, code_theme, line));
+                    continue;
+                }
+                CodeLines::ShellSession(session) => match session.command(line) {
+                    Some((prompt, command)) => {
+                        let prompt = (!prompt.is_empty())
+                            .then(|| Span::styled(prompt.to_owned(), self.styles.shell_prompt()));
+                        let command = highlight_line(highlighter, code_theme, command)
+                            .into_iter()
+                            .flat_map(|line| line.spans);
+                        Line::from_iter(prompt.into_iter().chain(command))
+                    }
+                    None => plain_line(line, self.styles.shell_output()),
+                },
+                CodeLines::Diff => match DiffLine::of(line) {
+                    DiffLine::Added | DiffLine::Removed | DiffLine::Context => {
+                        let (marker, code) = line.split_at(1);
+                        let code = highlight_line(highlighter, code_theme, code)
+                            .into_iter()
+                            .flat_map(|line| line.spans);
+                        Line::from_iter(std::iter::once(Span::raw(marker.to_owned())).chain(code))
+                    }
+                    _ => plain_line(line, Style::default()),
+                },
+            };
+            lines.push(code_line);
+        }
+        for line in lines {
+            self.push_line(line);
         }
         self.needs_newline = false;
         true
@@ -112,34 +383,328 @@ where
         false
     }
 
+    /// Returns the panel style of the current code block.
+    ///
+    /// Highlighted code sits on the background of its theme. Other code, and themes without a
+    /// background, use the style sheet's code style.
+    fn code_panel_style(&self) -> Style {
+        #[cfg(feature = "highlight-code")]
+        if self.code_highlighter.is_some() {
+            let code_theme = self.selected_code_theme();
+            if let Some(background) = code_theme::theme(code_theme).settings.background {
+                let background = code_theme::terminal_color(code_theme, background);
+                return Style::new().bg(background);
+            }
+        }
+        self.styles.code()
+    }
+
+    /// Lays out the lines of the current code block.
+    ///
+    /// Tabs are expanded first, so every width below is a display width, and long lines are then
+    /// wrapped or truncated. Panels are filled to the width of the longest line plus padding on
+    /// both sides, or to the available width after line prefixes and the gutter when that is wider.
+    /// Emphasized lines are then restyled, and the gutter is inserted after the line prefixes.
+    fn finish_code_block(&mut self) {
+        let Some(block) = self.code_block.take() else {
+            return;
+        };
+        let has_prefixes = !self.line_prefixes.is_empty();
+        let prefix_count = self.line_prefixes.len() + usize::from(has_prefixes);
+        let prefix_width =
+            self.line_prefixes.iter().map(Span::width).sum::<usize>() + usize::from(has_prefixes);
+        self.expand_code_whitespace(block.first_line, prefix_count);
+        let line_count = self.text.lines.len() - block.first_line;
+        let separator = self.styles.code_line_number_separator().to_owned();
+        let number_style = self.styles.code_line_number();
+        let digits = (self.code_line_numbers == CodeLineNumbers::Shown)
+            .then(|| line_count.to_string().len());
+        let gutter_width = digits.map_or(0, |digits| digits + 1 + Span::raw(&separator).width());
+        let content_width = |line: &Line| {
+            line.spans[prefix_count..]
+                .iter()
+                .map(Span::width)
+                .sum::<usize>()
+        };
+
+        let available = self.width.map_or(0, |available| {
+            usize::from(available).saturating_sub(prefix_width + gutter_width)
+        });
+        let diff_styles: Vec<_> = if block.diff {
+            let styles = &self.styles;
+            self.text.lines[block.first_line..]
+                .iter()
+                .map(|line| {
+                    let content: String = line.spans[prefix_count..]
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect();
+                    match DiffLine::of(&content) {
+                        DiffLine::FileHeader => Some(styles.diff_file_header()),
+                        DiffLine::HunkHeader => Some(styles.diff_hunk_header()),
+                        DiffLine::Added => Some(styles.diff_added()),
+                        DiffLine::Removed => Some(styles.diff_removed()),
+                        DiffLine::Context | DiffLine::Other => None,
+                    }
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        let padding = usize::from(self.code_block_padding);
+        let limit = self.width.map(|_| match block.panel {
+            Some(_) => available.saturating_sub(2 * padding),
+            None => available,
+        });
+        let rows = self.fit_code_lines(block.first_line, prefix_count, limit);
+
+        let lines = &mut self.text.lines[block.first_line..];
+        if let Some(style) = block.panel {
+            let longest = lines.iter().map(content_width).max().unwrap_or_default();
+            let width = available.max(longest + 2 * padding);
+            for line in lines.iter_mut() {
+                let fill = width - padding - content_width(line);
+                for span in &mut line.spans[prefix_count..] {
+                    span.style = style.patch(span.style);
+                }
+                if padding > 0 {
+                    let span = Span::styled(" ".repeat(padding), style);
+                    line.spans.insert(prefix_count, span);
+                }
+                if fill > 0 {
+                    line.spans.push(Span::styled(" ".repeat(fill), style));
+                }
+            }
+        }
+
+        // Diff lines are filled to the width of the block, so their backgrounds span full lines.
+        let width = lines.iter().map(content_width).max().unwrap_or_default();
+        let width = width.max(available);
+        for (line, row) in lines.iter_mut().zip(&rows) {
+            let Some(style) = diff_styles.get(row.source).copied().flatten() else {
+                continue;
+            };
+            for span in &mut line.spans[prefix_count..] {
+                span.style = span.style.patch(style);
+            }
+            let fill = width - content_width(line);
+            if fill > 0 {
+                line.spans.push(Span::styled(" ".repeat(fill), style));
+            }
+        }
+
+        let highlight = self.styles.code_highlighted_line();
+        for (line, row) in lines.iter_mut().zip(&rows) {
+            let number = row.source + 1;
+            let highlighted_lines = block.info.highlighted_lines();
+            if highlighted_lines
+                .iter()
+                .any(|range| range.contains(&number))
+            {
+                for span in &mut line.spans[prefix_count..] {
+                    span.style = span.style.patch(highlight);
+                }
+            }
+            if let Some(digits) = digits {
+                if !separator.is_empty() {
+                    let span = Span::styled(separator.clone(), number_style);
+                    line.spans.insert(prefix_count, span);
+                }
+                let number = if row.continuation {
+                    " ".repeat(digits + 1)
+                } else {
+                    format!("{number:>digits$} ")
+                };
+                line.spans
+                    .insert(prefix_count, Span::styled(number, number_style));
+            }
+        }
+
+        // Collected footnote definitions are rendered apart from the document, so their line
+        // indices would not refer to the final text.
+        if self.footnote_capture.is_none() {
+            self.code_blocks.push(CodeBlock {
+                info: block.info,
+                lines: block.first_line..self.text.lines.len(),
+            });
+        }
+    }
+
     #[cfg(feature = "highlight-code")]
     #[instrument(level = "trace", skip(self))]
     fn set_code_highlighter(&mut self, lang: &str) {
-        if let Some(syntax) = SYNTAX_SET.find_syntax_by_token(lang) {
-            debug!("Starting code block with syntax: {:?}", lang);
-            let code_theme = match self.code_theme {
-                Some(code_theme) => code_theme,
-                None => code_theme::default(),
+        if lang.is_empty() && self.code_language_detection == CodeLanguageDetection::Content {
+            self.code_language_pending = true;
+            return;
+        }
+        let diff_code = diff_language(lang).filter(|code| !code.is_empty());
+        let token = self.resolve_code_language(diff_code.unwrap_or(lang));
+        if let Some((syntax, code_lines)) = find_syntax(token) {
+            debug!("Starting code block with syntax: {:?}", token);
+            let code_lines = if diff_code.is_some() {
+                CodeLines::Diff
+            } else {
+                code_lines
             };
-            let theme = code_theme::theme(code_theme);
-            let highlighter = HighlightLines::new(syntax, theme);
-            self.code_highlighter = Some(highlighter);
+            self.start_code_highlighter(syntax, code_lines);
         } else {
             warn!("Could not find syntax for code block: {:?}", lang);
         }
     }
 
+    /// Returns the highlighter token for a fenced language, resolving configured and built-in
+    /// aliases.
+    #[cfg(feature = "highlight-code")]
+    fn resolve_code_language<'l>(&'l self, lang: &'l str) -> &'l str {
+        if let Some(language) = self.code_language_aliases.get(&lang.to_lowercase()) {
+            return language;
+        }
+        LANGUAGE_ALIASES
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(lang))
+            .map_or(lang, |(_, language)| language)
+    }
+
+    /// Selects the syntax of a code block without a language from its first line of code.
+    ///
+    /// A panel's background is chosen again, because a recognized block uses the theme's.
+    #[cfg(feature = "highlight-code")]
+    fn detect_code_language(&mut self, text: &str) {
+        let first_line = text.lines().next().unwrap_or_default();
+        let syntax = SYNTAX_SET
+            .find_syntax_by_first_line(first_line)
+            .map(|syntax| (syntax, CodeLines::Code))
+            .or_else(|| find_syntax(detect_language(first_line)?));
+        let Some((syntax, code_lines)) = syntax else {
+            debug!("Could not detect syntax for code block: {:?}", first_line);
+            return;
+        };
+        debug!("Detected code block syntax: {:?}", syntax.name);
+        self.start_code_highlighter(syntax, code_lines);
+        let panel_style = self.code_panel_style();
+        if let Some(block) = &mut self.code_block {
+            block.diff = diff_language(&syntax.name).is_some();
+            if let Some(panel) = &mut block.panel {
+                *panel = panel_style;
+            }
+        }
+    }
+
+    #[cfg(feature = "highlight-code")]
+    fn start_code_highlighter(&mut self, syntax: &'static SyntaxReference, code_lines: CodeLines) {
+        self.code_lines = code_lines;
+        self.code_highlighter = Some(self.code_highlighter_for(syntax));
+    }
+
+    /// Creates a highlighter for `syntax` with the selected theme.
+    #[cfg(feature = "highlight-code")]
+    fn code_highlighter_for(&self, syntax: &'static SyntaxReference) -> HighlightLines<'theme> {
+        HighlightLines::new(syntax, code_theme::theme(self.selected_code_theme()))
+    }
+
+    /// Returns the configured code theme, or the shared default.
+    #[cfg(feature = "highlight-code")]
+    fn selected_code_theme(&self) -> &'theme CodeTheme {
+        match self.code_theme {
+            Some(code_theme) => code_theme,
+            None => code_theme::default(),
+        }
+    }
+
     #[cfg(feature = "highlight-code")]
     #[instrument(level = "trace", skip(self))]
     fn clear_code_highlighter(&mut self) {
         self.code_highlighter = None;
+        self.code_language_pending = false;
+        self.code_lines = CodeLines::Code;
+    }
+}
+
+/// Finds the syntax for a highlighter token, and how the lines of code are split.
+#[cfg(feature = "highlight-code")]
+fn find_syntax(token: &str) -> Option<(&'static SyntaxReference, CodeLines)> {
+    let session = SHELL_SESSION_LANGUAGES
+        .iter()
+        .any(|language| language.eq_ignore_ascii_case(token));
+    let (token, code_lines) = if session {
+        ("bash", CodeLines::ShellSession(ShellSession::default()))
+    } else {
+        (token, CodeLines::Code)
+    };
+    SYNTAX_SET
+        .find_syntax_by_token(token)
+        .map(|syntax| (syntax, code_lines))
+}
+
+/// Returns the language of the code in a diff, such as `rust` for `diff-rust`.
+///
+/// Plain `diff` and `patch` blocks have an empty language. Returns `None` for other languages.
+fn diff_language(language: &str) -> Option<&str> {
+    ["diff", "patch"].into_iter().find_map(|diff| {
+        let (prefix, code) = language.split_at_checked(diff.len())?;
+        if !prefix.eq_ignore_ascii_case(diff) {
+            return None;
+        }
+        if code.is_empty() {
+            Some(code)
+        } else {
+            code.strip_prefix('-')
+        }
+    })
+}
+
+/// Returns a line of code without highlighting or its line ending.
+#[cfg(feature = "highlight-code")]
+fn plain_line(line: &str, style: Style) -> Line<'static> {
+    let line = line.trim_end_matches(['\n', '\r']).to_owned();
+    Line::from(Span::styled(line, style))
+}
+
+/// Highlights one line of code, including its line ending, into terminal lines.
+#[cfg(feature = "highlight-code")]
+fn highlight_line(
+    highlighter: &mut HighlightLines<'_>,
+    code_theme: &CodeTheme,
+    line: &str,
+) -> Vec<Line<'static>> {
+    let mut lines = highlighter
+        .highlight_line(line, &SYNTAX_SET)
+        .ok()
+        .and_then(|ranges| as_24_bit_terminal_escaped(&ranges, false).into_text().ok())
+        .map(|text| text.lines)
+        .unwrap_or_default();
+    for span in lines.iter_mut().flat_map(|line| &mut line.spans) {
+        span.style = code_theme::terminal_style(code_theme, span.style);
     }
+    lines
+}
+
+/// Recognizes a language from a first line of code that the highlighter's own first-line
+/// patterns miss.
+#[cfg(feature = "highlight-code")]
+fn detect_language(first_line: &str) -> Option<&'static str> {
+    let line = first_line.trim_start();
+    let language = if line.starts_with("$ ") {
+        "console"
+    } else if line.starts_with("diff --git ") || line.starts_with("--- a/") {
+        "diff"
+    } else if line.starts_with("<!DOCTYPE html") || line.starts_with("<html") {
+        "html"
+    } else if line.starts_with("<?xml") {
+        "xml"
+    } else if line.starts_with('{') || line.starts_with("[{") || line.starts_with("[\"") {
+        "json"
+    } else {
+        return None;
+    };
+    Some(language)
 }
 
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
+    use ratatui_core::style::Color;
     use rstest::rstest;
 
     use super::*;
@@ -278,6 +843,440 @@ mod tests {
         assert_eq!(text.to_string(), "fn main() {}");
     }
 
+    #[rstest]
+    fn panel_fills_code_lines_to_block_width(_with_tracing: DefaultGuard) {
+        let options = Options::default().code_block_background(CodeBlockBackground::Panel);
+        let text = from_str_with_options("```not-a-language\nlet x;\nx\n```", &options);
+        let code = Style::new().white().on_black();
+
+        assert_eq!(
+            text.lines[1].spans,
+            [
+                Span::styled(" ", code),
+                Span::styled("let x;", code),
+                Span::styled(" ", code),
+            ]
+        );
+        assert_eq!(
+            text.lines[2].spans,
+            [
+                Span::styled(" ", code),
+                Span::styled("x", code),
+                Span::styled("      ", code),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn panel_fills_available_width_after_prefixes(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .code_block_background(CodeBlockBackground::Panel)
+            .code_block_padding(2)
+            .width(12);
+        let text = from_str_with_options("> ```\n> code\n> ```", &options);
+
+        assert_eq!(text.to_string(), "> ```\n>   code    \n> ```");
+    }
+
+    #[cfg(feature = "highlight-code")]
+    #[rstest]
+    fn highlighted_panel_uses_theme_background(_with_tracing: DefaultGuard) {
+        let options = Options::default().code_block_background(CodeBlockBackground::Panel);
+        let text = from_str_with_options("```rust\nfn main() {}\n```", &options);
+        let background = Some(Color::Rgb(0x2b, 0x30, 0x3b));
+
+        assert!(text.lines[1]
+            .spans
+            .iter()
+            .all(|span| span.style.bg == background));
+        assert_eq!(text.lines[1].width(), "fn main() {}".len() + 2);
+    }
+
+    #[rstest]
+    fn line_numbers_align_in_gutter(_with_tracing: DefaultGuard) {
+        let options = Options::default().code_line_numbers(CodeLineNumbers::Shown);
+        let code = (1..=10)
+            .map(|n| format!("> line {n}\n"))
+            .collect::<String>();
+        let markdown = format!("> ```\n{code}> ```");
+        let text = from_str_with_options(&markdown, &options);
+
+        assert_eq!(text.lines[1].to_string(), ">  1 │ line 1");
+        assert_eq!(text.lines[10].to_string(), "> 10 │ line 10");
+        assert_eq!(text.lines[1].spans[2].style, Style::new().dim());
+    }
+
+    #[rstest]
+    fn info_string_emphasizes_lines(_with_tracing: DefaultGuard) {
+        let markdown = "```not-a-language {2}\none\ntwo\n```";
+        let text = from_str(markdown);
+
+        assert_eq!(text.lines[0].to_string(), "```not-a-language");
+        assert_eq!(text.lines[1].spans[0].style.bg, None);
+        assert_eq!(text.lines[2].spans[0].style.bg, Some(Color::DarkGray));
+    }
+
+    #[cfg(feature = "highlight-code")]
+    #[rstest]
+    fn line_attributes_keep_syntax_highlighting(_with_tracing: DefaultGuard) {
+        let plain = from_str("```rust\nfn main() {}\n```");
+        let emphasized = from_str("```rust {1}\nfn main() {}\n```");
+
+        assert_eq!(emphasized.lines[0].to_string(), "```rust");
+        for (span, plain_span) in emphasized.lines[1].spans.iter().zip(&plain.lines[1].spans) {
+            assert_eq!(span.style.fg, plain_span.style.fg);
+            assert_eq!(span.style.bg, Some(Color::DarkGray));
+        }
+    }
+
+    #[rstest]
+    fn gutter_sits_outside_panel(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .code_block_background(CodeBlockBackground::Panel)
+            .code_line_numbers(CodeLineNumbers::Shown)
+            .width(12);
+        let text = from_str_with_options("```\ncode\n```", &options);
+
+        assert_eq!(text.lines[1].to_string(), "1 │  code   ");
+        assert_eq!(text.lines[1].spans[1].style, Style::new().dim());
+    }
+
+    #[rstest]
+    fn title_attribute_adds_header(_with_tracing: DefaultGuard) {
+        let markdown = "```not-a-language title=\"src/main.rs\" ignore\ncode\n```";
+        let text = from_str(markdown);
+
+        assert_eq!(
+            text.to_string(),
+            "```not-a-language\nsrc/main.rs\ncode\n```"
+        );
+        assert_eq!(text.lines[1].spans[0].style, Style::new().bold());
+    }
+
+    #[rstest]
+    fn style_sheet_selects_title(_with_tracing: DefaultGuard) {
+        #[derive(Clone, Copy)]
+        struct IdTitle;
+
+        impl StyleSheet for IdTitle {
+            fn code_block_title<'i>(&'i self, info: &'i CodeBlockInfo) -> Option<&'i str> {
+                info.attribute("id")
+            }
+        }
+
+        let markdown = "```text {#setup title=\"ignored\"}\ncode\n```";
+        let text = from_str_with_options(markdown, &Options::new(IdTitle));
+
+        assert_eq!(text.to_string(), "```text\nsetup\ncode\n```");
+    }
+
+    #[rstest]
+    fn document_lists_code_blocks(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {r#"
+            ```rust title="main.rs" ignore
+            fn main() {}
+            ```
+
+                indented
+        "#};
+        let document = document_from_str(markdown, &Options::default());
+        let blocks = document.code_blocks();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].info.language(), "rust");
+        assert!(blocks[0].info.has_attribute("ignore"));
+        assert_eq!(blocks[0].lines, 2..3);
+        assert_eq!(blocks[1].info, CodeBlockInfo::default());
+        assert_eq!(
+            document.text().lines[blocks[1].lines.start].to_string(),
+            "indented"
+        );
+    }
+
+    #[cfg(feature = "highlight-code")]
+    #[rstest]
+    fn attributes_do_not_affect_syntax_lookup(_with_tracing: DefaultGuard) {
+        let plain = from_str("```rust\nfn main() {}\n```");
+        let titled = from_str("```rust title=\"main.rs\" ignore\nfn main() {}\n```");
+
+        assert_eq!(titled.lines[2], plain.lines[1]);
+    }
+
+    #[cfg(feature = "highlight-code")]
+    mod languages {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+        use crate::CodeLanguageDetection;
+
+        #[rstest]
+        #[case::builtin("shell", Options::default())]
+        #[case::configured("nushell", Options::default().code_language_alias("NuShell", "bash"))]
+        #[case::configured_over_builtin(
+            "console",
+            Options::default().code_language_alias("console", "bash")
+        )]
+        fn aliases_select_syntax(
+            _with_tracing: DefaultGuard,
+            #[case] alias: &str,
+            #[case] options: Options,
+        ) {
+            let markdown = format!("```{alias}\nls -la\n```");
+            let aliased = from_str_with_options(&markdown, &options);
+            let bash = from_str("```bash\nls -la\n```");
+
+            assert_eq!(aliased.lines[0].to_string(), format!("```{alias}"));
+            assert_eq!(aliased.lines[1], bash.lines[1]);
+        }
+
+        #[rstest]
+        #[case::shebang("#!/usr/bin/env python\nprint(1)", "py")]
+        #[case::prompt("$ cargo test", "console")]
+        #[case::json("{\"key\": [1, 2]}", "json")]
+        fn detection_highlights_unlabeled_code(
+            _with_tracing: DefaultGuard,
+            #[case] code: &str,
+            #[case] language: &str,
+        ) {
+            let options =
+                Options::default().code_language_detection(CodeLanguageDetection::Content);
+            let unlabeled = format!("```\n{code}\n```");
+            let labeled = format!("```{language}\n{code}\n```");
+            let detected = from_str_with_options(&unlabeled, &options);
+            let undetected = from_str(&unlabeled);
+            let labeled = from_str(&labeled);
+
+            assert_eq!(detected.lines[1..], labeled.lines[1..]);
+            assert_ne!(detected.lines[1], undetected.lines[1]);
+        }
+
+        #[rstest]
+        fn detection_applies_to_indented_code_in_panels(_with_tracing: DefaultGuard) {
+            let options = Options::default()
+                .code_language_detection(CodeLanguageDetection::Content)
+                .code_block_background(CodeBlockBackground::Panel);
+            let text = from_str_with_options("    #!/bin/sh\n    echo hi", &options);
+            let background = Some(Color::Rgb(0x2b, 0x30, 0x3b));
+
+            assert!(text.lines[2]
+                .spans
+                .iter()
+                .all(|span| span.style.bg == background));
+        }
+
+        #[rstest]
+        fn detection_keeps_unrecognized_languages(_with_tracing: DefaultGuard) {
+            let options =
+                Options::default().code_language_detection(CodeLanguageDetection::Content);
+            let markdown = "```not-a-language\n#!/bin/sh\n```";
+
+            assert_eq!(
+                from_str_with_options(markdown, &options),
+                from_str(markdown)
+            );
+        }
+    }
+
+    mod diffs {
+        use pretty_assertions::assert_eq;
+        use ratatui_core::style::Modifier;
+
+        use super::*;
+
+        const DIFF: &str = indoc! {"
+            ```diff
+            --- a/greeting.txt
+            +++ b/greeting.txt
+            @@ -1 +1 @@
+            -hello
+            +hello, world
+             goodbye
+            ```
+        "};
+
+        #[rstest]
+        fn styles_lines_by_kind(_with_tracing: DefaultGuard) {
+            let text = from_str(DIFF);
+            let style_of = |line: &Line| line.spans[0].style;
+
+            assert!(style_of(&text.lines[1])
+                .add_modifier
+                .contains(Modifier::BOLD));
+            assert_eq!(style_of(&text.lines[3]).fg, Some(Color::Cyan));
+            assert_eq!(style_of(&text.lines[4]).bg, Some(Color::Indexed(52)));
+            assert_eq!(style_of(&text.lines[5]).bg, Some(Color::Indexed(22)));
+            assert_ne!(style_of(&text.lines[6]).bg, Some(Color::Indexed(22)));
+        }
+
+        #[rstest]
+        fn fills_changed_lines_to_block_width(_with_tracing: DefaultGuard) {
+            let options = Options::default().width(20);
+            let text = from_str_with_options(DIFF, &options);
+
+            for line in &text.lines[4..=5] {
+                assert_eq!(line.width(), 20);
+                assert!(line
+                    .spans
+                    .iter()
+                    .all(|span| span.style.bg.is_some_and(|bg| bg != Color::Reset)));
+            }
+            assert_eq!(text.lines[6].width(), " goodbye".len());
+        }
+
+        #[cfg(feature = "highlight-code")]
+        #[rstest]
+        fn highlights_code_after_markers(_with_tracing: DefaultGuard) {
+            let diff = from_str("```diff-rust\n+let x = 1;\n```");
+            let rust = from_str("```rust\nlet x = 1;\n```");
+            let foreground = |spans: &[Span]| {
+                spans
+                    .iter()
+                    .map(|span| (span.content.to_string(), span.style.fg))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(diff.lines[1].spans[0].content, "+");
+            assert_eq!(
+                foreground(&diff.lines[1].spans[1..]),
+                foreground(&rust.lines[1].spans)
+            );
+            assert_eq!(diff.lines[1].spans[1].style.bg, Some(Color::Indexed(22)));
+        }
+    }
+
+    #[cfg(feature = "highlight-code")]
+    mod shell_sessions {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[rstest]
+        fn separates_prompts_commands_and_output(_with_tracing: DefaultGuard) {
+            let text = from_str(indoc! {"
+                ```console
+                $ echo hi
+                hi
+                ```
+            "});
+            let bash = from_str(
+                "```bash
+echo hi
+```",
+            );
+
+            assert_eq!(
+                text.lines[1].spans[0],
+                Span::styled("$ ", Style::new().dim())
+            );
+            assert_eq!(text.lines[1].spans[1..], bash.lines[1].spans);
+            assert_eq!(text.lines[2], Line::from("hi"));
+        }
+
+        #[rstest]
+        #[case::continuation(
+            "$ cargo build \\
+    --release
+ok",
+            3,
+            2
+        )]
+        #[case::root_prompt(
+            "# apt update
+ok",
+            2,
+            1
+        )]
+        fn highlights_commands_and_continuations(
+            _with_tracing: DefaultGuard,
+            #[case] session: &str,
+            #[case] output_line: usize,
+            #[case] commands: usize,
+        ) {
+            let markdown = format!("```shell-session\n{session}\n```");
+            let text = from_str(&markdown);
+            let highlighted = |line: &Line| line.spans.iter().any(|span| span.style.fg.is_some());
+
+            assert_eq!(
+                text.lines[1..=commands]
+                    .iter()
+                    .filter(|line| highlighted(line))
+                    .count(),
+                commands
+            );
+            assert_eq!(text.lines[output_line], Line::from("ok"));
+        }
+    }
+
+    #[cfg(feature = "highlight-code")]
+    mod inline_highlighting {
+        use pretty_assertions::assert_eq;
+        use ratatui_core::style::Color;
+
+        use super::*;
+        use crate::InlineCodeHighlighting;
+
+        #[rstest]
+        fn attribute_selects_syntax_and_is_removed(_with_tracing: DefaultGuard) {
+            let options =
+                Options::default().inline_code_highlighting(InlineCodeHighlighting::Tagged);
+            let text = from_str_with_options("Use `let x = 1`{.rust} here.", &options);
+            let code = &text.lines[0].spans[1..text.lines[0].spans.len() - 1];
+
+            assert_eq!(text.to_string(), "Use let x = 1 here.");
+            assert_eq!(code[0].content, "let");
+            assert!(matches!(code[0].style.fg, Some(Color::Rgb(..))));
+            assert!(code.iter().all(|span| span.style.bg == Some(Color::Black)));
+            assert_eq!(text.lines[0].spans.last(), Some(&Span::raw(" here.")));
+        }
+
+        #[rstest]
+        fn prefix_selects_syntax_through_aliases(_with_tracing: DefaultGuard) {
+            let options =
+                Options::default().inline_code_highlighting(InlineCodeHighlighting::Tagged);
+            let prefixed = from_str_with_options("`rs: let x = 1`", &options);
+            let attributed = from_str_with_options("`let x = 1`{.rust}", &options);
+
+            assert_eq!(prefixed, attributed);
+        }
+
+        #[rstest]
+        fn unknown_prefix_is_kept(_with_tracing: DefaultGuard) {
+            let options =
+                Options::default().inline_code_highlighting(InlineCodeHighlighting::Tagged);
+            let text = from_str_with_options("`note: x`", &options);
+
+            assert_eq!(
+                text,
+                Text::from(Line::from(Span::styled(
+                    "note: x",
+                    Style::new().white().on_black()
+                )))
+            );
+        }
+
+        #[rstest]
+        fn unknown_attribute_is_removed(_with_tracing: DefaultGuard) {
+            let options =
+                Options::default().inline_code_highlighting(InlineCodeHighlighting::Tagged);
+            let text = from_str_with_options("`x`{.nolang}!", &options);
+
+            assert_eq!(
+                text,
+                Text::from(Line::from_iter([
+                    Span::styled("x", Style::new().white().on_black()),
+                    Span::raw("!"),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn tags_are_text_by_default(_with_tracing: DefaultGuard) {
+            let text = from_str("`rs: x`{.rust}");
+
+            assert_eq!(text.to_string(), "rs: x{.rust}");
+        }
+    }
+
     #[cfg(feature = "highlight-code")]
     mod code_theme {
         use pretty_assertions::assert_eq;
@@ -0,0 +1,157 @@
+//! Tracking of enclosing blocks for context-aware styling.
+//!
+//! Block tags push a [`BlockKind`] when they start and pop it when they end, so the stack always
+//! describes the ancestry of the next inline element. Inline styles are patched with the style
+//! sheet's [`StyleSheet::in_context`] style for that ancestry.
+
+use pulldown_cmark::{Event, Tag, TagEnd};
+use ratatui_core::style::Style;
+
+use super::{blockquote, TextWriter};
+use crate::{BlockKind, InlineElement, StyleContext, StyleSheet};
+
+impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
+where
+    I: Iterator<Item = Event<'a>>,
+    S: StyleSheet,
+{
+    pub fn enter_block(&mut self, tag: &Tag<'a>) {
+        let block = match tag {
+            Tag::Paragraph => BlockKind::Paragraph,
+            Tag::Heading { level, .. } => BlockKind::Heading(*level as u8),
+            Tag::BlockQuote(None) => BlockKind::BlockQuote,
+            Tag::BlockQuote(Some(kind)) => BlockKind::Alert(blockquote::alert_kind(*kind)),
+            Tag::List(start) => BlockKind::List {
+                ordered: start.is_some(),
+            },
+            Tag::Item => BlockKind::ListItem,
+            Tag::Table(_) => BlockKind::Table,
+            Tag::TableHead => BlockKind::TableHead,
+            Tag::TableCell => BlockKind::TableCell,
+            Tag::FootnoteDefinition(_) => BlockKind::FootnoteDefinition,
+            Tag::DefinitionList => BlockKind::DefinitionList,
+            Tag::DefinitionListTitle => BlockKind::DefinitionTerm,
+            Tag::DefinitionListDefinition => BlockKind::DefinitionDescription,
+            _ => return,
+        };
+        self.blocks.push(block);
+    }
+
+    pub fn leave_block(&mut self, tag: TagEnd) {
+        let ends_block = matches!(
+            tag,
+            TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::BlockQuote(_)
+                | TagEnd::List(_)
+                | TagEnd::Item
+                | TagEnd::Table
+                | TagEnd::TableHead
+                | TagEnd::TableCell
+                | TagEnd::FootnoteDefinition
+                | TagEnd::DefinitionList
+                | TagEnd::DefinitionListTitle
+                | TagEnd::DefinitionListDefinition
+        );
+        if ends_block {
+            self.blocks.pop();
+        }
+    }
+
+    /// Patches `style` with the style sheet's style for `element` in the current context.
+    pub fn element_style(&self, element: InlineElement, style: Style) -> Style {
+        let context = StyleContext::new(&self.blocks);
+        style.patch(self.styles.in_context(element, &context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::renderer::test_support::{with_tracing, DefaultGuard};
+    use crate::renderer::*;
+    use crate::AlertKind;
+
+    #[derive(Clone, Copy)]
+    struct ContextualStyle;
+
+    impl StyleSheet for ContextualStyle {
+        fn in_context(&self, element: InlineElement, context: &StyleContext<'_>) -> Style {
+            match element {
+                InlineElement::Code if context.heading_level().is_some() => Style::new().bold(),
+                InlineElement::Link if context.alert() == Some(AlertKind::Warning) => {
+                    Style::new().red()
+                }
+                InlineElement::Strong if context.list_level() == 2 => Style::new().green(),
+                _ => Style::default(),
+            }
+        }
+    }
+
+    fn styles_of(text: &Text<'_>, content: &str) -> Vec<Style> {
+        text.iter()
+            .flatten()
+            .filter(|span| span.content == content)
+            .map(|span| span.style)
+            .collect()
+    }
+
+    #[rstest]
+    fn code_in_headings(_with_tracing: DefaultGuard) {
+        let options = Options::new(ContextualStyle);
+        let text = from_str_with_options("## `api`\n\n`api`", &options);
+
+        assert_eq!(
+            styles_of(&text, "api"),
+            [
+                Style::new().white().on_black().bold(),
+                Style::new().white().on_black(),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn links_in_warning_alerts(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            > [!WARNING]
+            > [docs](x)
+
+            > [!NOTE]
+            > [docs](x)
+        "};
+        let options = Options::new(ContextualStyle);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(
+            styles_of(&text, "docs"),
+            [
+                Style::new().blue().underlined().red(),
+                Style::new().blue().underlined(),
+            ]
+        );
+        assert_eq!(
+            styles_of(&text, "x"),
+            [
+                Style::new().blue().underlined().red(),
+                Style::new().blue().underlined(),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn strong_text_by_list_level(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            - **a**
+              - **b**
+        "};
+        let options = Options::new(ContextualStyle);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(styles_of(&text, "a"), [Style::new().bold()]);
+        assert_eq!(styles_of(&text, "b"), [Style::new().bold().green()]);
+    }
+}
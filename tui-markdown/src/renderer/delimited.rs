@@ -0,0 +1,239 @@
+//! Delimited data blocks rendered as tables.
+//!
+//! With [`DelimitedBlocks::Table`], the text of a ` ```csv ` or ` ```tsv ` block is buffered
+//! instead of being written as code. When the block ends, its records are parsed and passed through
+//! the [`TableBuilder`](super::table::TableBuilder), so the data shares the borders, display-width
+//! handling, and styles of Markdown tables. The first record is the header.
+
+use itertools::Itertools;
+use pulldown_cmark::{Alignment, Event};
+use ratatui_core::text::Span;
+
+use super::TextWriter;
+use crate::{DelimitedBlocks, StyleSheet};
+
+/// The text of a delimited data block, parsed when the block ends.
+pub struct DelimitedBlock {
+    delimiter: char,
+    data: String,
+}
+
+impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
+where
+    I: Iterator<Item = Event<'a>>,
+    S: StyleSheet,
+{
+    /// Starts buffering a code block as a table when its language names delimited data.
+    ///
+    /// Returns whether the block is buffered.
+    pub fn start_delimited_block(&mut self, language: &str) -> bool {
+        if self.delimited_blocks != DelimitedBlocks::Table {
+            return false;
+        }
+        let delimiter = if language.eq_ignore_ascii_case("csv") {
+            ','
+        } else if language.eq_ignore_ascii_case("tsv") {
+            '\t'
+        } else {
+            return false;
+        };
+        self.delimited_block = Some(DelimitedBlock {
+            delimiter,
+            data: String::new(),
+        });
+        true
+    }
+
+    /// Adds code text to the buffered block, returning whether a block is being buffered.
+    pub fn push_delimited_text(&mut self, text: &str) -> bool {
+        let Some(block) = &mut self.delimited_block else {
+            return false;
+        };
+        block.data.push_str(text);
+        true
+    }
+
+    /// Renders the buffered block as a table, returning whether a block was buffered.
+    pub fn end_delimited_block(&mut self) -> bool {
+        let Some(block) = self.delimited_block.take() else {
+            return false;
+        };
+        let records = parse_records(&block.data, block.delimiter);
+        self.start_table(alignments(&records));
+        if let Some(builder) = &mut self.table_builder {
+            for (index, record) in records.into_iter().enumerate() {
+                for field in record {
+                    builder.start_cell();
+                    builder.push_span(Span::raw(field));
+                    builder.finish_cell();
+                }
+                if index == 0 {
+                    builder.finish_header();
+                } else {
+                    builder.finish_row();
+                }
+            }
+        }
+        self.end_table();
+        true
+    }
+}
+
+/// Right-aligns the columns whose body values are all numbers.
+fn alignments(records: &[Vec<String>]) -> Vec<Alignment> {
+    let body = records.get(1..).unwrap_or_default();
+    let column_count = records.iter().map(Vec::len).max().unwrap_or_default();
+    (0..column_count)
+        .map(|column| {
+            let mut values = body
+                .iter()
+                .filter_map(|record| record.get(column))
+                .filter(|value| !value.is_empty())
+                .peekable();
+            let numeric = values.peek().is_some() && values.all(|value| is_number(value));
+            if numeric {
+                Alignment::Right
+            } else {
+                Alignment::None
+            }
+        })
+        .collect()
+}
+
+fn is_number(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    digits.starts_with(|c: char| c.is_ascii_digit()) && value.parse::<f64>().is_ok()
+}
+
+/// Parses records separated by line breaks into fields separated by `delimiter`.
+///
+/// Comma-separated fields may be quoted with `"`, which allows delimiters, line breaks, and doubled
+/// `""` quotes inside them. Line breaks inside a field are shown as spaces. Fields are trimmed, and
+/// blank lines are skipped.
+fn parse_records(data: &str, delimiter: char) -> Vec<Vec<String>> {
+    let quoting = delimiter == ',';
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if quoting && field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                finish_record(&mut records, &mut record);
+            }
+            '\r' => {}
+            _ if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    record.push(field);
+    finish_record(&mut records, &mut record);
+    records
+}
+
+/// Adds the fields of `record` to `records` as display text, unless the record is a blank line.
+fn finish_record(records: &mut Vec<Vec<String>>, record: &mut Vec<String>) {
+    let fields = std::mem::take(record);
+    if let [field] = fields.as_slice() {
+        if field.trim().is_empty() {
+            return;
+        }
+    }
+    let fields = fields
+        .iter()
+        .map(|field| field.lines().map(str::trim).join(" "))
+        .collect();
+    records.push(fields);
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::renderer::test_support::{with_tracing, DefaultGuard};
+    use crate::renderer::*;
+    use crate::{DefaultStyleSheet, Options};
+
+    #[rstest]
+    fn csv_block_renders_as_table(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {r#"
+            ```csv
+            item,"note, quoted",qty
+            apple,"say ""hi""",3
+            pear,,-12.5
+            ```
+        "#};
+        let options = Options::default().delimited_blocks(DelimitedBlocks::Table);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(
+            text.to_string(),
+            indoc! {r#"
+                ┌───────┬──────────────┬───────┐
+                │ item  │ note, quoted │   qty │
+                ├───────┼──────────────┼───────┤
+                │ apple │ say "hi"     │     3 │
+                │ pear  │              │ -12.5 │
+                └───────┴──────────────┴───────┘"#}
+        );
+    }
+
+    #[rstest]
+    fn tsv_block_uses_table_styles(_with_tracing: DefaultGuard) {
+        let markdown = "```tsv\nname\t\"size\"\nlib.rs\t12\n```";
+        let options = Options::default().delimited_blocks(DelimitedBlocks::Table);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(text.lines[1].to_string(), "│ name   │ \"size\" │");
+        assert_eq!(
+            text.lines[1].spans[2].style,
+            DefaultStyleSheet.table_header()
+        );
+        assert_eq!(text.lines[3].to_string(), "│ lib.rs │     12 │");
+    }
+
+    #[rstest]
+    fn table_follows_previous_block(_with_tracing: DefaultGuard) {
+        let markdown = "Data:\n\n```csv\na\n1\n```\n\nDone.";
+        let options = Options::default().delimited_blocks(DelimitedBlocks::Table);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(text.lines[0].to_string(), "Data:");
+        assert_eq!(text.lines[1].to_string(), "");
+        assert_eq!(text.lines[2].to_string(), "┌───┐");
+        assert_eq!(text.lines[6].to_string(), "└───┘");
+        assert_eq!(text.lines[7].to_string(), "");
+        assert_eq!(text.lines[8].to_string(), "Done.");
+    }
+
+    #[rstest]
+    fn delimited_blocks_are_code_by_default(_with_tracing: DefaultGuard) {
+        let text = from_str("```csv\na,b\n```");
+
+        assert_eq!(text.to_string(), "```csv\na,b\n```");
+    }
+
+    #[test]
+    fn parses_quoted_fields_across_lines() {
+        let records = parse_records("a,\"multi\nline\"\r\n\n b , c\n", ',');
+
+        assert_eq!(records, [vec!["a", "multi line"], vec!["b", "c"]]);
+    }
+}
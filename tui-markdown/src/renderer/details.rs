@@ -0,0 +1,523 @@
+//! Collapsible `<details>` rendering.
+//!
+//! GitHub READMEs wrap long content in `<details><summary>…</summary>…</details>`.
+//! Pulldown-cmark reports the tags as HTML blocks around ordinary Markdown content, so [`split`]
+//! finds them inside a buffered HTML block and the writer tracks each open element. The summary
+//! renders once with a disclosure marker. While any enclosing element is collapsed, the content
+//! between its tags is skipped.
+//!
+//! Elements are numbered in document order, whether or not they are visible, so that an index
+//! reported in [`DetailsSummary`] identifies the same element after it is toggled and re-rendered.
+
+use pulldown_cmark::Event;
+use ratatui_core::text::{Line, Span};
+
+use super::html::tag::HtmlTag;
+use super::TextWriter;
+use crate::{DetailsState, DetailsSummary, StyleSheet};
+
+/// Summary text used when a `<details>` element has no `<summary>`, matching browsers.
+const DEFAULT_SUMMARY: &str = "Details";
+
+/// A part of an HTML block, separating `<details>` structure from other HTML.
+#[derive(Debug, PartialEq, Eq)]
+pub enum HtmlBlockPart<'h> {
+    /// A `<details>` start tag, with whether it has the `open` attribute.
+    DetailsStart { open: bool },
+    /// The text content of a `<summary>` element, with nested tags removed.
+    Summary(String),
+    /// A `</details>` end tag.
+    DetailsEnd,
+    /// Any other HTML, rendered according to the HTML block settings.
+    Html(&'h str),
+}
+
+/// Splits `html` into `<details>` structure and the HTML around it.
+///
+/// Tags inside `<!-- … -->` comments are ignored. A block without details tags returns a single
+/// [`HtmlBlockPart::Html`] part containing the unchanged block. Otherwise, newlines at the edges of the remaining HTML are trimmed and
+/// whitespace-only HTML between the tags is dropped.
+pub fn split(html: &str) -> Vec<HtmlBlockPart<'_>> {
+    let lowercase = html.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut html_start = 0;
+    let mut position = 0;
+    while let Some(offset) = html[position..].find('<') {
+        let tag_start = position + offset;
+        position = tag_start + 1;
+        if html[tag_start..].starts_with("<!--") {
+            position = html[tag_start + 4..]
+                .find("-->")
+                .map_or(html.len(), |end| tag_start + 4 + end + 3);
+            continue;
+        }
+        let Some((tag, rest)) = HtmlTag::parse_prefix(&html[tag_start..]) else {
+            continue;
+        };
+        let tag_end = html.len() - rest.len();
+        let part = match (tag.name.as_str(), tag.closing) {
+            ("details", false) => HtmlBlockPart::DetailsStart {
+                open: tag.attr("open").is_some(),
+            },
+            ("details", true) => HtmlBlockPart::DetailsEnd,
+            ("summary", false) => {
+                let Some(length) = lowercase[tag_end..].find("</summary>") else {
+                    continue;
+                };
+                let summary = summary_text(&html[tag_end..tag_end + length]);
+                position = tag_end + length + "</summary>".len();
+                push_html(&mut parts, &html[html_start..tag_start]);
+                parts.push(HtmlBlockPart::Summary(summary));
+                html_start = position;
+                continue;
+            }
+            _ => continue,
+        };
+        push_html(&mut parts, &html[html_start..tag_start]);
+        parts.push(part);
+        position = tag_end;
+        html_start = position;
+    }
+
+    if parts.is_empty() {
+        return vec![HtmlBlockPart::Html(html)];
+    }
+    push_html(&mut parts, &html[html_start..]);
+    parts
+}
+
+fn push_html<'h>(parts: &mut Vec<HtmlBlockPart<'h>>, html: &'h str) {
+    let html = html.trim_matches('\n');
+    if !html.trim().is_empty() {
+        parts.push(HtmlBlockPart::Html(html));
+    }
+}
+
+/// Returns the visible text of summary HTML, removing tags and collapsing whitespace.
+fn summary_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = match HtmlTag::parse_prefix(&rest[start..]) {
+            Some((_, after)) => after,
+            None => {
+                text.push('<');
+                &rest[start + 1..]
+            }
+        };
+    }
+    text.push_str(rest);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A `<details>` element whose end tag has not been seen yet.
+#[derive(Debug)]
+pub struct OpenDetails {
+    /// Position of the element in document order.
+    index: usize,
+    state: DetailsState,
+    /// Whether the summary line has been written or deliberately skipped.
+    has_summary: bool,
+}
+
+impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
+where
+    I: Iterator<Item = Event<'a>>,
+    S: StyleSheet,
+{
+    pub fn start_details(&mut self, open: bool) {
+        self.write_pending_details_summary();
+        let index = self.details_count;
+        self.details_count += 1;
+        let state = match self.details_states.get(&index) {
+            Some(&state) => state,
+            None if open => DetailsState::Expanded,
+            None => self.details_default.unwrap_or_default(),
+        };
+        self.details.push(OpenDetails {
+            index,
+            state,
+            has_summary: false,
+        });
+    }
+
+    pub fn details_summary(&mut self, summary: &str) {
+        match self.details.last() {
+            Some(details) if !details.has_summary => self.write_details_summary(summary),
+            // A summary outside `<details>`, or a second summary, is not a disclosure control.
+            _ => self.render_html_block_part(summary),
+        }
+    }
+
+    pub fn end_details(&mut self) {
+        self.write_pending_details_summary();
+        self.details.pop();
+        self.needs_newline = true;
+    }
+
+    /// Writes the default summary when content follows a `<details>` tag without a `<summary>`.
+    pub fn write_pending_details_summary(&mut self) {
+        if self
+            .details
+            .last()
+            .is_some_and(|details| !details.has_summary)
+        {
+            self.write_details_summary(DEFAULT_SUMMARY);
+        }
+    }
+
+    /// Returns whether `event` is hidden by a collapsed `<details>` element.
+    ///
+    /// Tags started in hidden content are counted until they end. An end tag that closes a block
+    /// opened before the collapsed element is not hidden, so that the block's line prefixes and
+    /// list nesting unwind as usual.
+    pub fn skip_hidden_event(&mut self, event: &Event<'a>) -> bool {
+        if !self.details.is_empty() {
+            self.write_pending_details_summary();
+        }
+        if !self.in_collapsed_details() {
+            return false;
+        }
+        match event {
+            Event::Start(_) => self.hidden_depth += 1,
+            Event::End(_) if self.hidden_depth == 0 => return false,
+            Event::End(_) => self.hidden_depth -= 1,
+            _ => {}
+        }
+        true
+    }
+
+    /// Returns whether output is currently hidden by a collapsed `<details>` element, or by a
+    /// block that started inside one and has not ended yet.
+    pub fn in_collapsed_details(&self) -> bool {
+        self.hidden_depth > 0
+            || self
+                .details
+                .iter()
+                .any(|details| details.state == DetailsState::Collapsed)
+    }
+
+    fn write_details_summary(&mut self, summary: &str) {
+        let Some(details) = self.details.last_mut() else {
+            return;
+        };
+        details.has_summary = true;
+        let index = details.index;
+        let state = details.state;
+
+        // The summary of a nested element is part of its parent's hidden content.
+        let parents = &self.details[..self.details.len() - 1];
+        if parents
+            .iter()
+            .any(|details| details.state == DetailsState::Collapsed)
+        {
+            return;
+        }
+
+        let marker = self.styles.details_marker(state);
+        let marker = if marker.is_empty() {
+            String::new()
+        } else {
+            format!("{marker} ")
+        };
+        let spans = [Span::from(marker), Span::from(summary.to_owned())];
+        let style = self.styles.details_summary();
+        if self.list_marker_line_is_open() {
+            // Like a paragraph, the first block of a list item continues the marker line.
+            for span in spans {
+                self.push_span(span.patch_style(style));
+            }
+        } else {
+            if self.needs_newline {
+                self.push_line(Line::default());
+            }
+            self.push_line(Line::from_iter(spans).style(style));
+        }
+        self.details_summaries.push(DetailsSummary {
+            index,
+            line: self.text.lines.len() - 1,
+            state,
+        });
+        self.needs_newline = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::renderer::test_support::{with_tracing, DefaultGuard};
+    use crate::renderer::*;
+    use crate::{document_from_str, DetailsState};
+
+    const DETAILS: &str = indoc! {"
+        Before
+
+        <details>
+        <summary>More <b>info</b></summary>
+
+        Hidden content.
+
+        </details>
+
+        After
+    "};
+
+    #[test]
+    fn split_separates_details_tags() {
+        assert_eq!(
+            split("<details open><summary> A  <i>b</i> </summary>\nText\n</details>\n"),
+            [
+                HtmlBlockPart::DetailsStart { open: true },
+                HtmlBlockPart::Summary("A b".to_owned()),
+                HtmlBlockPart::Html("Text"),
+                HtmlBlockPart::DetailsEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn split_ignores_tags_in_comments() {
+        assert_eq!(
+            split("<!-- <details> -->\n"),
+            [HtmlBlockPart::Html("<!-- <details> -->\n")]
+        );
+        assert_eq!(
+            split("<!-- </details> --><details>"),
+            [
+                HtmlBlockPart::Html("<!-- </details> -->"),
+                HtmlBlockPart::DetailsStart { open: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_keeps_other_html_unchanged() {
+        assert_eq!(
+            split("<div>\nText\n</div>\n"),
+            [HtmlBlockPart::Html("<div>\nText\n</div>\n")]
+        );
+    }
+
+    #[rstest]
+    fn details_render_as_html_by_default(_with_tracing: DefaultGuard) {
+        assert_eq!(
+            from_str(DETAILS).to_string(),
+            indoc! {"
+                Before
+
+                <details>
+                <summary>More <b>info</b></summary>
+
+                Hidden content.
+
+                </details>
+
+                After"}
+        );
+    }
+
+    #[rstest]
+    fn expanded_details_show_summary_and_content(_with_tracing: DefaultGuard) {
+        let bold = Style::new().bold();
+        let options = Options::default().details(DetailsState::Expanded);
+
+        assert_eq!(
+            from_str_with_options(DETAILS, &options),
+            Text::from_iter([
+                Line::from("Before"),
+                Line::default(),
+                Line::from_iter(["▼ ", "More info"]).style(bold),
+                Line::default(),
+                Line::from("Hidden content."),
+                Line::default(),
+                Line::from("After"),
+            ])
+        );
+    }
+
+    #[rstest]
+    fn collapsed_details_hide_content(_with_tracing: DefaultGuard) {
+        let options = Options::default().details(DetailsState::Collapsed);
+
+        assert_eq!(
+            from_str_with_options(DETAILS, &options).to_string(),
+            "Before\n\n▶ More info\n\nAfter"
+        );
+    }
+
+    #[rstest]
+    fn open_attribute_expands_collapsed_default(_with_tracing: DefaultGuard) {
+        let markdown = "<details open>\n<summary>Open</summary>\n\nShown.\n\n</details>";
+        let options = Options::default().details(DetailsState::Collapsed);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            "▼ Open\n\nShown."
+        );
+    }
+
+    #[rstest]
+    #[case::expanded(DetailsState::Expanded)]
+    #[case::collapsed(DetailsState::Collapsed)]
+    fn commented_out_details_render_as_html(
+        _with_tracing: DefaultGuard,
+        #[case] state: DetailsState,
+    ) {
+        let markdown = "<!-- <details> -->\n\n# Heading\n\ntext";
+        let options = Options::default().details(state);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            "<!-- <details> -->\n\n# Heading\n\ntext"
+        );
+    }
+
+    #[rstest]
+    fn missing_summary_uses_default_label(_with_tracing: DefaultGuard) {
+        let markdown = "<details>\n\nContent.\n\n</details>";
+        let options = Options::default().details(DetailsState::Expanded);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            "▼ Details\n\nContent."
+        );
+    }
+
+    #[rstest]
+    fn collapsed_details_end_enclosing_blockquote(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            > <details>
+            > <summary>S</summary>
+            >
+            > hidden
+
+            </details>
+
+            after
+        "};
+        let options = Options::default().details(DetailsState::Collapsed);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            "> ▶ S\n\nafter"
+        );
+    }
+
+    #[rstest]
+    fn collapsed_details_end_enclosing_list(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            - <details>
+              <summary>S</summary>
+
+              hidden
+
+            </details>
+
+            after
+
+            - x
+        "};
+        let options = Options::default().details(DetailsState::Collapsed);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            "- ▶ S\n\nafter\n\n- x"
+        );
+    }
+
+    #[rstest]
+    #[case::expanded(DetailsState::Expanded, "- ▼ S\n\nShown.")]
+    #[case::collapsed(DetailsState::Collapsed, "- ▶ S")]
+    fn summary_in_list_item_stays_on_marker_line(
+        _with_tracing: DefaultGuard,
+        #[case] state: DetailsState,
+        #[case] expected: &str,
+    ) {
+        let markdown = "- <details><summary>S</summary>\n\n  Shown.\n\n  </details>";
+        let options = Options::default().details(state);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn document_reports_summaries_for_toggling(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            <details>
+            <summary>Outer</summary>
+
+            <details>
+            <summary>Inner</summary>
+
+            Text.
+
+            </details>
+
+            </details>
+
+            <details>
+            <summary>Last</summary>
+            </details>
+        "};
+        let options = Options::default()
+            .details(DetailsState::Expanded)
+            .details_at(0, DetailsState::Collapsed);
+        let document = document_from_str(markdown, &options);
+
+        assert_eq!(document.text().to_string(), "▶ Outer\n\n▼ Last");
+        assert_eq!(
+            document.details(),
+            [
+                DetailsSummary {
+                    index: 0,
+                    line: 0,
+                    state: DetailsState::Collapsed,
+                },
+                DetailsSummary {
+                    index: 2,
+                    line: 2,
+                    state: DetailsState::Expanded,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn custom_marker_and_style(_with_tracing: DefaultGuard) {
+        #[derive(Clone, Copy)]
+        struct CustomDetails;
+
+        impl StyleSheet for CustomDetails {
+            fn details_marker(&self, state: DetailsState) -> &str {
+                match state {
+                    DetailsState::Collapsed => "+",
+                    DetailsState::Expanded => "",
+                }
+            }
+
+            fn details_summary(&self) -> Style {
+                Style::new().red()
+            }
+        }
+
+        let markdown = "<details>\n<summary>Title</summary>\n</details>";
+        let expanded = Options::new(CustomDetails).details(DetailsState::Expanded);
+        let collapsed = Options::new(CustomDetails).details(DetailsState::Collapsed);
+
+        assert_eq!(
+            from_str_with_options(markdown, &expanded),
+            Text::from(Line::from_iter(["", "Title"]).style(Style::new().red()))
+        );
+        assert_eq!(
+            from_str_with_options(markdown, &collapsed).to_string(),
+            "+ Title"
+        );
+    }
+}
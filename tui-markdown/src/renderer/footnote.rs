@@ -1,13 +1,51 @@
 //! Markdown footnote rendering.
 //!
-//! References render inline as `[label]`. Definitions start with `[label]: ` and retain paragraph
-//! boundaries without leaking their line style into following content.
+//! References render inline as a marker chosen by [`FootnoteNumbering`], `[label]` by default.
+//! Definitions start with the same marker and retain paragraph boundaries without leaking their
+//! line style into following content.
+//!
+//! With [`FootnoteLayout::Collected`], each definition is rendered into a separate [`Text`] while
+//! the rest of the document continues, and the definitions are appended in numbering order when
+//! the document ends. Their markers are added then, because a definition can precede the reference
+//! that determines its number.
+//!
+//! Pulldown-cmark only reports references whose definition exists. A reference without one arrives
+//! as text events such as `[`, `^label`, and `]`. When footnotes are collected or numbered, these
+//! are recognized and flagged with [`StyleSheet::footnote_missing`]; otherwise they stay plain
+//! text.
 
 use pulldown_cmark::{CowStr, Event};
-use ratatui_core::text::{Line, Span};
+use ratatui_core::style::Style;
+use ratatui_core::text::{Line, Span, Text};
 
+use super::list::ListItemLayout;
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{Footnote, FootnoteLayout, FootnoteNumbering, StyleSheet};
+
+/// Title of the section containing collected footnote definitions.
+const SECTION_TITLE: &str = "Footnotes";
+
+const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// The surrounding document's output state while a definition is rendered on its own.
+pub struct FootnoteCapture<'a> {
+    /// Index of the footnote in [`TextWriter::footnotes`].
+    footnote: usize,
+    text: Text<'a>,
+    line_styles: Vec<Style>,
+    line_prefixes: Vec<Span<'a>>,
+    list_items: Vec<ListItemLayout>,
+    needs_newline: bool,
+    /// References inside the definition, with the footnote index and the line in the definition.
+    references: Vec<(usize, usize)>,
+}
+
+/// A rendered definition waiting for the trailing footnote section.
+pub struct CollectedFootnote<'a> {
+    footnote: usize,
+    lines: Vec<Line<'a>>,
+    references: Vec<(usize, usize)>,
+}
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
@@ -15,23 +53,93 @@ where
     S: StyleSheet,
 {
     pub fn footnote_reference(&mut self, label: CowStr<'a>) {
+        let footnote = self.footnote_index(&label);
+        self.footnote_number(footnote);
         // A reference can appear inside other inline formatting, as in `**Text[^label]**`.
         // Styling it with only `footnote_ref()` would make `[label]` dim and italic but drop the
         // surrounding bold style. Start with the active inline style and patch the footnote style
         // over it so the reference adds its own appearance without losing enclosing formatting.
-        let inline_style = self.inline_styles.last().copied().unwrap_or_default();
+        let inline_style = self.inline_style();
         let style = inline_style.patch(self.styles.footnote_ref());
-        self.push_span(Span::styled(format!("[{label}]"), style));
+        self.push_span(Span::styled(self.footnote_marker(footnote), style));
+        self.record_footnote_reference(footnote);
+    }
+
+    /// Renders `[^label]` as a flagged reference when `text` starts one without a definition.
+    ///
+    /// Only references in documents with a [`FootnoteLayout::Collected`] layout or numbered
+    /// markers are flagged. Returns whether the text and the following events were consumed.
+    pub fn missing_footnote_reference(&mut self, text: &str) -> bool {
+        let flags_missing = self.footnote_layout == FootnoteLayout::Collected
+            || self.footnote_numbering != FootnoteNumbering::Label;
+        if !flags_missing || text != "[" {
+            return false;
+        }
+        // With superscripts enabled, the `^` can arrive as a separate text event.
+        let mut content = String::new();
+        let mut event_count = 0;
+        let mut is_closed = false;
+        while event_count < 3 {
+            let Some(Event::Text(next)) = self.iter.peek() else {
+                break;
+            };
+            event_count += 1;
+            if next.as_ref() == "]" {
+                is_closed = true;
+                break;
+            }
+            content.push_str(next);
+        }
+        self.iter.reset_peek();
+        let label = content
+            .strip_prefix('^')
+            .filter(|label| !label.is_empty() && !label.contains(char::is_whitespace));
+        let Some(label) = label.filter(|_| is_closed).map(str::to_owned) else {
+            return false;
+        };
+        for _ in 0..event_count {
+            self.iter.next();
+        }
+
+        let footnote = self.footnote_index(&label);
+        let inline_style = self.inline_style();
+        let style = inline_style
+            .patch(self.styles.footnote_ref())
+            .patch(self.styles.footnote_missing());
+        self.push_span(Span::styled(format!("[^{label}]"), style));
+        self.record_footnote_reference(footnote);
+        true
     }
 
     pub fn start_footnote_definition(&mut self, label: CowStr<'a>) {
-        if self.needs_newline {
-            self.push_line(Line::default());
+        let footnote = self.footnote_index(&label);
+        let collected = self.footnote_layout == FootnoteLayout::Collected;
+        if collected {
+            self.footnote_capture = Some(FootnoteCapture {
+                footnote,
+                text: std::mem::take(&mut self.text),
+                line_styles: std::mem::take(&mut self.line_styles),
+                line_prefixes: std::mem::take(&mut self.line_prefixes),
+                list_items: std::mem::take(&mut self.list_items),
+                needs_newline: std::mem::replace(&mut self.needs_newline, false),
+                references: Vec::new(),
+            });
+        } else {
+            self.footnote_number(footnote);
+            if self.needs_newline {
+                self.push_line(Line::default());
+            }
         }
         let style = self.styles.footnote_def();
         self.line_styles.push(style);
         self.push_line(Line::default());
-        self.push_span(Span::styled(format!("[{label}]: "), style));
+        if !collected {
+            self.footnotes[footnote]
+                .definition
+                .get_or_insert(self.text.lines.len() - 1);
+            let prefix = self.footnote_definition_prefix(footnote);
+            self.push_span(Span::styled(prefix, style));
+        }
         self.in_footnote_definition = true;
         self.needs_newline = false;
     }
@@ -39,8 +147,132 @@ where
     pub fn end_footnote_definition(&mut self) {
         self.line_styles.pop();
         self.in_footnote_definition = false;
+        let Some(capture) = self.footnote_capture.take() else {
+            self.needs_newline = true;
+            return;
+        };
+        let definition = std::mem::replace(&mut self.text, capture.text);
+        self.line_styles = capture.line_styles;
+        self.line_prefixes = capture.line_prefixes;
+        self.list_items = capture.list_items;
+        self.needs_newline = capture.needs_newline;
+        self.collected_footnotes.push(CollectedFootnote {
+            footnote: capture.footnote,
+            lines: definition.lines,
+            references: capture.references,
+        });
+    }
+
+    /// Appends the collected definitions under a title, ordered by footnote number.
+    pub fn write_footnote_section(&mut self) {
+        let mut collected = std::mem::take(&mut self.collected_footnotes);
+        if collected.is_empty() {
+            return;
+        }
+        // Definitions that were never referenced are numbered after the referenced ones.
+        for definition in &collected {
+            self.footnote_number(definition.footnote);
+        }
+        collected.sort_by_key(|definition| self.footnotes[definition.footnote].number);
+
+        if self.needs_newline {
+            self.push_line(Line::default());
+        }
+        self.push_line(Line::from(SECTION_TITLE).style(self.styles.footnote_heading()));
+        for definition in collected {
+            self.push_line(Line::default());
+            let first_line = self.text.lines.len();
+            let mut lines = definition.lines;
+            if let Some(line) = lines.first_mut() {
+                let prefix = self.footnote_definition_prefix(definition.footnote);
+                line.spans
+                    .insert(0, Span::styled(prefix, self.styles.footnote_def()));
+            }
+            self.text.lines.extend(lines);
+            self.footnotes[definition.footnote]
+                .definition
+                .get_or_insert(first_line);
+            for (footnote, line) in definition.references {
+                self.footnotes[footnote].references.push(first_line + line);
+            }
+        }
         self.needs_newline = true;
     }
+
+    /// Records a reference on the line where its marker was written.
+    pub fn add_footnote_reference(&mut self, footnote: usize, line: usize) {
+        match &mut self.footnote_capture {
+            Some(capture) => capture.references.push((footnote, line)),
+            None => self.footnotes[footnote].references.push(line),
+        }
+    }
+
+    fn record_footnote_reference(&mut self, footnote: usize) {
+        // Table lines are rendered when the table ends, so only the row is known yet.
+        if let Some(builder) = &self.table_builder {
+            let offset = builder.current_line();
+            self.table_footnote_references.push((footnote, offset));
+            return;
+        }
+        let line = self.text.lines.len().saturating_sub(1);
+        self.add_footnote_reference(footnote, line);
+    }
+
+    /// Returns the index of the footnote with `label`, adding it on first appearance.
+    ///
+    /// Labels match case-insensitively, as they do in pulldown-cmark.
+    fn footnote_index(&mut self, label: &str) -> usize {
+        let key = label.to_lowercase();
+        if let Some(index) = self
+            .footnotes
+            .iter()
+            .position(|footnote| footnote.label.to_lowercase() == key)
+        {
+            return index;
+        }
+        self.footnotes.push(Footnote {
+            label: label.to_owned(),
+            number: None,
+            definition: None,
+            references: Vec::new(),
+        });
+        self.footnotes.len() - 1
+    }
+
+    /// Returns the number of a footnote, assigning the next number on first use.
+    fn footnote_number(&mut self, footnote: usize) -> usize {
+        *self.footnotes[footnote].number.get_or_insert_with(|| {
+            self.footnote_count += 1;
+            self.footnote_count
+        })
+    }
+
+    fn footnote_marker(&self, footnote: usize) -> String {
+        let footnote = &self.footnotes[footnote];
+        let number = footnote.number.unwrap_or_default();
+        match self.footnote_numbering {
+            FootnoteNumbering::Label => format!("[{}]", footnote.label),
+            FootnoteNumbering::Bracketed => format!("[{number}]"),
+            FootnoteNumbering::Superscript => superscript(number),
+        }
+    }
+
+    fn footnote_definition_prefix(&self, footnote: usize) -> String {
+        let marker = self.footnote_marker(footnote);
+        match self.footnote_numbering {
+            FootnoteNumbering::Superscript => format!("{marker} "),
+            _ => format!("{marker}: "),
+        }
+    }
+}
+
+fn superscript(number: usize) -> String {
+    number
+        .to_string()
+        .chars()
+        .filter_map(|digit| digit.to_digit(10))
+        .map(|digit| SUPERSCRIPT_DIGITS[digit as usize])
+        .collect()
 }
 
 #[cfg(test)]
@@ -49,6 +281,7 @@ mod tests {
     use rstest::rstest;
 
     use super::*;
+    use crate::document_from_str;
     use crate::renderer::test_support::{with_tracing, DefaultGuard};
     use crate::renderer::*;
 
@@ -249,4 +482,171 @@ mod tests {
             );
         }
     }
+    mod collected {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        const MARKDOWN: &str = indoc! {"
+            [^unused]: Never referenced.
+
+            [^late]: Defined before its reference.
+
+            > Quoted[^first].
+            >
+            > [^first]: First note with[^late].
+
+            Then[^late] again.
+        "};
+
+        #[rstest]
+        fn definitions_follow_the_document_in_reference_order(_with_tracing: DefaultGuard) {
+            let options = Options::default()
+                .footnote_layout(FootnoteLayout::Collected)
+                .footnote_numbering(FootnoteNumbering::Bracketed);
+            let definition_style = Style::new().dim();
+
+            let text = from_str_with_options(MARKDOWN, &options);
+
+            assert_eq!(
+                text.to_string(),
+                indoc! {"
+                    > Quoted[1].
+
+                    Then[2] again.
+
+                    Footnotes
+
+                    [1]: First note with[2].
+
+                    [2]: Defined before its reference.
+
+                    [3]: Never referenced."}
+            );
+            assert_eq!(
+                text.lines[4],
+                Line::from("Footnotes").style(Style::new().bold())
+            );
+            assert_eq!(
+                text.lines[10],
+                Line::from_iter([
+                    Span::styled("[3]: ", definition_style),
+                    Span::raw("Never referenced."),
+                ])
+                .style(definition_style)
+            );
+        }
+
+        #[rstest]
+        fn superscript_numbers(_with_tracing: DefaultGuard) {
+            let options = Options::default()
+                .footnote_layout(FootnoteLayout::Collected)
+                .footnote_numbering(FootnoteNumbering::Superscript);
+
+            assert_eq!(
+                from_str_with_options(MARKDOWN, &options).to_string(),
+                indoc! {"
+                    > Quoted¹.
+
+                    Then² again.
+
+                    Footnotes
+
+                    ¹ First note with².
+
+                    ² Defined before its reference.
+
+                    ³ Never referenced."}
+            );
+        }
+
+        #[rstest]
+        fn document_reports_reference_and_definition_lines(_with_tracing: DefaultGuard) {
+            let options = Options::default()
+                .footnote_layout(FootnoteLayout::Collected)
+                .footnote_numbering(FootnoteNumbering::Label);
+            let document = document_from_str(MARKDOWN, &options);
+            let labels = document
+                .footnotes()
+                .iter()
+                .map(|footnote| {
+                    (
+                        footnote.label.as_str(),
+                        footnote.number,
+                        footnote.definition,
+                        footnote.references.clone(),
+                    )
+                })
+                .collect_vec();
+
+            assert_eq!(
+                labels,
+                [
+                    ("first", Some(1), Some(6), vec![0]),
+                    ("late", Some(2), Some(8), vec![2, 6]),
+                    ("unused", Some(3), Some(10), vec![]),
+                ]
+            );
+        }
+
+        #[rstest]
+        fn table_references_report_their_row(_with_tracing: DefaultGuard) {
+            let markdown = indoc! {"
+                | A | B |
+                |---|---|
+                | x | y[^n] |
+
+                [^n]: Note.
+            "};
+            let options = Options::default()
+                .footnote_layout(FootnoteLayout::Collected)
+                .footnote_numbering(FootnoteNumbering::Bracketed);
+            let document = document_from_str(markdown, &options);
+
+            assert_eq!(document.text().lines[3].to_string(), "│ x │ y[1] │");
+            assert_eq!(document.footnotes()[0].references, [3]);
+            assert_eq!(document.footnotes()[0].definition, Some(8));
+        }
+    }
+
+    mod missing {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[rstest]
+        fn reference_without_definition_is_flagged(_with_tracing: DefaultGuard) {
+            let options = Options::default().footnote_numbering(FootnoteNumbering::Bracketed);
+            let document = document_from_str("See[^nowhere] and [x].", &options);
+            let missing_style = Style::new().dim().italic().red();
+
+            assert_eq!(
+                document.text().lines[0],
+                Line::from_iter([
+                    Span::raw("See"),
+                    Span::styled("[^nowhere]", missing_style),
+                    Span::raw(" and "),
+                    Span::raw("["),
+                    Span::raw("x"),
+                    Span::raw("]"),
+                    Span::raw("."),
+                ])
+            );
+            assert_eq!(document.footnotes()[0].label, "nowhere");
+            assert_eq!(document.footnotes()[0].number, None);
+            assert_eq!(document.footnotes()[0].references, [0]);
+        }
+
+        #[rstest]
+        fn reference_without_definition_is_plain_by_default(_with_tracing: DefaultGuard) {
+            let document = document_from_str("See [^nope].", &Options::default());
+
+            assert_eq!(document.text().to_string(), "See [^nope].");
+            assert!(document.text().lines[0]
+                .spans
+                .iter()
+                .all(|span| span.style == Style::default()));
+            assert!(document.footnotes().is_empty());
+        }
+    }
 }
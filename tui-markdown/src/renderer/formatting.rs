@@ -1,20 +1,42 @@
 //! Markdown inline formatting.
 //!
 //! The inline style stack patches nested formatting over its enclosing style. Closing a formatting
-//! tag restores the previous style.
+//! tag restores the previous style. Body text is additionally patched over the style sheet's
+//! paragraph style. That includes paragraphs, tight list items, and definition-list descriptions.
+//! Headings, table cells, code blocks, and metadata blocks keep their own styles.
 
 use pulldown_cmark::Event;
 use ratatui_core::style::Style;
 use tracing::{debug, instrument};
 
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{BlockKind, StyleSheet};
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
     I: Iterator<Item = Event<'a>>,
     S: StyleSheet,
 {
+    /// Returns the style for inline content at the current position.
+    pub fn inline_style(&self) -> Style {
+        let style = self.inline_styles.last().copied().unwrap_or_default();
+        if self.in_body_text() {
+            self.styles.paragraph().patch(style)
+        } else {
+            style
+        }
+    }
+
+    /// Returns whether inline content at the current position is body text.
+    fn in_body_text(&self) -> bool {
+        self.code_block.is_none()
+            && !self.in_metadata_block
+            && !self
+                .blocks
+                .iter()
+                .any(|block| matches!(block, BlockKind::Heading(_) | BlockKind::TableCell))
+    }
+
     #[instrument(level = "trace", skip(self))]
     pub fn push_inline_style(&mut self, style: Style) {
         let current_style = self.inline_styles.last().copied().unwrap_or_default();
@@ -32,8 +54,9 @@ where
 
 #[cfg(test)]
 mod tests {
+    use indoc::indoc;
     use pretty_assertions::assert_eq;
-    use ratatui_core::style::Stylize;
+    use ratatui_core::style::{Color, Stylize};
     use rstest::rstest;
 
     use super::*;
@@ -110,4 +133,102 @@ mod tests {
             ]))
         );
     }
+
+    #[derive(Clone, Copy)]
+    struct PlainTerminalStyle;
+
+    impl StyleSheet for PlainTerminalStyle {
+        fn paragraph(&self) -> Style {
+            Style::new().gray()
+        }
+
+        fn emphasis(&self) -> Style {
+            Style::new().underlined()
+        }
+
+        fn strong(&self) -> Style {
+            Style::new().white()
+        }
+
+        fn strikethrough(&self) -> Style {
+            Style::new().dark_gray()
+        }
+
+        fn subscript(&self) -> Style {
+            Style::new().blue()
+        }
+
+        fn superscript(&self) -> Style {
+            Style::new().red()
+        }
+    }
+
+    #[rstest]
+    fn style_sheet_selects_inline_formatting(_with_tracing: DefaultGuard) {
+        let options = Options::new(PlainTerminalStyle);
+
+        assert_eq!(
+            from_str_with_options("*a* **b** ~~c~~ ~d~ ^e^", &options),
+            Text::from(Line::from_iter([
+                "a".gray().underlined(),
+                " ".gray(),
+                "b".white(),
+                " ".gray(),
+                "c".dark_gray(),
+                " ".gray(),
+                "d".blue(),
+                " ".gray(),
+                "e".red(),
+            ]))
+        );
+    }
+
+    #[rstest]
+    fn paragraph_style_does_not_apply_to_headings(_with_tracing: DefaultGuard) {
+        let options = Options::new(PlainTerminalStyle);
+        let text = from_str_with_options("# Title\n\nBody", &options);
+
+        assert_eq!(text.lines[0].spans[1].style, Style::default());
+        assert_eq!(text.lines[2], Line::from("Body".gray()));
+    }
+
+    #[rstest]
+    #[case::tight_list_item("- item", 0, 1)]
+    #[case::loose_list_item("- loose\n\n- item", 1, 1)]
+    #[case::definition_description("Term\n: item", 1, 1)]
+    #[case::blockquote("> item", 0, 2)]
+    fn paragraph_style_applies_to_body_text(
+        _with_tracing: DefaultGuard,
+        #[case] markdown: &str,
+        #[case] line: usize,
+        #[case] span: usize,
+    ) {
+        let options = Options::new(PlainTerminalStyle);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(text.lines[line].spans[span], "item".gray());
+    }
+
+    #[rstest]
+    fn paragraph_style_does_not_apply_to_tables_or_code(_with_tracing: DefaultGuard) {
+        let options = Options::new(PlainTerminalStyle);
+        let text = from_str_with_options(
+            indoc! {"
+                | a |
+                | - |
+                | b |
+
+                ```
+                code
+                ```
+            "},
+            &options,
+        );
+
+        assert!(text
+            .lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .all(|span| span.style.fg != Some(Color::Gray)));
+    }
 }
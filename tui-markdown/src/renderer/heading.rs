@@ -1,13 +1,36 @@
 //! Markdown heading rendering.
 //!
-//! Headings retain their Markdown `#` prefix. IDs, classes, and key-value attributes render as a
-//! styled attribute-block suffix after the heading text.
+//! Headings retain their Markdown `#` prefix, optionally followed by a hierarchical section number.
+//! IDs, classes, and key-value attributes render as a styled attribute-block suffix after the
+//! heading text.
+//!
+//! Big headings and decorations are applied when the heading ends, once its content is known. A
+//! big heading replaces the content of the heading line with the first row of block letters and
+//! adds the remaining rows below it. Decorations then apply to every row. A boxed heading reserves
+//! its top border line when it starts so that the positions of lines written inside the heading,
+//! such as footnote references, stay valid.
 
 use pulldown_cmark::{CowStr, Event, HeadingLevel};
+use ratatui_core::layout::Alignment;
 use ratatui_core::text::{Line, Span};
 
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{BigHeadings, HeadingDecoration, HeadingNumbering, StyleSheet};
+
+mod big_text;
+
+/// A heading whose end tag has not been seen yet.
+pub struct OpenHeading {
+    level: u8,
+    /// Index of the heading line in the output.
+    line: usize,
+    /// Index of the first span after the line prefixes, starting with the heading marker.
+    content_start: usize,
+    /// Number of heading marker spans before the section number and heading text.
+    marker_spans: usize,
+    /// Index of the line reserved for the top border of a boxed heading.
+    top_border: Option<usize>,
+}
 
 /// Heading attributes collected from pulldown-cmark to render after the heading text.
 pub struct HeadingMeta<'a> {
@@ -70,26 +93,180 @@ where
             HeadingLevel::H5 => 5,
             HeadingLevel::H6 => 6,
         };
+        let top_border =
+            (self.heading_decoration(heading_level) == HeadingDecoration::Boxed).then(|| {
+                self.push_line(Line::default());
+                self.text.lines.len() - 1
+            });
         let style = self.styles.heading(heading_level);
+        self.push_line(Line::default().style(style));
+        let line = self.text.lines.len() - 1;
+        let content_start = self.text.lines[line].spans.len();
+
         let marker = self.styles.heading_marker(heading_level);
-        let content = if marker.is_empty() {
-            String::new()
-        } else {
-            format!("{marker} ")
-        };
-        self.push_line(Line::styled(content, style));
+        let marker_spans = usize::from(!marker.is_empty());
+        if !marker.is_empty() {
+            self.push_span(Span::raw(format!("{marker} ")));
+        }
+        if let Some(number) = self.heading_number(heading_level) {
+            self.push_span(Span::raw(format!("{number} ")));
+        }
+        self.heading = Some(OpenHeading {
+            level: heading_level,
+            line,
+            content_start,
+            marker_spans,
+            top_border,
+        });
         self.heading_meta = heading_meta.into_option();
         self.needs_newline = false;
     }
 
     pub fn end_heading(&mut self) {
-        if let Some(meta) = self.heading_meta.take() {
+        let heading = self.heading.take();
+        let is_big = heading
+            .as_ref()
+            .is_some_and(|heading| self.write_big_heading(heading));
+        if let Some(meta) = self.heading_meta.take().filter(|_| !is_big) {
             if let Some(suffix) = meta.to_suffix() {
                 self.push_span(Span::styled(suffix, self.styles.heading_meta()));
             }
         }
+        if let Some(heading) = heading {
+            self.decorate_heading(&heading);
+        }
         self.needs_newline = true;
     }
+
+    /// Replaces the heading content with block letters when enabled and when they fit.
+    ///
+    /// Returns whether the heading was replaced.
+    fn write_big_heading(&mut self, heading: &OpenHeading) -> bool {
+        let enabled = match self.big_headings {
+            BigHeadings::Off => false,
+            BigHeadings::H1 => heading.level == 1,
+            BigHeadings::H1AndH2 => heading.level <= 2,
+        };
+        if !enabled {
+            return false;
+        }
+        let line = &self.text.lines[heading.line];
+        let text = line.spans[heading.content_start + heading.marker_spans..]
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<String>();
+        let Some(rows) = big_text::render(text.trim()) else {
+            return false;
+        };
+        let prefix_width = line.spans[..heading.content_start]
+            .iter()
+            .map(Span::width)
+            .sum::<usize>();
+        let width = rows.iter().map(|row| row.chars().count()).max();
+        let fits = self.width.is_none_or(|available| {
+            prefix_width + width.unwrap_or_default() <= usize::from(available)
+        });
+        if !fits {
+            return false;
+        }
+
+        let style = self.styles.heading(heading.level);
+        let mut rows = rows.into_iter();
+        let line = &mut self.text.lines[heading.line];
+        line.spans.truncate(heading.content_start);
+        line.spans.extend(rows.next().map(Span::raw));
+        for row in rows {
+            self.push_line(Line::from(row).style(style));
+        }
+        true
+    }
+
+    fn heading_decoration(&self, level: u8) -> HeadingDecoration {
+        self.heading_decorations[usize::from(level) - 1]
+    }
+
+    /// Counts a heading at `level` and returns its section number, if numbering shows it.
+    fn heading_number(&mut self, level: u8) -> Option<String> {
+        let index = usize::from(level) - 1;
+        self.heading_counters[index] += 1;
+        self.heading_counters[index + 1..].fill(0);
+        let first = match self.heading_numbering {
+            HeadingNumbering::Off => return None,
+            HeadingNumbering::FromH1 => 0,
+            HeadingNumbering::FromH2 => 1,
+        };
+        if index < first {
+            return None;
+        }
+        let number = self.heading_counters[first..=index]
+            .iter()
+            .map(|count| format!("{count}."))
+            .collect();
+        Some(number)
+    }
+
+    fn decorate_heading(&mut self, heading: &OpenHeading) {
+        let decoration = self.heading_decoration(heading.level);
+        let border = self.styles.heading_decoration(heading.level);
+        let rows = heading.line..self.text.lines.len();
+        let prefix_width = self.text.lines[heading.line].spans[..heading.content_start]
+            .iter()
+            .map(Span::width)
+            .sum::<usize>();
+        let content_width = |line: &Line| {
+            line.spans[heading.content_start..]
+                .iter()
+                .map(Span::width)
+                .sum::<usize>()
+        };
+        let width = self.text.lines[rows.clone()]
+            .iter()
+            .map(content_width)
+            .max()
+            .unwrap_or_default();
+
+        match decoration {
+            HeadingDecoration::Plain => {}
+            HeadingDecoration::Centered => {
+                for line in &mut self.text.lines[rows] {
+                    line.alignment = Some(Alignment::Center);
+                }
+            }
+            HeadingDecoration::Underline => {
+                let width = self.width.map_or(width, |available| {
+                    usize::from(available).saturating_sub(prefix_width)
+                });
+                let rule = if heading.level == 1 { "═" } else { "─" };
+                self.push_line(Line::from(Span::styled(rule.repeat(width), border)));
+            }
+            HeadingDecoration::Boxed => {
+                for line in &mut self.text.lines[rows] {
+                    let padding = width - content_width(line);
+                    // The heading style moves from the line to its content so that it does not
+                    // color the borders.
+                    let style = std::mem::take(&mut line.style);
+                    let mut content = line.spans.split_off(heading.content_start);
+                    for span in &mut content {
+                        span.style = style.patch(span.style);
+                    }
+                    line.spans.push(Span::styled("│ ", border));
+                    line.spans.extend(content);
+                    if padding > 0 {
+                        line.spans.push(Span::raw(" ".repeat(padding)));
+                    }
+                    line.spans.push(Span::styled(" │", border));
+                }
+
+                let horizontal = "─".repeat(width + 2);
+                if let Some(top_border) = heading.top_border {
+                    let top = Span::styled(format!("┌{horizontal}┐"), border);
+                    self.text.lines[top_border].spans.push(top);
+                }
+                let bottom = Span::styled(format!("└{horizontal}┘"), border);
+                self.push_line(Line::from(bottom));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +400,157 @@ mod tests {
             )
         );
     }
+
+    #[rstest]
+    fn hierarchical_numbering_resets_deeper_levels(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            # One
+            ### Skipped level
+            ## Two
+            # Three
+            ## Four
+        "};
+        let options = Options::default().heading_numbering(HeadingNumbering::FromH1);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            indoc! {"
+                # 1. One
+
+                ### 1.0.1. Skipped level
+
+                ## 1.1. Two
+
+                # 2. Three
+
+                ## 2.1. Four"}
+        );
+    }
+
+    #[rstest]
+    fn underline_fits_content_or_fills_width(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .heading_decoration(1, HeadingDecoration::Underline)
+            .heading_decoration(2, HeadingDecoration::Underline);
+        let border = Style::new().cyan();
+
+        assert_eq!(
+            from_str_with_options("# Title", &options),
+            Text::from_iter([
+                Line::from_iter(["# ", "Title"]).style(Style::new().on_cyan().bold().underlined()),
+                Line::from(Span::styled("═══════", border)),
+            ])
+        );
+        assert_eq!(
+            from_str_with_options("> ## Quoted", &options.width(12)).to_string(),
+            "> ## Quoted\n> ──────────"
+        );
+    }
+
+    #[rstest]
+    fn centered_heading_uses_line_alignment(_with_tracing: DefaultGuard) {
+        let options = Options::default().heading_decoration(1, HeadingDecoration::Centered);
+        let h1 = Style::new().on_cyan().bold().underlined();
+
+        assert_eq!(
+            from_str_with_options("# Title\n\nText", &options),
+            Text::from_iter([
+                Line::from_iter(["# ", "Title"]).style(h1).centered(),
+                Line::default(),
+                Line::from("Text"),
+            ])
+        );
+    }
+
+    #[rstest]
+    fn boxed_heading_styles_content_and_borders(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .heading_decoration(2, HeadingDecoration::Boxed)
+            .heading_numbering(HeadingNumbering::FromH2);
+        let h2 = Style::new().cyan().bold();
+        let border = Style::new().cyan();
+
+        assert_eq!(
+            from_str_with_options("Intro\n\n## *Setup*", &options),
+            Text::from_iter([
+                Line::from("Intro"),
+                Line::default(),
+                Line::from(Span::styled("┌─────────────┐", border)),
+                Line::from_iter([
+                    Span::styled("│ ", border),
+                    Span::styled("## ", h2),
+                    Span::styled("1. ", h2),
+                    Span::styled("Setup", h2.italic()),
+                    Span::styled(" │", border),
+                ]),
+                Line::from(Span::styled("└─────────────┘", border)),
+            ])
+        );
+    }
+
+    #[rstest]
+    fn big_heading_uses_heading_style_and_number(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .big_headings(BigHeadings::H1)
+            .heading_numbering(HeadingNumbering::FromH1);
+        let h1 = Style::new().on_cyan().bold().underlined();
+
+        assert_eq!(
+            from_str_with_options("# Hi {#top}\n\n## Hi", &options),
+            Text::from_iter([
+                Line::from(" ▄█         █   █  ▀█▀").style(h1),
+                Line::from("  █         █▄▄▄█   █ ").style(h1),
+                Line::from("  █         █   █   █ ").style(h1),
+                Line::from(" ▀▀▀  ▀     ▀   ▀  ▀▀▀").style(h1),
+                Line::default(),
+                Line::from_iter(["## ", "1.1. ", "Hi"]).style(Style::new().cyan().bold()),
+            ])
+        );
+    }
+
+    #[rstest]
+    fn big_heading_falls_back_when_it_does_not_fit(_with_tracing: DefaultGuard) {
+        let options = Options::default().big_headings(BigHeadings::H1AndH2);
+
+        assert_eq!(
+            from_str_with_options("## Café", &options).to_string(),
+            "## Café"
+        );
+        assert_eq!(
+            from_str_with_options("> ## Hi", &options.width(11)).to_string(),
+            "> ## Hi"
+        );
+    }
+
+    #[rstest]
+    fn centered_big_heading_rows_share_a_width(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .big_headings(BigHeadings::H1)
+            .heading_decoration(1, HeadingDecoration::Centered);
+        let text = from_str_with_options("# Hi", &options);
+
+        assert_eq!(text.lines.len(), 4);
+        for line in &text.lines {
+            assert_eq!(line.alignment, Some(Alignment::Center));
+            assert_eq!(line.width(), 10, "{line}");
+        }
+    }
+
+    #[rstest]
+    fn big_heading_is_boxed_on_every_row(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .big_headings(BigHeadings::H1)
+            .heading_decoration(1, HeadingDecoration::Boxed);
+
+        assert_eq!(
+            from_str_with_options("# I", &options).to_string(),
+            indoc! {"
+                ┌──────┐
+                │  ▀█▀ │
+                │   █  │
+                │   █  │
+                │  ▀▀▀ │
+                └──────┘"}
+        );
+    }
 }
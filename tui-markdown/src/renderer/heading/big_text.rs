@@ -0,0 +1,148 @@
+//! Large block letters for headings.
+//!
+//! The bundled font draws capital letters, digits, and common punctuation on a 5×7 pixel grid.
+//! Pairs of pixel rows share one terminal row through the upper and lower half-block characters,
+//! so each line of big text is four rows tall. Lowercase letters are drawn as capitals.
+
+/// Number of pixel rows in each glyph.
+const GLYPH_HEIGHT: usize = 7;
+
+/// Columns between adjacent glyphs.
+const LETTER_SPACING: usize = 1;
+
+/// Renders `text` as rows of block letters.
+///
+/// Returns `None` when `text` is empty or contains a character the font does not include.
+pub fn render(text: &str) -> Option<Vec<String>> {
+    if text.is_empty() {
+        return None;
+    }
+    let glyphs = text
+        .chars()
+        .map(|c| glyph(c.to_ascii_uppercase()))
+        .collect::<Option<Vec<_>>>()?;
+
+    // Pixel rows, padded with an empty row so they pair into terminal rows.
+    let mut pixels = vec![Vec::new(); GLYPH_HEIGHT + 1];
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let width = glyph[0].len();
+        for (row, pixel_row) in pixels.iter_mut().enumerate() {
+            if index > 0 {
+                pixel_row.extend(std::iter::repeat_n(false, LETTER_SPACING));
+            }
+            match glyph.get(row) {
+                Some(glyph_row) => pixel_row.extend(glyph_row.bytes().map(|pixel| pixel == b'#')),
+                None => pixel_row.extend(std::iter::repeat_n(false, width)),
+            }
+        }
+    }
+
+    let mut rows: Vec<String> = pixels
+        .chunks(2)
+        .map(|pair| {
+            pair[0]
+                .iter()
+                .zip(&pair[1])
+                .map(|pixels| match pixels {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect();
+    // Only blank columns shared by every row are trimmed, so the rows keep a common width and
+    // stay aligned when centered.
+    let blank_columns = rows
+        .iter()
+        .map(|row| row.len() - row.trim_end().len())
+        .min()
+        .unwrap_or_default();
+    for row in &mut rows {
+        row.truncate(row.len() - blank_columns);
+    }
+    Some(rows)
+}
+
+/// Returns the pixel rows of a glyph, with `#` for set pixels.
+#[rustfmt::skip]
+fn glyph(c: char) -> Option<[&'static str; GLYPH_HEIGHT]> {
+    let glyph = match c {
+        'A' => [".###.", "#...#", "#...#", "#####", "#...#", "#...#", "#...#"],
+        'B' => ["####.", "#...#", "#...#", "####.", "#...#", "#...#", "####."],
+        'C' => [".###.", "#...#", "#....", "#....", "#....", "#...#", ".###."],
+        'D' => ["####.", "#...#", "#...#", "#...#", "#...#", "#...#", "####."],
+        'E' => ["#####", "#....", "#....", "####.", "#....", "#....", "#####"],
+        'F' => ["#####", "#....", "#....", "####.", "#....", "#....", "#...."],
+        'G' => [".###.", "#...#", "#....", "#.###", "#...#", "#...#", ".####"],
+        'H' => ["#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#"],
+        'I' => [".###.", "..#..", "..#..", "..#..", "..#..", "..#..", ".###."],
+        'J' => ["..###", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##.."],
+        'K' => ["#...#", "#..#.", "#.#..", "##...", "#.#..", "#..#.", "#...#"],
+        'L' => ["#....", "#....", "#....", "#....", "#....", "#....", "#####"],
+        'M' => ["#...#", "##.##", "#.#.#", "#.#.#", "#...#", "#...#", "#...#"],
+        'N' => ["#...#", "#...#", "##..#", "#.#.#", "#..##", "#...#", "#...#"],
+        'O' => [".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###."],
+        'P' => ["####.", "#...#", "#...#", "####.", "#....", "#....", "#...."],
+        'Q' => [".###.", "#...#", "#...#", "#...#", "#.#.#", "#..#.", ".##.#"],
+        'R' => ["####.", "#...#", "#...#", "####.", "#.#..", "#..#.", "#...#"],
+        'S' => [".####", "#....", "#....", ".###.", "....#", "....#", "####."],
+        'T' => ["#####", "..#..", "..#..", "..#..", "..#..", "..#..", "..#.."],
+        'U' => ["#...#", "#...#", "#...#", "#...#", "#...#", "#...#", ".###."],
+        'V' => ["#...#", "#...#", "#...#", "#...#", "#...#", ".#.#.", "..#.."],
+        'W' => ["#...#", "#...#", "#...#", "#.#.#", "#.#.#", "#.#.#", ".#.#."],
+        'X' => ["#...#", "#...#", ".#.#.", "..#..", ".#.#.", "#...#", "#...#"],
+        'Y' => ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "..#.."],
+        'Z' => ["#####", "....#", "...#.", "..#..", ".#...", "#....", "#####"],
+        '0' => [".###.", "#...#", "#..##", "#.#.#", "##..#", "#...#", ".###."],
+        '1' => ["..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###."],
+        '2' => [".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####"],
+        '3' => ["#####", "...#.", "..#..", "...#.", "....#", "#...#", ".###."],
+        '4' => ["...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#."],
+        '5' => ["#####", "#....", "####.", "....#", "....#", "#...#", ".###."],
+        '6' => ["..##.", ".#...", "#....", "####.", "#...#", "#...#", ".###."],
+        '7' => ["#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#..."],
+        '8' => [".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###."],
+        '9' => [".###.", "#...#", "#...#", ".####", "....#", "...#.", ".##.."],
+        ' ' => ["...", "...", "...", "...", "...", "...", "..."],
+        '.' => [".", ".", ".", ".", ".", ".", "#"],
+        ',' => [".", ".", ".", ".", ".", "#", "#"],
+        ':' => [".", "#", ".", ".", ".", "#", "."],
+        '!' => ["#", "#", "#", "#", "#", ".", "#"],
+        '\'' => ["#", "#", ".", ".", ".", ".", "."],
+        '-' => ["...", "...", "...", "###", "...", "...", "..."],
+        '?' => [".###.", "#...#", "....#", "...#.", "..#..", ".....", "..#.."],
+        '/' => ["....#", "...#.", "...#.", "..#..", ".#...", ".#...", "#...."],
+        _ => return None,
+    };
+    Some(glyph)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn renders_half_block_rows() {
+        assert_eq!(
+            render("Hi").unwrap(),
+            ["█   █  ▀█▀", "█▄▄▄█   █ ", "█   █   █ ", "▀   ▀  ▀▀▀",]
+        );
+    }
+
+    #[test]
+    fn glyphs_have_consistent_widths() {
+        for c in (' '..='~').filter_map(glyph) {
+            assert!(c.iter().all(|row| row.len() == c[0].len()), "{c:?}");
+        }
+    }
+
+    #[test]
+    fn unsupported_text_is_rejected() {
+        assert_eq!(render(""), None);
+        assert_eq!(render("Crème"), None);
+    }
+}
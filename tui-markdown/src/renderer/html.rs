@@ -1,13 +1,41 @@
 //! Raw Markdown HTML rendering.
 //!
 //! HTML remains visible as literal text. Inline tags compose with enclosing formatting, while HTML
-//! blocks preserve their physical lines and surrounding block spacing.
+//! blocks preserve their physical lines and surrounding block spacing. HTML blocks are buffered
+//! until they end so that `<details>` tags inside them can be recognized, and [`HtmlBlockMode`] can
+//! hide them, remove their comments, or replace them with a text summary.
+//!
+//! With [`InlineHtmlMode::Interpret`], common inline formatting tags instead push and pop styles on
+//! the inline style stack, `<br>` breaks the line, and `<a href>` renders like a Markdown link.
+//! Links cannot nest, so an anchor inside a link keeps only its text. Unknown tags and unmatched
+//! end tags still render literally.
 
 use pulldown_cmark::{CowStr, Event};
+use ratatui_core::style::Style;
 use ratatui_core::text::{Line, Span};
 
+use self::summary::SummaryPart;
+use self::tag::HtmlTag;
+use super::details::{self, HtmlBlockPart};
+use super::image::PendingImage;
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{HtmlBlockMode, InlineElement, InlineHtmlMode, StyleSheet};
+
+mod summary;
+pub mod tag;
+
+/// An interpreted inline HTML tag whose end tag has not been seen yet.
+#[derive(Debug)]
+pub struct OpenHtmlTag {
+    name: String,
+    /// Length of the inline style stack after the start tag pushed its style.
+    ///
+    /// An end tag only closes this element when the stack is back at this depth, so malformed HTML
+    /// such as `<b>*text</b>*` cannot pop a style that belongs to Markdown formatting.
+    style_depth: usize,
+    /// Whether the start tag opened a link, which its end tag closes.
+    opens_link: bool,
+}
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
@@ -15,21 +43,67 @@ where
     S: StyleSheet,
 {
     pub fn start_html_block(&mut self) {
-        if self.needs_newline {
-            self.push_line(Line::default());
+        self.html_block = Some(String::new());
+    }
+
+    pub fn html_block(&mut self, html: CowStr<'a>) {
+        match &mut self.html_block {
+            Some(block) => block.push_str(&html),
+            None => self.render_html_block_part(&html),
         }
-        self.push_line(Line::default());
-        self.line_styles.push(self.styles.html());
-        self.needs_newline = false;
     }
 
+    /// Renders a buffered HTML block once its complete content is known.
+    ///
+    /// `<details>` structure can share a block with other HTML, so the block is split first and
+    /// only the remaining HTML is written as literal lines.
     pub fn end_html_block(&mut self) {
-        self.line_styles.pop();
-        self.needs_newline = true;
+        let Some(html) = self.html_block.take() else {
+            return;
+        };
+        if self.details_default.is_none() {
+            self.render_html_block_part(&html);
+            return;
+        }
+        for part in details::split(&html) {
+            match part {
+                HtmlBlockPart::DetailsStart { open } => self.start_details(open),
+                HtmlBlockPart::Summary(summary) => self.details_summary(&summary),
+                HtmlBlockPart::DetailsEnd => self.end_details(),
+                HtmlBlockPart::Html(html) => {
+                    self.write_pending_details_summary();
+                    if !self.in_collapsed_details() {
+                        self.render_html_block_part(html);
+                    }
+                }
+            }
+        }
     }
 
-    pub fn html_block(&mut self, html: CowStr<'a>) {
+    /// Writes block HTML according to the configured [`HtmlBlockMode`].
+    pub fn render_html_block_part(&mut self, html: &str) {
+        match self.html_block_mode {
+            HtmlBlockMode::Literal => self.write_html_lines(html),
+            HtmlBlockMode::Hide => {}
+            HtmlBlockMode::CommentFree => {
+                let lines = summary::strip_comment_lines(html);
+                if lines.iter().any(|line| !line.trim().is_empty()) {
+                    self.write_html_lines(&lines.join("\n"));
+                }
+            }
+            HtmlBlockMode::Summarize => self.write_html_summary(html),
+        }
+    }
+
+    /// Writes HTML as a block of literal lines separated from surrounding blocks.
+    fn write_html_lines(&mut self, html: &str) {
+        if self.needs_newline {
+            self.push_line(Line::default());
+        }
+        self.push_line(Line::default());
         let style = self.styles.html();
+        self.line_styles.push(style);
+        self.needs_newline = false;
         for line in html.lines() {
             if self.needs_newline {
                 self.push_line(Line::default());
@@ -38,13 +112,146 @@ where
             self.push_span(Span::styled(line.to_owned(), style));
             self.needs_newline = true;
         }
+        self.line_styles.pop();
+        self.needs_newline = true;
+    }
+
+    /// Writes the text, images, and link destinations extracted from block HTML.
+    fn write_html_summary(&mut self, html: &str) {
+        let lines = summary::summarize(html);
+        if lines.is_empty() {
+            return;
+        }
+        if self.needs_newline {
+            self.push_line(Line::default());
+        }
+        for parts in lines {
+            self.push_line(Line::default());
+            for part in parts {
+                match part {
+                    SummaryPart::Text(text) => {
+                        self.push_span(Span::styled(text, self.styles.html()));
+                    }
+                    SummaryPart::Image { alt, src } => {
+                        let mut image = PendingImage::new(src.into(), self.styles.image_alt());
+                        if !alt.is_empty() {
+                            image.push_span(Span::styled(alt, self.styles.image_alt()));
+                        }
+                        for span in image.into_fallback(self.image_fallback) {
+                            self.push_span(span);
+                        }
+                    }
+                    SummaryPart::Href(href) => {
+                        self.push_span(Span::styled(href, self.styles.link()));
+                    }
+                }
+            }
+        }
+        self.needs_newline = true;
     }
 
     pub fn inline_html(&mut self, html: CowStr<'a>) {
-        let inline_style = self.inline_styles.last().copied().unwrap_or_default();
+        if self.inline_html_mode == InlineHtmlMode::Interpret && self.interpret_inline_html(&html) {
+            return;
+        }
+        let inline_style = self.inline_style();
         let style = inline_style.patch(self.styles.html());
         self.push_span(Span::styled(html, style));
     }
+
+    /// Applies a recognized inline tag, returning `false` when it should render literally.
+    fn interpret_inline_html(&mut self, html: &str) -> bool {
+        let Some(tag) = HtmlTag::parse(html) else {
+            return false;
+        };
+        if tag.closing {
+            return self.close_inline_html_tag(&tag.name);
+        }
+        let mut opens_link = false;
+        match tag.name.as_str() {
+            "br" => self.hard_break(),
+            "a" => {
+                let Some(href) = tag.attr("href") else {
+                    return false;
+                };
+                // Links cannot nest, so an anchor inside an open link only keeps its text.
+                if self.link.is_some() {
+                    self.push_inline_style(Style::default());
+                } else {
+                    self.push_link(CowStr::from(href.to_owned()));
+                    opens_link = true;
+                }
+            }
+            name => {
+                let Some(style) = self.inline_html_style(name) else {
+                    return false;
+                };
+                self.push_inline_style(style);
+            }
+        }
+        if tag.name != "br" {
+            self.inline_html_tags.push(OpenHtmlTag {
+                name: tag.name,
+                style_depth: self.inline_styles.len(),
+                opens_link,
+            });
+        }
+        true
+    }
+
+    fn inline_html_style(&self, name: &str) -> Option<Style> {
+        let style = match name {
+            "b" | "strong" => self.element_style(InlineElement::Strong, self.styles.strong()),
+            "i" | "em" | "cite" | "var" => {
+                self.element_style(InlineElement::Emphasis, self.styles.emphasis())
+            }
+            "u" | "ins" => self.styles.underline(),
+            "s" | "del" | "strike" => {
+                self.element_style(InlineElement::Strikethrough, self.styles.strikethrough())
+            }
+            "sub" => self.element_style(InlineElement::Subscript, self.styles.subscript()),
+            "sup" => self.element_style(InlineElement::Superscript, self.styles.superscript()),
+            "mark" => self.styles.mark(),
+            "code" => self.element_style(InlineElement::Code, self.styles.code()),
+            "kbd" => self.styles.keyboard_key(),
+            _ => return None,
+        };
+        Some(style)
+    }
+
+    fn close_inline_html_tag(&mut self, name: &str) -> bool {
+        let matches_open_tag = self
+            .inline_html_tags
+            .last()
+            .is_some_and(|open| open.name == name && open.style_depth == self.inline_styles.len());
+        if !matches_open_tag {
+            return false;
+        }
+        if self
+            .inline_html_tags
+            .pop()
+            .is_some_and(|open| open.opens_link)
+        {
+            self.pop_link();
+        } else {
+            self.pop_inline_style();
+        }
+        true
+    }
+
+    /// Closes interpreted tags left open when their enclosing block ends.
+    ///
+    /// Inline HTML cannot span blocks, so an unclosed `<b>` must not style the following
+    /// paragraph.
+    pub fn close_inline_html_tags(&mut self) {
+        while let Some(open) = self.inline_html_tags.pop() {
+            if open.opens_link {
+                self.pop_link();
+            } else {
+                self.pop_inline_style();
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -116,5 +323,265 @@ mod tests {
                 ])
             );
         }
+
+        const README_HEADER: &str = indoc! {r#"
+            <!-- Badges -->
+            <p align="center">
+              <a href="https://crates.io"><img alt="Crate" src="crate.svg"></a>
+              <a href="https://docs.rs"></a>
+            </p>
+
+            # Title
+        "#};
+
+        #[rstest]
+        fn html_block_mode_hide_omits_blocks(_with_tracing: DefaultGuard) {
+            let options = Options::default().html_block_mode(HtmlBlockMode::Hide);
+
+            assert_eq!(
+                from_str_with_options(README_HEADER, &options).to_string(),
+                "# Title"
+            );
+        }
+
+        #[rstest]
+        fn html_block_mode_comment_free_removes_comments(_with_tracing: DefaultGuard) {
+            let markdown = "<!-- only a comment -->\n\n<div>\n<!-- note -->\nText\n</div>";
+            let options = Options::default().html_block_mode(HtmlBlockMode::CommentFree);
+            let html = Style::new().dim();
+
+            assert_eq!(
+                from_str_with_options(markdown, &options),
+                Text::from_iter([
+                    Line::from(Span::styled("<div>", html)),
+                    Line::from(Span::styled("Text", html)).style(html),
+                    Line::from(Span::styled("</div>", html)).style(html),
+                ])
+            );
+        }
+
+        #[rstest]
+        fn html_block_mode_comment_free_keeps_blank_lines(_with_tracing: DefaultGuard) {
+            let markdown = "<pre>\na\n\nb\n</pre>";
+            let options = Options::default().html_block_mode(HtmlBlockMode::CommentFree);
+
+            assert_eq!(
+                from_str_with_options(markdown, &options).to_string(),
+                "<pre>\na\n\nb\n</pre>"
+            );
+        }
+
+        #[rstest]
+        fn html_block_mode_summarize_extracts_images_and_links(_with_tracing: DefaultGuard) {
+            let options = Options::default().html_block_mode(HtmlBlockMode::Summarize);
+            let image = Style::new().dim().italic();
+            let link = Style::new().blue().underlined();
+            let heading = Style::new().on_cyan().bold().underlined();
+
+            assert_eq!(
+                from_str_with_options(README_HEADER, &options),
+                Text::from_iter([
+                    Line::from_iter([
+                        Span::styled("[img] ", image),
+                        Span::styled("Crate", image),
+                        Span::styled(" ", Style::new().dim()),
+                        Span::styled("https://docs.rs", link),
+                    ]),
+                    Line::default(),
+                    Line::from_iter(["# ", "Title"]).style(heading),
+                ])
+            );
+        }
+
+        #[rstest]
+        fn html_block_mode_summarize_uses_image_fallback(_with_tracing: DefaultGuard) {
+            let options = Options::default()
+                .html_block_mode(HtmlBlockMode::Summarize)
+                .image_fallback(ImageFallback::Url);
+
+            assert_eq!(
+                from_str_with_options(r#"<img alt="Logo" src="logo.png">"#, &options).to_string(),
+                "[img] logo.png"
+            );
+        }
+
+        #[rstest]
+        fn interpreted_tags_style_their_content(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("<b>bold</b> <i>italic</i> <u>under</u>", &options),
+                Text::from(Line::from_iter([
+                    Span::styled("bold", Style::new().bold()),
+                    Span::raw(" "),
+                    Span::styled("italic", Style::new().italic()),
+                    Span::raw(" "),
+                    Span::styled("under", Style::new().underlined()),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn interpreted_tags_nest_with_markdown_formatting(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("*a <mark>b</mark> c*", &options),
+                Text::from(Line::from_iter([
+                    Span::styled("a ", Style::new().italic()),
+                    Span::styled("b", Style::new().italic().black().on_yellow()),
+                    Span::styled(" c", Style::new().italic()),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn formatting_tags_use_style_sheet(_with_tracing: DefaultGuard) {
+            #[derive(Clone, Copy)]
+            struct CustomFormatting;
+
+            impl StyleSheet for CustomFormatting {
+                fn emphasis(&self) -> Style {
+                    Style::new().underlined()
+                }
+
+                fn strong(&self) -> Style {
+                    Style::new().yellow()
+                }
+            }
+
+            let options =
+                Options::new(CustomFormatting).inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("<em>a</em> <b>b</b>", &options),
+                Text::from(Line::from_iter([
+                    Span::styled("a", Style::new().underlined()),
+                    Span::raw(" "),
+                    Span::styled("b", Style::new().yellow()),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn underline_and_mark_use_style_sheet(_with_tracing: DefaultGuard) {
+            #[derive(Clone, Copy)]
+            struct CustomMarks;
+
+            impl StyleSheet for CustomMarks {
+                fn underline(&self) -> Style {
+                    Style::new().cyan()
+                }
+
+                fn mark(&self) -> Style {
+                    Style::new().on_blue()
+                }
+            }
+
+            let options = Options::new(CustomMarks).inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("<u>a</u> <ins>b</ins> <mark>c</mark>", &options),
+                Text::from(Line::from_iter([
+                    Span::styled("a", Style::new().cyan()),
+                    Span::raw(" "),
+                    Span::styled("b", Style::new().cyan()),
+                    Span::raw(" "),
+                    Span::styled("c", Style::new().on_blue()),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn kbd_uses_keyboard_key_style(_with_tracing: DefaultGuard) {
+            #[derive(Clone, Copy)]
+            struct CustomKeyStyle;
+
+            impl StyleSheet for CustomKeyStyle {
+                fn keyboard_key(&self) -> Style {
+                    Style::new().red().bold()
+                }
+            }
+
+            let options = Options::new(CustomKeyStyle).inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("Press <kbd>Ctrl</kbd>+<kbd>C</kbd>", &options),
+                Text::from(Line::from_iter([
+                    Span::raw("Press "),
+                    Span::styled("Ctrl", Style::new().red().bold()),
+                    Span::raw("+"),
+                    Span::styled("C", Style::new().red().bold()),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn br_breaks_the_line(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("one<br>two<br/>three", &options),
+                Text::from_iter(["one", "two", "three"])
+            );
+        }
+
+        #[rstest]
+        fn anchor_renders_as_link(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+            let link = Style::new().blue().underlined();
+
+            assert_eq!(
+                from_str_with_options(r#"<a href="https://example.com">site</a>"#, &options),
+                Text::from(Line::from_iter([
+                    Span::styled("site", link),
+                    Span::raw(" ("),
+                    Span::styled("https://example.com", link),
+                    Span::raw(")"),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn anchor_inside_link_keeps_outer_destination(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+            let markdown = r#"[outer <a href="inner">in</a> tail](outer)"#;
+
+            assert_eq!(
+                from_str_with_options(markdown, &options).to_string(),
+                "outer in tail (outer)"
+            );
+        }
+
+        #[rstest]
+        fn unknown_and_unmatched_tags_render_literally(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+            let html = Style::new().dim();
+
+            assert_eq!(
+                from_str_with_options("<span>x</span> y</b>", &options),
+                Text::from(Line::from_iter([
+                    Span::styled("<span>", html),
+                    Span::raw("x"),
+                    Span::styled("</span>", html),
+                    Span::raw(" y"),
+                    Span::styled("</b>", html),
+                ]))
+            );
+        }
+
+        #[rstest]
+        fn unclosed_tag_does_not_leak_into_next_paragraph(_with_tracing: DefaultGuard) {
+            let options = Options::default().inline_html_mode(InlineHtmlMode::Interpret);
+
+            assert_eq!(
+                from_str_with_options("<b>open\n\nnext", &options),
+                Text::from_iter([
+                    Line::from(Span::styled("open", Style::new().bold())),
+                    Line::default(),
+                    Line::from("next"),
+                ])
+            );
+        }
     }
 }
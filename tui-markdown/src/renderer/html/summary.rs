@@ -0,0 +1,315 @@
+//! Text summaries of HTML blocks.
+//!
+//! README badge rows and centered logos are HTML blocks whose useful content is in attributes:
+//! image descriptions, image sources, and link destinations. [`summarize`] keeps the visible text,
+//! turns images and otherwise empty links into [`SummaryPart`] values, and starts a new line for
+//! block-level elements. Comments, unknown tags, and attributes are dropped.
+
+use std::borrow::Cow;
+
+use super::tag::HtmlTag;
+
+/// Tags that start a new summary line, as they would start a new line in a browser.
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// A piece of a summarized HTML line.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SummaryPart {
+    /// Visible text with whitespace collapsed and common character references decoded.
+    Text(String),
+    /// An `<img>` element's description and source.
+    Image { alt: String, src: String },
+    /// The destination of an `<a href>` element that contains no text or image.
+    Href(String),
+}
+
+/// Removes `<!-- … -->` comments, including an unterminated comment at the end of `html`.
+pub fn strip_comments(html: &str) -> Cow<'_, str> {
+    if !html.contains("<!--") {
+        return Cow::Borrowed(html);
+    }
+    let mut stripped = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<!--") {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start + 4..].find("-->") {
+            Some(end) => &rest[start + 4 + end + 3..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+    Cow::Owned(stripped)
+}
+
+/// Removes `<!-- … -->` comments from each line of `html`, including comments that span lines.
+///
+/// Lines left blank by removing a comment are dropped, while lines that were already blank are
+/// kept.
+pub fn strip_comment_lines(html: &str) -> Vec<Cow<'_, str>> {
+    let mut lines = vec![];
+    let mut in_comment = false;
+    for line in html.lines() {
+        if !in_comment && !line.contains("<!--") {
+            lines.push(Cow::Borrowed(line));
+            continue;
+        }
+        let mut stripped = String::new();
+        let mut rest = line;
+        loop {
+            if in_comment {
+                let Some(end) = rest.find("-->") else {
+                    break;
+                };
+                rest = &rest[end + 3..];
+                in_comment = false;
+            }
+            let Some(start) = rest.find("<!--") else {
+                stripped.push_str(rest);
+                break;
+            };
+            stripped.push_str(&rest[..start]);
+            rest = &rest[start + 4..];
+            in_comment = true;
+        }
+        if !stripped.trim().is_empty() {
+            lines.push(Cow::Owned(stripped));
+        }
+    }
+    lines
+}
+
+/// Summarizes `html` as lines of visible text, images, and link destinations.
+///
+/// Lines without content are omitted, so a block containing only comments or empty elements
+/// returns no lines.
+pub fn summarize(html: &str) -> Vec<Vec<SummaryPart>> {
+    let html = strip_comments(html);
+    let mut summary = Summary::default();
+    let mut rest = html.as_ref();
+    while let Some(start) = rest.find('<') {
+        summary.text(&rest[..start]);
+        match HtmlTag::parse_prefix(&rest[start..]) {
+            Some((tag, after)) => {
+                summary.tag(&tag);
+                rest = after;
+            }
+            None => {
+                summary.text("<");
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    summary.text(rest);
+    summary.finish()
+}
+
+#[derive(Default)]
+struct Summary {
+    lines: Vec<Vec<SummaryPart>>,
+    line: Vec<SummaryPart>,
+    /// Destinations of open links, with whether each has shown any content yet.
+    links: Vec<(String, bool)>,
+}
+
+impl Summary {
+    fn text(&mut self, text: &str) {
+        let text = decode_references(text);
+        if text.is_empty() {
+            return;
+        }
+        let mut collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() {
+            collapsed.push(' ');
+        } else {
+            self.mark_link_content();
+            if text.starts_with(char::is_whitespace) {
+                collapsed.insert(0, ' ');
+            }
+            if text.ends_with(char::is_whitespace) {
+                collapsed.push(' ');
+            }
+        }
+        match self.line.last_mut() {
+            Some(SummaryPart::Text(previous)) => {
+                if previous.ends_with(' ') && collapsed.starts_with(' ') {
+                    collapsed.remove(0);
+                }
+                previous.push_str(&collapsed);
+            }
+            _ => self.line.push(SummaryPart::Text(collapsed)),
+        }
+    }
+
+    fn tag(&mut self, tag: &HtmlTag) {
+        if BLOCK_TAGS.contains(&tag.name.as_str()) {
+            self.finish_line();
+        }
+        match (tag.name.as_str(), tag.closing) {
+            ("img", false) => {
+                self.mark_link_content();
+                self.line.push(SummaryPart::Image {
+                    alt: tag.attr("alt").unwrap_or_default().to_owned(),
+                    src: tag.attr("src").unwrap_or_default().to_owned(),
+                });
+            }
+            ("a", false) => {
+                let href = tag.attr("href").unwrap_or_default().to_owned();
+                self.links.push((href, false));
+            }
+            ("a", true) => {
+                if let Some((href, false)) = self.links.pop() {
+                    if !href.is_empty() {
+                        self.line.push(SummaryPart::Href(href));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn mark_link_content(&mut self) {
+        for (_, has_content) in &mut self.links {
+            *has_content = true;
+        }
+    }
+
+    fn finish_line(&mut self) {
+        let mut line = std::mem::take(&mut self.line);
+        if let Some(SummaryPart::Text(text)) = line.first_mut() {
+            *text = text.trim_start().to_owned();
+        }
+        if let Some(SummaryPart::Text(text)) = line.last_mut() {
+            *text = text.trim_end().to_owned();
+        }
+        line.retain(|part| !matches!(part, SummaryPart::Text(text) if text.is_empty()));
+        if !line.is_empty() {
+            self.lines.push(line);
+        }
+    }
+
+    fn finish(mut self) -> Vec<Vec<SummaryPart>> {
+        self.finish_line();
+        self.lines
+    }
+}
+
+/// Decodes the character references most common in README HTML.
+fn decode_references(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let decoded = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    Cow::Owned(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn text(text: &str) -> SummaryPart {
+        SummaryPart::Text(text.to_owned())
+    }
+
+    fn image(alt: &str, src: &str) -> SummaryPart {
+        SummaryPart::Image {
+            alt: alt.to_owned(),
+            src: src.to_owned(),
+        }
+    }
+
+    #[test]
+    fn strips_comments() {
+        assert_eq!(strip_comments("a<!-- b -->c<!-- d"), "ac");
+        assert!(matches!(strip_comments("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn strip_comment_lines_keeps_blank_lines_without_comments() {
+        assert_eq!(
+            strip_comment_lines("a\n\n<!-- b\nc -->\nd <!-- e --> f\n<!-- g -->"),
+            ["a", "", "d  f"]
+        );
+    }
+
+    #[test]
+    fn badge_block_summarizes_images() {
+        let html = indoc! {r#"
+            <p align="center">
+              <a href="https://crates.io/crates/demo"><img alt="Crate" src="crate.svg"></a>
+              <a href="https://docs.rs/demo"><img alt="Docs" src="docs.svg"></a>
+            </p>
+        "#};
+
+        assert_eq!(
+            summarize(html),
+            [vec![
+                image("Crate", "crate.svg"),
+                text(" "),
+                image("Docs", "docs.svg"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn block_tags_start_lines_and_text_is_kept() {
+        let html = "<div><h1>Demo &amp; more</h1><p>A <b>fast</b> tool.</p></div>";
+
+        assert_eq!(
+            summarize(html),
+            [vec![text("Demo & more")], vec![text("A fast tool.")]]
+        );
+    }
+
+    #[test]
+    fn empty_link_shows_its_destination() {
+        assert_eq!(
+            summarize(r#"<a href="https://example.com"></a> <a href="x">Label</a>"#),
+            [vec![
+                SummaryPart::Href("https://example.com".to_owned()),
+                text(" Label"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn comment_only_block_has_no_lines() {
+        assert!(summarize("<!-- hidden -->\n").is_empty());
+    }
+}
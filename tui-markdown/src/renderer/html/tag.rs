@@ -0,0 +1,153 @@
+//! A minimal HTML tag tokenizer.
+//!
+//! Markdown delivers raw HTML as text. This module recognizes the start, end, and self-closing tags
+//! that the renderer interprets, along with their attributes. It is not a general HTML parser:
+//! anything other than a single well-formed tag is left for the caller to render literally.
+
+/// A single HTML start, end, or self-closing tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlTag {
+    /// The lowercase tag name.
+    pub name: String,
+    /// Whether this is an end tag such as `</b>`.
+    pub closing: bool,
+    /// Attributes in source order, with lowercase names and unquoted values.
+    ///
+    /// An attribute without a value, such as `open` in `<details open>`, has an empty value.
+    pub attrs: Vec<(String, String)>,
+}
+
+impl HtmlTag {
+    /// Parses `source` when it consists of exactly one tag, ignoring surrounding whitespace.
+    pub fn parse(source: &str) -> Option<Self> {
+        let (tag, rest) = Self::parse_prefix(source.trim())?;
+        rest.is_empty().then_some(tag)
+    }
+
+    /// Parses the tag at the start of `source`, returning it with the text that follows.
+    pub fn parse_prefix(source: &str) -> Option<(Self, &str)> {
+        let source = source.strip_prefix('<')?;
+        let (closing, source) = match source.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, source),
+        };
+        let name_end = source
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(source.len());
+        if name_end == 0 || !source.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let name = source[..name_end].to_ascii_lowercase();
+        let mut rest = &source[name_end..];
+
+        let mut attrs = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix("/>") {
+                return Some((Self::new(name, closing, attrs), after));
+            }
+            if let Some(after) = rest.strip_prefix('>') {
+                return Some((Self::new(name, closing, attrs), after));
+            }
+            let (attr, after) = parse_attribute(rest)?;
+            attrs.push(attr);
+            rest = after;
+        }
+    }
+
+    fn new(name: String, closing: bool, attrs: Vec<(String, String)>) -> Self {
+        Self {
+            name,
+            closing,
+            attrs,
+        }
+    }
+
+    /// Returns the value of the named attribute, if present.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn parse_attribute(source: &str) -> Option<((String, String), &str)> {
+    let name_end = source
+        .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/' | '"' | '\''))
+        .unwrap_or(source.len());
+    if name_end == 0 {
+        return None;
+    }
+    let name = source[..name_end].to_ascii_lowercase();
+    let rest = source[name_end..].trim_start();
+    let Some(rest) = rest.strip_prefix('=') else {
+        return Some(((name, String::new()), rest));
+    };
+    let rest = rest.trim_start();
+    let (value, rest) = match rest.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let rest = &rest[1..];
+            let end = rest.find(quote)?;
+            (&rest[..end], &rest[end + 1..])
+        }
+        _ => {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '>')
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        }
+    };
+    Some(((name, value.to_owned()), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn start_and_end_tags() {
+        let start = HtmlTag::parse("<KBD>").unwrap();
+        assert_eq!(start.name, "kbd");
+        assert!(!start.closing);
+
+        let end = HtmlTag::parse("</kbd>").unwrap();
+        assert_eq!(end.name, "kbd");
+        assert!(end.closing);
+    }
+
+    #[test]
+    fn self_closing_tag() {
+        let tag = HtmlTag::parse("<br/>").unwrap();
+        assert_eq!(tag.name, "br");
+        assert!(!tag.closing);
+    }
+
+    #[test]
+    fn quoted_unquoted_and_empty_attributes() {
+        let tag = HtmlTag::parse(r#"<a href="https://example.com" title='A b' data-x=1 hidden>"#)
+            .unwrap();
+        assert_eq!(tag.attr("href"), Some("https://example.com"));
+        assert_eq!(tag.attr("title"), Some("A b"));
+        assert_eq!(tag.attr("data-x"), Some("1"));
+        assert_eq!(tag.attr("hidden"), Some(""));
+        assert_eq!(tag.attr("missing"), None);
+    }
+
+    #[test]
+    fn prefix_returns_following_text() {
+        let (tag, rest) = HtmlTag::parse_prefix("<b>bold</b>").unwrap();
+        assert_eq!(tag.name, "b");
+        assert_eq!(rest, "bold</b>");
+    }
+
+    #[test]
+    fn rejects_non_tags() {
+        assert_eq!(HtmlTag::parse("<!-- comment -->"), None);
+        assert_eq!(HtmlTag::parse("<b>text</b>"), None);
+        assert_eq!(HtmlTag::parse("<a href=\"unterminated>"), None);
+        assert_eq!(HtmlTag::parse("< b>"), None);
+    }
+}
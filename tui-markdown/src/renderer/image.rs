@@ -25,7 +25,7 @@ pub struct PendingImage<'a> {
 }
 
 impl<'a> PendingImage<'a> {
-    fn new(destination: CowStr<'a>, style: Style) -> Self {
+    pub fn new(destination: CowStr<'a>, style: Style) -> Self {
         Self {
             destination,
             style,
@@ -37,7 +37,7 @@ impl<'a> PendingImage<'a> {
         self.description.push(span);
     }
 
-    fn into_fallback(self, fallback: ImageFallback) -> Vec<Span<'a>> {
+    pub fn into_fallback(self, fallback: ImageFallback) -> Vec<Span<'a>> {
         let Self {
             destination,
             style,
@@ -88,7 +88,7 @@ where
     #[instrument(level = "trace", skip(self))]
     pub fn start_image(&mut self, dest_url: CowStr<'a>) {
         self.push_inline_style(self.styles.image_alt());
-        let style = self.inline_styles.last().copied().unwrap_or_default();
+        let style = self.inline_style();
         self.images.push(PendingImage::new(dest_url, style));
     }
 
@@ -109,7 +109,7 @@ where
     pub fn image_description_break(&mut self) {
         // Image descriptions are inline content. Keep a break readable without allowing it to
         // split the surrounding document, and retain the image style in case it has a background.
-        let style = self.inline_styles.last().copied().unwrap_or_default();
+        let style = self.inline_style();
         self.push_span(Span::styled(" ", style));
     }
 }
@@ -8,7 +8,7 @@ use ratatui_core::text::Span;
 use tracing::instrument;
 
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{InlineElement, StyleSheet};
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
@@ -19,7 +19,8 @@ where
     #[instrument(level = "trace", skip(self))]
     pub fn push_link(&mut self, dest_url: CowStr<'a>) {
         self.link = Some(dest_url);
-        self.push_inline_style(self.styles.link());
+        let style = self.element_style(InlineElement::Link, self.styles.link());
+        self.push_inline_style(style);
     }
 
     /// Restores the enclosing style and appends the destination.
@@ -28,7 +29,8 @@ where
         self.pop_inline_style();
         if let Some(link) = self.link.take() {
             self.push_span(" (".into());
-            self.push_span(Span::styled(link, self.styles.link()));
+            let style = self.element_style(InlineElement::Link, self.styles.link());
+            self.push_span(Span::styled(link, style));
             self.push_span(")".into());
         }
     }
@@ -1,11 +1,17 @@
 //! Markdown list and task-item rendering.
+//!
+//! Markers come from the style sheet: a bullet for unordered items, or a number in the level's
+//! numbering scheme followed by its delimiter. Each nesting level indents markers by four more
+//! columns. Bullets and the numbers of ordered markers are right-aligned within the indentation,
+//! so markers wider than the indentation push the item content right.
 
 use pulldown_cmark::Event;
-use ratatui_core::style::Stylize;
+use ratatui_core::style::Style;
 use ratatui_core::text::{Line, Span};
+use unicode_width::UnicodeWidthStr;
 
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{CompletedTasks, StyleSheet};
 
 /// Records how an active list item occupies the rendered output.
 ///
@@ -27,6 +33,10 @@ pub struct ListItemLayout {
     /// This uses terminal display width rather than bytes so continuation lines align after
     /// unordered markers and multi-digit ordered markers alike.
     pub continuation_width: usize,
+    /// Whether the item belongs to an ordered list.
+    pub ordered: bool,
+    /// Whether a completed-task style was pushed for the item's content.
+    pub completed_style: bool,
 }
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
@@ -49,15 +59,23 @@ where
     pub fn start_item(&mut self) {
         let marker_line = self.text.lines.len();
         self.push_line(Line::default());
-        let width = self.list_indices.len() * 4 - 3;
+        let level = self.list_indices.len();
+        let width = level * 4 - 3;
         if let Some(last_index) = self.list_indices.last_mut() {
-            let span = match last_index {
-                None => Span::from(" ".repeat(width - 1) + "- "),
+            let ordered = last_index.is_some();
+            let (label, delimiter) = match last_index {
+                None => (self.styles.list_bullet(level).to_owned(), ""),
                 Some(index) => {
                     *index += 1;
-                    format!("{:width$}. ", *index - 1).light_blue()
+                    let number = self.styles.list_numbering(level).format(*index - 1);
+                    (number, self.styles.list_delimiter(level).as_str())
                 }
             };
+            let padding = " ".repeat(width.saturating_sub(label.width()));
+            let span = Span::styled(
+                format!("{padding}{label}{delimiter} "),
+                self.styles.list_marker(ordered),
+            );
             let continuation_width = span.width();
             self.push_span(span);
             let marker_span_count = self.text.lines[marker_line].spans.len();
@@ -65,33 +83,76 @@ where
                 marker_line,
                 marker_span_count,
                 continuation_width,
+                ordered,
+                completed_style: false,
             });
         }
         self.needs_newline = false;
     }
 
+    /// Returns whether the innermost list item's marker line has no content yet, so that the
+    /// item's first block continues it.
+    pub fn list_marker_line_is_open(&self) -> bool {
+        self.list_items.last().is_some_and(|item| {
+            !self.needs_newline
+                && self.text.lines.len() == item.marker_line + 1
+                && self.text.lines[item.marker_line].spans.len() == item.marker_span_count
+        })
+    }
+
     pub fn end_item(&mut self) {
-        self.list_items.pop();
+        if self
+            .list_items
+            .pop()
+            .is_some_and(|item| item.completed_style)
+        {
+            self.pop_inline_style();
+        }
     }
 
     pub fn task_list_marker(&mut self, checked: bool) {
-        let marker = if checked { 'x' } else { ' ' };
-        let marker_span = Span::from(format!("[{marker}] "));
-        if let Some(line) = self.text.lines.last_mut() {
-            if let Some(first_span) = line.spans.first_mut() {
-                let content = first_span.content.to_mut();
-                if content.ends_with("- ") {
-                    let len = content.len();
-                    content.truncate(len - 2);
-                    content.push_str("- [");
-                    content.push(marker);
-                    content.push_str("] ");
-                    return;
-                }
+        self.tasks.total += 1;
+        if checked {
+            self.tasks.completed += 1;
+        }
+        self.write_task_checkbox(checked);
+        let completed_style = match self.completed_tasks {
+            _ if !checked => None,
+            CompletedTasks::Unchanged => None,
+            CompletedTasks::Dim => Some(Style::new().dim()),
+            CompletedTasks::CrossedOut => Some(Style::new().crossed_out()),
+        };
+        if let Some(style) = completed_style {
+            if let Some(item) = self.list_items.last_mut() {
+                item.completed_style = true;
+                self.push_inline_style(style);
             }
-            line.spans.insert(1, marker_span);
+        }
+    }
+
+    fn write_task_checkbox(&mut self, checked: bool) {
+        let marker = self.styles.task_marker(checked);
+        if marker.is_empty() {
+            return;
+        }
+        let checkbox = format!("{marker} ");
+        let style = self.styles.task_marker_style(checked);
+        let Some(item) = self.list_items.last().copied() else {
+            self.push_span(Span::styled(checkbox, style));
+            return;
+        };
+        let line = &mut self.text.lines[item.marker_line];
+        let bullet = &mut line.spans[item.marker_span_count - 1];
+        if item.ordered {
+            line.spans
+                .insert(item.marker_span_count, Span::styled(checkbox, style));
+        } else if bullet.style.patch(style) == bullet.style {
+            // An unordered marker reads as one `- [x] ` marker.
+            bullet.content.to_mut().push_str(&checkbox);
         } else {
-            self.push_span(marker_span);
+            let style = bullet.style.patch(style);
+            line.spans
+                .insert(item.marker_span_count, Span::styled(checkbox, style));
         }
     }
 }
@@ -100,11 +161,13 @@ where
 mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
+    use ratatui_core::style::Stylize;
     use rstest::rstest;
 
     use super::*;
     use crate::renderer::test_support::{with_tracing, DefaultGuard};
     use crate::renderer::*;
+    use crate::{ListDelimiter, ListNumbering};
 
     #[rstest]
     fn list_single(_with_tracing: DefaultGuard) {
@@ -309,4 +372,202 @@ mod tests {
             ])
         );
     }
+
+    #[derive(Clone, Copy)]
+    struct OutlineStyleSheet;
+
+    impl StyleSheet for OutlineStyleSheet {
+        fn list_bullet(&self, level: usize) -> &str {
+            ["•", "◦", "▪"][(level - 1) % 3]
+        }
+
+        fn list_numbering(&self, level: usize) -> ListNumbering {
+            match level {
+                1 => ListNumbering::UpperRoman,
+                _ => ListNumbering::LowerAlpha,
+            }
+        }
+
+        fn list_delimiter(&self, _level: usize) -> ListDelimiter {
+            ListDelimiter::Parenthesis
+        }
+
+        fn list_marker(&self, _ordered: bool) -> Style {
+            Style::new().magenta()
+        }
+    }
+
+    #[test]
+    fn numbering_schemes_format_numbers() {
+        let formatted = [1, 4, 27, 1994]
+            .map(|number| {
+                [
+                    ListNumbering::Decimal,
+                    ListNumbering::LowerAlpha,
+                    ListNumbering::UpperAlpha,
+                    ListNumbering::LowerRoman,
+                    ListNumbering::UpperRoman,
+                ]
+                .map(|numbering| numbering.format(number))
+                .join(" ")
+            })
+            .join(", ");
+
+        assert_eq!(
+            formatted,
+            "1 a A i I, 4 d D iv IV, 27 aa AA xxvii XXVII, 1994 bxr BXR mcmxciv MCMXCIV"
+        );
+        assert_eq!(ListNumbering::LowerRoman.format(0), "0");
+    }
+
+    #[rstest]
+    fn style_sheet_selects_bullets_per_level(_with_tracing: DefaultGuard) {
+        let options = Options::new(OutlineStyleSheet);
+        let marker = Style::new().magenta();
+
+        assert_eq!(
+            from_str_with_options("- One\n  - Two\n    - Three\n      - [x] Four", &options),
+            Text::from_iter([
+                Line::from_iter([Span::styled("• ", marker), Span::raw("One")]),
+                Line::from_iter([Span::styled("    ◦ ", marker), Span::raw("Two")]),
+                Line::from_iter([Span::styled("        ▪ ", marker), Span::raw("Three")]),
+                Line::from_iter([
+                    Span::styled("            • [x] ", marker),
+                    Span::raw("Four")
+                ]),
+            ])
+        );
+    }
+
+    #[derive(Clone, Copy)]
+    struct CheckboxStyleSheet;
+
+    impl StyleSheet for CheckboxStyleSheet {
+        fn task_marker(&self, checked: bool) -> &str {
+            if checked {
+                "☑"
+            } else {
+                "☐"
+            }
+        }
+
+        fn task_marker_style(&self, checked: bool) -> Style {
+            if checked {
+                Style::new().green()
+            } else {
+                Style::new().dim()
+            }
+        }
+    }
+
+    #[rstest]
+    fn style_sheet_selects_task_markers(_with_tracing: DefaultGuard) {
+        let options = Options::new(CheckboxStyleSheet);
+
+        assert_eq!(
+            from_str_with_options(
+                "- [ ] Open
+1. [x] Done",
+                &options
+            ),
+            Text::from_iter([
+                Line::from_iter(["- ".into(), "☐ ".dim(), "Open".into()]),
+                Line::default(),
+                Line::from_iter(["1. ".light_blue(), "☑ ".green(), "Done".into()]),
+            ])
+        );
+    }
+
+    #[derive(Clone, Copy)]
+    struct NoCheckboxStyleSheet;
+
+    impl StyleSheet for NoCheckboxStyleSheet {
+        fn task_marker(&self, _checked: bool) -> &str {
+            ""
+        }
+    }
+
+    #[rstest]
+    fn empty_task_marker_omits_checkbox(_with_tracing: DefaultGuard) {
+        let options = Options::new(NoCheckboxStyleSheet);
+
+        assert_eq!(
+            from_str_with_options("- [x] Done", &options),
+            Text::from(Line::from_iter(["- ", "Done"]))
+        );
+    }
+
+    #[rstest]
+    #[case::dim(CompletedTasks::Dim, Style::new().dim())]
+    #[case::crossed_out(CompletedTasks::CrossedOut, Style::new().crossed_out())]
+    fn completed_tasks_style_item_content(
+        _with_tracing: DefaultGuard,
+        #[case] completed_tasks: CompletedTasks,
+        #[case] style: Style,
+    ) {
+        let markdown = indoc! {"
+            - [x] Done *now*
+              - Nested
+            - [ ] Open
+        "};
+        let options = Options::default().completed_tasks(completed_tasks);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options),
+            Text::from_iter([
+                Line::from_iter([
+                    Span::raw("- [x] "),
+                    Span::styled("Done ", style),
+                    Span::styled("now", style.italic()),
+                ]),
+                Line::from_iter([Span::raw("    - "), Span::styled("Nested", style)]),
+                Line::from_iter(["- [ ] ", "Open"]),
+            ])
+        );
+    }
+
+    #[rstest]
+    fn document_counts_tasks(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            - [x] One
+            - [ ] Two
+              1. [x] Three
+            - Not a task
+        "};
+
+        let document = document_from_str(markdown, &Options::default());
+
+        assert_eq!(document.tasks().completed, 2);
+        assert_eq!(document.tasks().total, 3);
+    }
+
+    #[rstest]
+    fn style_sheet_selects_numbering_per_level(_with_tracing: DefaultGuard) {
+        let options = Options::new(OutlineStyleSheet);
+
+        assert_eq!(
+            from_str_with_options("7. Seven\n8. Eight\n   1. Nested", &options).to_string(),
+            "VII) Seven\nVIII) Eight\n    a) Nested"
+        );
+    }
+
+    #[rstest]
+    fn wide_markers_indent_continuation_lines(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            8. | A |
+               |---|
+               | b |
+        "};
+        let options = Options::new(OutlineStyleSheet);
+
+        assert_eq!(
+            from_str_with_options(markdown, &options).to_string(),
+            indoc! {"
+                VIII) ┌───┐
+                      │ A │
+                      ├───┤
+                      │ b │
+                      └───┘"}
+        );
+    }
 }
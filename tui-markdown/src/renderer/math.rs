@@ -1,13 +1,16 @@
 //! Markdown inline and display math rendering.
 //!
 //! Inline math retains `$` delimiters and its position in the surrounding line. Display math keeps
-//! `$$` delimiters and writes each source line as a physical Ratatui line.
+//! `$$` delimiters and writes each source line as a physical Ratatui line. With
+//! [`DisplayMath::Layout`], supported display math is instead drawn across rows by [`layout`].
 
 use pulldown_cmark::{CowStr, Event};
 use ratatui_core::text::{Line, Span};
 
 use super::TextWriter;
-use crate::StyleSheet;
+use crate::{DisplayMath, StyleSheet};
+
+mod layout;
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
@@ -15,7 +18,7 @@ where
     S: StyleSheet,
 {
     pub fn inline_math(&mut self, math: CowStr<'a>) {
-        let inline_style = self.inline_styles.last().copied().unwrap_or_default();
+        let inline_style = self.inline_style();
         let style = inline_style.patch(self.styles.math_inline());
         self.push_span(Span::styled(format!("${math}$"), style));
     }
@@ -25,12 +28,17 @@ where
             self.push_line(Line::default());
         }
         let style = self.styles.math_display();
-        let display_math = format!("$${math}$$");
-        for (index, line) in display_math.lines().enumerate() {
+        let rows = match self.display_math {
+            DisplayMath::Layout => layout::layout(&math),
+            DisplayMath::Source => None,
+        };
+        let rows =
+            rows.unwrap_or_else(|| format!("$${math}$$").lines().map(str::to_owned).collect());
+        for (index, line) in rows.into_iter().enumerate() {
             if index > 0 {
                 self.push_line(Line::default());
             }
-            self.push_span(Span::styled(line.to_owned(), style));
+            self.push_span(Span::styled(line, style));
         }
         self.needs_newline = true;
     }
@@ -131,5 +139,63 @@ mod tests {
                 ])
             );
         }
+
+        #[rstest]
+        fn display_math_layout_draws_rows(_with_tracing: DefaultGuard) {
+            let markdown = indoc! {r"
+                Before
+
+                $$
+                x = \frac{a+b}{2}
+                $$
+
+                After
+            "};
+            let options = Options::default().display_math(DisplayMath::Layout);
+            let style = Style::new().fg(Color::Magenta);
+
+            assert_eq!(
+                from_str_with_options(markdown, &options),
+                Text::from_iter([
+                    Line::from("Before"),
+                    Line::default(),
+                    Line::from(Span::styled("    a + b", style)),
+                    Line::from(Span::styled("x = ─────", style)),
+                    Line::from(Span::styled("      2", style)),
+                    Line::default(),
+                    Line::from("After"),
+                ])
+            );
+        }
+
+        #[rstest]
+        fn display_math_layout_falls_back_to_source(_with_tracing: DefaultGuard) {
+            let markdown = "$$\\unsupported{x}$$";
+            let options = Options::default().display_math(DisplayMath::Layout);
+
+            assert_eq!(
+                from_str_with_options(markdown, &options),
+                from_str(markdown)
+            );
+        }
+
+        #[rstest]
+        fn display_math_layout_falls_back_for_deep_nesting(_with_tracing: DefaultGuard) {
+            let markdown = format!("$${}x{}$$", "{".repeat(10_000), "}".repeat(10_000));
+            let options = Options::default().display_math(DisplayMath::Layout);
+
+            assert_eq!(
+                from_str_with_options(&markdown, &options),
+                from_str(&markdown)
+            );
+        }
+
+        #[rstest]
+        fn display_math_layout_keeps_blockquote_prefix(_with_tracing: DefaultGuard) {
+            let options = Options::default().display_math(DisplayMath::Layout);
+            let text = from_str_with_options("> $$\\sqrt{x}$$", &options);
+
+            assert_eq!(text.to_string(), ">  ─\n> √x");
+        }
     }
 }
@@ -0,0 +1,894 @@
+//! Two-dimensional layout for display math.
+//!
+//! A small TeX subset is parsed straight into [`MathBox`] values: rectangles of text rows with a
+//! baseline row that aligns neighbouring boxes. Fractions stack around a rule, roots draw an
+//! overbar, big operators carry their limits above and below, and matrices align their cells in
+//! columns inside stretched delimiters.
+//!
+//! Any construct outside the subset makes [`layout`] return `None`. The caller then renders the
+//! math source unchanged, so an unsupported command never produces misleading output.
+
+use unicode_width::UnicodeWidthStr;
+
+/// Deepest nesting of groups and command arguments that [`layout`] accepts.
+///
+/// Parsing recurses once per level, so deeper input returns `None` instead of overflowing the
+/// stack.
+const MAX_DEPTH: usize = 64;
+
+/// Lays out `source` as rows of terminal text, or returns `None` when it uses unsupported TeX.
+///
+/// Top-level `\\` separators stack equations vertically with their left edges aligned.
+pub fn layout(source: &str) -> Option<Vec<String>> {
+    let mut parser = Parser::new(source);
+    let mut equations = vec![parser.parse_row()?];
+    while parser.eat(&Token::Newline) {
+        equations.push(parser.parse_row()?);
+    }
+    if parser.peek().is_some() {
+        return None;
+    }
+
+    let rows = equations
+        .into_iter()
+        .flat_map(|equation| equation.rows)
+        .map(|row| row.trim_end().to_owned())
+        .collect();
+    Some(rows)
+}
+
+/// A rectangle of text rows, every row padded to the same display width.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct MathBox {
+    rows: Vec<String>,
+    /// Row index aligned with the baseline of horizontally adjacent boxes.
+    baseline: usize,
+    width: usize,
+}
+
+impl MathBox {
+    fn text(text: &str) -> Self {
+        Self {
+            rows: vec![text.to_owned()],
+            baseline: 0,
+            width: text.width(),
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn is_single_row(&self) -> bool {
+        self.rows.len() == 1
+    }
+
+    /// Places `boxes` side by side, aligning their baselines.
+    fn row(boxes: Vec<Self>) -> Self {
+        let above = boxes.iter().map(|b| b.baseline).max().unwrap_or(0);
+        let below = boxes
+            .iter()
+            .map(|b| b.height() - b.baseline - 1)
+            .max()
+            .unwrap_or(0);
+        let width = boxes.iter().map(|b| b.width).sum();
+        let mut rows = vec![String::new(); above + below + 1];
+        for b in &boxes {
+            let offset = above - b.baseline;
+            for (index, row) in rows.iter_mut().enumerate() {
+                match index.checked_sub(offset).and_then(|i| b.rows.get(i)) {
+                    Some(content) => row.push_str(content),
+                    None => row.push_str(&" ".repeat(b.width)),
+                }
+            }
+        }
+        Self {
+            rows,
+            baseline: above,
+            width,
+        }
+    }
+
+    /// Stacks `boxes` vertically, centering each within the widest box.
+    fn column(boxes: Vec<Self>, baseline: usize) -> Self {
+        let width = boxes.iter().map(|b| b.width).max().unwrap_or(0);
+        let rows = boxes
+            .into_iter()
+            .flat_map(|b| b.centered(width).rows)
+            .collect();
+        Self {
+            rows,
+            baseline,
+            width,
+        }
+    }
+
+    fn centered(self, width: usize) -> Self {
+        let total = width - self.width;
+        self.padded(total / 2, total - total / 2)
+    }
+
+    fn padded(self, left: usize, right: usize) -> Self {
+        let (left, right) = (" ".repeat(left), " ".repeat(right));
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|row| format!("{left}{row}{right}"))
+            .collect();
+        Self {
+            rows,
+            baseline: self.baseline,
+            width: self.width + left.len() + right.len(),
+        }
+    }
+
+    fn fraction(numerator: Self, denominator: Self) -> Self {
+        let width = numerator.width.max(denominator.width);
+        let baseline = numerator.height();
+        let rule = Self::text(&"─".repeat(width));
+        Self::column(vec![numerator, rule, denominator], baseline)
+    }
+
+    fn sqrt(radicand: Self) -> Self {
+        let last = radicand.height() - 1;
+        let mut rows = vec![format!(" {}", "─".repeat(radicand.width))];
+        for (index, row) in radicand.rows.into_iter().enumerate() {
+            let sign = if index == last { '√' } else { '│' };
+            rows.push(format!("{sign}{row}"));
+        }
+        Self {
+            rows,
+            baseline: radicand.baseline + 1,
+            width: radicand.width + 1,
+        }
+    }
+
+    /// Places limits centered above and below an operator symbol.
+    fn limits(operator: Self, lower: Option<Self>, upper: Option<Self>) -> Self {
+        let upper = upper.unwrap_or_default();
+        let baseline = upper.height() + operator.baseline;
+        let boxes = [Some(upper), Some(operator), lower];
+        Self::column(boxes.into_iter().flatten().collect(), baseline)
+    }
+
+    /// Attaches scripts to the right of `base`, raised above and lowered below it.
+    fn scripts(base: Self, sub: Option<Self>, sup: Option<Self>) -> Self {
+        if base.is_single_row() {
+            let sub_text = sub.as_ref().map_or(Some(String::new()), unicode_subscript);
+            let sup_text = sup
+                .as_ref()
+                .map_or(Some(String::new()), unicode_superscript);
+            if let (Some(sub_text), Some(sup_text)) = (sub_text, sup_text) {
+                return Self::text(&format!("{}{sub_text}{sup_text}", base.rows[0]));
+            }
+        }
+
+        let sup = sup.unwrap_or_default();
+        let sub = sub.unwrap_or_default();
+        let script_width = sup.width.max(sub.width);
+        let mut rows = Vec::new();
+        for row in &sup.rows {
+            let padding = script_width - sup.width;
+            rows.push(format!(
+                "{}{row}{}",
+                " ".repeat(base.width),
+                " ".repeat(padding)
+            ));
+        }
+        for row in &base.rows {
+            rows.push(format!("{row}{}", " ".repeat(script_width)));
+        }
+        for row in &sub.rows {
+            let padding = script_width - sub.width;
+            rows.push(format!(
+                "{}{row}{}",
+                " ".repeat(base.width),
+                " ".repeat(padding)
+            ));
+        }
+        Self {
+            rows,
+            baseline: sup.height() + base.baseline,
+            width: base.width + script_width,
+        }
+    }
+
+    /// Surrounds `self` with delimiters stretched to its height.
+    fn delimited(self, left: Delimiter, right: Delimiter) -> Self {
+        let height = self.height();
+        let baseline = self.baseline;
+        let left = left.stretch(height, baseline, true);
+        let right = right.stretch(height, baseline, false);
+        Self::row([left, Some(self), right].into_iter().flatten().collect())
+    }
+
+    /// Aligns matrix cells in columns separated by two spaces.
+    fn matrix(cells: Vec<Vec<Self>>) -> Self {
+        let column_count = cells.iter().map(Vec::len).max().unwrap_or(0);
+        let mut widths = vec![0; column_count];
+        for row in &cells {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(cell.width);
+            }
+        }
+
+        let rows: Vec<Self> = cells
+            .into_iter()
+            .map(|row| {
+                let mut boxes = Vec::new();
+                let mut row = row.into_iter();
+                for (column, &width) in widths.iter().enumerate() {
+                    if column > 0 {
+                        boxes.push(Self::text("  "));
+                    }
+                    let cell = row.next().unwrap_or_else(|| Self::text(""));
+                    boxes.push(cell.centered(width));
+                }
+                Self::row(boxes)
+            })
+            .collect();
+        let height: usize = rows.iter().map(Self::height).sum();
+        Self::column(rows, height.saturating_sub(1) / 2)
+    }
+}
+
+/// A bracket that grows to the height of the content it surrounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Delimiter {
+    None,
+    Paren,
+    Bracket,
+    Brace,
+    Bar,
+    DoubleBar,
+}
+
+impl Delimiter {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::None),
+            '(' | ')' => Some(Self::Paren),
+            '[' | ']' => Some(Self::Bracket),
+            '{' | '}' => Some(Self::Brace),
+            '|' => Some(Self::Bar),
+            '‖' => Some(Self::DoubleBar),
+            _ => None,
+        }
+    }
+
+    /// Returns the glyphs for a one-row delimiter, then the top, middle, extension, and bottom
+    /// glyphs for a taller one.
+    fn glyphs(self, left: bool) -> (&'static str, [&'static str; 4]) {
+        match (self, left) {
+            (Self::None, _) => ("", ["", "", "", ""]),
+            (Self::Paren, true) => ("(", ["⎛", "⎜", "⎜", "⎝"]),
+            (Self::Paren, false) => (")", ["⎞", "⎟", "⎟", "⎠"]),
+            (Self::Bracket, true) => ("[", ["⎡", "⎢", "⎢", "⎣"]),
+            (Self::Bracket, false) => ("]", ["⎤", "⎥", "⎥", "⎦"]),
+            (Self::Brace, true) => ("{", ["⎧", "⎨", "⎪", "⎩"]),
+            (Self::Brace, false) => ("}", ["⎫", "⎬", "⎪", "⎭"]),
+            (Self::Bar, _) => ("|", ["│", "│", "│", "│"]),
+            (Self::DoubleBar, _) => ("‖", ["‖", "‖", "‖", "‖"]),
+        }
+    }
+
+    fn stretch(self, height: usize, baseline: usize, left: bool) -> Option<MathBox> {
+        if self == Self::None {
+            return None;
+        }
+        let (single, [top, middle, extension, bottom]) = self.glyphs(left);
+        let rows = match height {
+            1 => vec![single.to_owned()],
+            _ => (0..height)
+                .map(|index| match index {
+                    0 => top,
+                    index if index == height - 1 => bottom,
+                    index if index == (height - 1) / 2 => middle,
+                    _ => extension,
+                })
+                .map(str::to_owned)
+                .collect(),
+        };
+        Some(MathBox {
+            rows,
+            baseline,
+            width: 1,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Command(String),
+    Char(char),
+    Open,
+    Close,
+    Superscript,
+    Subscript,
+    Ampersand,
+    Newline,
+    Space,
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '\\' => match chars.next() {
+                Some('\\') => Token::Newline,
+                Some(c) if c.is_ascii_alphabetic() => {
+                    let mut name = String::from(c);
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                        name.push(c);
+                        chars.next();
+                    }
+                    Token::Command(name)
+                }
+                Some(c) => Token::Command(c.to_string()),
+                None => Token::Char('\\'),
+            },
+            '{' => Token::Open,
+            '}' => Token::Close,
+            '^' => Token::Superscript,
+            '_' => Token::Subscript,
+            '&' => Token::Ampersand,
+            c if c.is_whitespace() => Token::Space,
+            c => Token::Char(c),
+        };
+        if token != Token::Space || tokens.last() != Some(&Token::Space) {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Number of atoms being parsed that enclose the current position.
+    depth: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Self {
+        Self {
+            tokens: tokenize(source),
+            position: 0,
+            depth: 0,
+        }
+    }
+
+    /// Returns the next significant token. Spaces only matter inside `\text{…}`.
+    fn peek(&mut self) -> Option<&Token> {
+        if self.tokens.get(self.position) == Some(&Token::Space) {
+            self.position += 1;
+        }
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        self.peek();
+        self.next_raw()
+    }
+
+    fn next_raw(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Parses atoms until a group end, cell separator, row separator, or environment end.
+    fn parse_row(&mut self) -> Option<MathBox> {
+        let mut boxes = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some(Token::Close | Token::Ampersand | Token::Newline) => break,
+                Some(Token::Command(name)) if name == "end" || name == "right" => break,
+                _ => {}
+            }
+            let atom = self.parse_atom(boxes.is_empty())?;
+            boxes.push(self.parse_scripts(atom)?);
+        }
+        Some(MathBox::row(boxes))
+    }
+
+    fn parse_scripts(&mut self, base: Atom) -> Option<MathBox> {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            let script = match self.peek() {
+                Some(Token::Subscript) => &mut sub,
+                Some(Token::Superscript) => &mut sup,
+                _ => break,
+            };
+            // A repeated script such as `x^a^b` is a TeX error too.
+            if script.is_some() {
+                return None;
+            }
+            self.next();
+            *script = Some(self.parse_argument()?);
+        }
+        match base {
+            Atom::Ordinary(base) if sub.is_none() && sup.is_none() => Some(base),
+            Atom::Ordinary(base) => Some(MathBox::scripts(base, sub, sup)),
+            Atom::Limits(operator) => Some(MathBox::limits(operator, sub, sup)),
+        }
+    }
+
+    /// Parses a script or command argument: a braced group or a single token.
+    fn parse_argument(&mut self) -> Option<MathBox> {
+        if self.eat(&Token::Open) {
+            let group = self.parse_row()?;
+            return self.eat(&Token::Close).then_some(group);
+        }
+        match self.parse_atom(true)? {
+            Atom::Ordinary(atom) => Some(atom),
+            Atom::Limits(_) => None,
+        }
+    }
+
+    /// Parses a braced argument as literal text, as used by `\text{…}`.
+    fn parse_text_argument(&mut self) -> Option<String> {
+        if !self.eat(&Token::Open) {
+            return None;
+        }
+        let mut text = String::new();
+        loop {
+            match self.next_raw()? {
+                Token::Close => return Some(text),
+                Token::Char(c) => text.push(c),
+                Token::Space => text.push(' '),
+                _ => return None,
+            }
+        }
+    }
+
+    /// Parses an atom, failing when it would nest deeper than [`MAX_DEPTH`].
+    fn parse_atom(&mut self, starts_row: bool) -> Option<Atom> {
+        if self.depth == MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let atom = self.parse_nested_atom(starts_row);
+        self.depth -= 1;
+        atom
+    }
+
+    fn parse_nested_atom(&mut self, starts_row: bool) -> Option<Atom> {
+        let atom = match self.next()? {
+            Token::Open => {
+                let group = self.parse_row()?;
+                self.eat(&Token::Close).then_some(group)?
+            }
+            Token::Char(c) => char_box(c, starts_row),
+            Token::Command(name) => return self.parse_command(&name, starts_row),
+            _ => return None,
+        };
+        Some(Atom::Ordinary(atom))
+    }
+
+    fn parse_command(&mut self, name: &str, starts_row: bool) -> Option<Atom> {
+        let atom = match name {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                MathBox::fraction(numerator, denominator)
+            }
+            "sqrt" => MathBox::sqrt(self.parse_argument()?),
+            "text" | "mathrm" | "textrm" | "operatorname" => {
+                MathBox::text(&self.parse_text_argument()?)
+            }
+            "mathbf" | "mathit" | "mathsf" | "mathtt" | "boldsymbol" => self.parse_argument()?,
+            "left" => {
+                let left = self.parse_delimiter()?;
+                let body = self.parse_row()?;
+                if !self.eat(&Token::Command("right".to_owned())) {
+                    return None;
+                }
+                let right = self.parse_delimiter()?;
+                body.delimited(left, right)
+            }
+            "begin" => self.parse_environment()?,
+            _ => {
+                if let Some(operator) = large_operator(name) {
+                    return Some(Atom::Limits(MathBox::text(operator)));
+                }
+                if let Some(symbol) = symbol(name) {
+                    symbol_box(symbol, starts_row)
+                } else {
+                    return None;
+                }
+            }
+        };
+        Some(Atom::Ordinary(atom))
+    }
+
+    fn parse_delimiter(&mut self) -> Option<Delimiter> {
+        match self.next()? {
+            Token::Char(c) => Delimiter::from_char(c),
+            Token::Command(name) => match name.as_str() {
+                "{" | "}" | "lbrace" | "rbrace" => Some(Delimiter::Brace),
+                "|" | "Vert" => Some(Delimiter::DoubleBar),
+                "vert" => Some(Delimiter::Bar),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn parse_environment(&mut self) -> Option<MathBox> {
+        let name = self.parse_text_argument()?;
+        let (left, right) = match name.as_str() {
+            "matrix" => (Delimiter::None, Delimiter::None),
+            "pmatrix" => (Delimiter::Paren, Delimiter::Paren),
+            "bmatrix" => (Delimiter::Bracket, Delimiter::Bracket),
+            "Bmatrix" => (Delimiter::Brace, Delimiter::Brace),
+            "vmatrix" => (Delimiter::Bar, Delimiter::Bar),
+            "Vmatrix" => (Delimiter::DoubleBar, Delimiter::DoubleBar),
+            _ => return None,
+        };
+
+        let mut cells = vec![vec![]];
+        loop {
+            let cell = self.parse_row()?;
+            let row = cells.last_mut()?;
+            row.push(cell);
+            match self.next()? {
+                Token::Ampersand => {}
+                Token::Newline => cells.push(vec![]),
+                Token::Command(command) if command == "end" => break,
+                _ => return None,
+            }
+        }
+        if self.parse_text_argument()? != name {
+            return None;
+        }
+        // A trailing `\\` before `\end` leaves one empty row behind.
+        if cells.len() > 1 && cells.last().is_some_and(|row| row == &[MathBox::text("")]) {
+            cells.pop();
+        }
+        Some(MathBox::matrix(cells).delimited(left, right))
+    }
+}
+
+/// A parsed atom before its scripts are attached.
+enum Atom {
+    /// An atom whose scripts attach to its right.
+    Ordinary(MathBox),
+    /// A large operator whose scripts become limits above and below.
+    Limits(MathBox),
+}
+
+/// Binary operators and relations that read better with surrounding spaces.
+fn is_spaced_operator(symbol: &str) -> bool {
+    matches!(
+        symbol,
+        "=" | "+"
+            | "-"
+            | "<"
+            | ">"
+            | "±"
+            | "∓"
+            | "×"
+            | "÷"
+            | "·"
+            | "≤"
+            | "≥"
+            | "≠"
+            | "≈"
+            | "≡"
+            | "∼"
+            | "→"
+            | "←"
+            | "⇒"
+            | "⇔"
+            | "∈"
+            | "∉"
+            | "⊂"
+            | "⊆"
+            | "∪"
+            | "∩"
+    )
+}
+
+fn char_box(c: char, starts_row: bool) -> MathBox {
+    let symbol = match c {
+        '-' => "-".to_owned(),
+        '*' => "·".to_owned(),
+        c => c.to_string(),
+    };
+    symbol_box(&symbol, starts_row)
+}
+
+fn symbol_box(symbol: &str, starts_row: bool) -> MathBox {
+    // A leading operator is unary (as in `-x`), so it keeps its operand close.
+    if is_spaced_operator(symbol) && !starts_row {
+        MathBox::text(&format!(" {symbol} "))
+    } else {
+        MathBox::text(symbol)
+    }
+}
+
+fn large_operator(name: &str) -> Option<&'static str> {
+    let operator = match name {
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "oint" => "∮",
+        "bigcup" => "⋃",
+        "bigcap" => "⋂",
+        "lim" => "lim",
+        "max" => "max",
+        "min" => "min",
+        "sup" => "sup",
+        "inf" => "inf",
+        _ => return None,
+    };
+    Some(operator)
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    let symbol = match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" | "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" | "vartheta" => "θ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" | "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "cdot" => "·",
+        "times" => "×",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "le" | "leq" => "≤",
+        "ge" | "geq" => "≥",
+        "ne" | "neq" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftrightarrow" | "iff" => "⇔",
+        "in" => "∈",
+        "notin" => "∉",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "cup" => "∪",
+        "cap" => "∩",
+        "forall" => "∀",
+        "exists" => "∃",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "prime" => "′",
+        "sin" => "sin",
+        "cos" => "cos",
+        "tan" => "tan",
+        "log" => "log",
+        "ln" => "ln",
+        "exp" => "exp",
+        "{" => "{",
+        "}" => "}",
+        "|" => "‖",
+        "%" => "%",
+        "," | ":" | ";" => " ",
+        "!" => "",
+        "quad" => "  ",
+        "qquad" => "    ",
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+fn unicode_superscript(script: &MathBox) -> Option<String> {
+    if !script.is_single_row() {
+        return None;
+    }
+    script.rows[0]
+        .chars()
+        .map(|c| match c {
+            '0' => Some('⁰'),
+            '1' => Some('¹'),
+            '2' => Some('²'),
+            '3' => Some('³'),
+            '4' => Some('⁴'),
+            '5' => Some('⁵'),
+            '6' => Some('⁶'),
+            '7' => Some('⁷'),
+            '8' => Some('⁸'),
+            '9' => Some('⁹'),
+            '+' => Some('⁺'),
+            '-' => Some('⁻'),
+            'n' => Some('ⁿ'),
+            'i' => Some('ⁱ'),
+            '′' => Some('′'),
+            _ => None,
+        })
+        .collect()
+}
+
+fn unicode_subscript(script: &MathBox) -> Option<String> {
+    if !script.is_single_row() {
+        return None;
+    }
+    script.rows[0]
+        .chars()
+        .map(|c| match c {
+            '0' => Some('₀'),
+            '1' => Some('₁'),
+            '2' => Some('₂'),
+            '3' => Some('₃'),
+            '4' => Some('₄'),
+            '5' => Some('₅'),
+            '6' => Some('₆'),
+            '7' => Some('₇'),
+            '8' => Some('₈'),
+            '9' => Some('₉'),
+            '+' => Some('₊'),
+            '-' => Some('₋'),
+            'i' => Some('ᵢ'),
+            'j' => Some('ⱼ'),
+            'k' => Some('ₖ'),
+            'n' => Some('ₙ'),
+            'x' => Some('ₓ'),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[track_caller]
+    fn assert_layout(source: &str, expected: &[&str]) {
+        assert_eq!(
+            layout(source),
+            Some(expected.iter().map(|row| row.to_string()).collect())
+        );
+    }
+
+    #[test]
+    fn spaces_binary_operators() {
+        assert_layout("a+b=c", &["a + b = c"]);
+        assert_layout("-x", &["-x"]);
+    }
+
+    #[test]
+    fn fraction_stacks_around_rule() {
+        assert_layout(r"x = \frac{a+b}{2}", &["    a + b", "x = ─────", "      2"]);
+    }
+
+    #[test]
+    fn nested_fraction() {
+        assert_layout(
+            r"\frac{1}{1+\frac{1}{x}}",
+            &["  1", "─────", "    1", "1 + ─", "    x"],
+        );
+    }
+
+    #[test]
+    fn sqrt_draws_overbar() {
+        assert_layout(r"\sqrt{x+1}", &[" ─────", "√x + 1"]);
+    }
+
+    #[test]
+    fn simple_scripts_use_unicode() {
+        assert_layout("x^2 + a_i", &["x² + aᵢ"]);
+    }
+
+    #[test]
+    fn complex_scripts_are_raised_and_lowered() {
+        assert_layout("e^{x y}", &[" xy", "e"]);
+    }
+
+    #[test]
+    fn sum_places_limits_above_and_below() {
+        assert_layout(r"\sum_{i=1}^{n} i", &["  n", "  ∑  i", "i = 1"]);
+    }
+
+    #[test]
+    fn pmatrix_stretches_parentheses() {
+        assert_layout(
+            r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}",
+            &["⎛a  b⎞", "⎝c  d⎠"],
+        );
+    }
+
+    #[test]
+    fn bmatrix_with_three_rows() {
+        assert_layout(
+            r"\begin{bmatrix} 1 & 0 \\ 0 & 1 \\ 0 & 0 \end{bmatrix}",
+            &["⎡1  0⎤", "⎢0  1⎥", "⎣0  0⎦"],
+        );
+    }
+
+    #[test]
+    fn left_right_delimiters_grow_with_content() {
+        assert_layout(r"\left(\frac{a}{b}\right)", &["⎛a⎞", "⎜─⎟", "⎝b⎠"]);
+    }
+
+    #[test]
+    fn greek_letters_and_text() {
+        assert_layout(r"\alpha \le \text{max}", &["α ≤ max"]);
+        assert_layout(r"x \text{ if } y", &["x if y"]);
+    }
+
+    #[test]
+    fn top_level_newlines_stack_equations() {
+        assert_layout(r"x = 1 \\ y = 2", &["x = 1", "y = 2"]);
+    }
+
+    #[test]
+    fn unsupported_constructs_return_none() {
+        assert_eq!(layout(r"\unknown{x}"), None);
+        assert_eq!(layout(r"\frac{a}"), None);
+        assert_eq!(layout(r"\begin{align} a &= b \end{align}"), None);
+        assert_eq!(layout("a & b"), None);
+        assert_eq!(layout("x^a^b"), None);
+    }
+
+    #[test]
+    fn deep_nesting_returns_none() {
+        let depth = 10_000;
+        let groups = format!("{}x{}", "{".repeat(depth), "}".repeat(depth));
+        let fractions = format!("{}x{}", r"\frac{".repeat(depth), "}{y}".repeat(depth));
+        let roots = format!("{}x", r"\sqrt".repeat(depth));
+
+        assert_eq!(layout(&groups), None);
+        assert_eq!(layout(&fractions), None);
+        assert_eq!(layout(&roots), None);
+        assert_layout(
+            &format!(
+                "{}x{}",
+                "{".repeat(MAX_DEPTH - 1),
+                "}".repeat(MAX_DEPTH - 1)
+            ),
+            &["x"],
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Mermaid diagrams drawn as text.
+//!
+//! With [`MermaidDiagrams::Layout`], the source of a ` ```mermaid ` block is buffered instead of
+//! being written as code. When the block ends, simple `graph` or `flowchart` definitions are laid
+//! out by [`flowchart`], and `sequenceDiagram` definitions by [`sequence`], both with box-drawing
+//! characters. A diagram that uses syntax outside this subset, or that is wider than the available
+//! width, is rendered as an ordinary code block of its source instead.
+
+use pulldown_cmark::{CowStr, Event};
+use ratatui_core::text::{Line, Span};
+
+use super::TextWriter;
+use crate::{CodeBlockInfo, MermaidDiagrams, StyleSheet};
+
+mod flowchart;
+mod grid;
+mod sequence;
+
+/// The source of a Mermaid block, laid out when the block ends.
+pub struct MermaidBlock {
+    info: CodeBlockInfo,
+    source: String,
+}
+
+impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
+where
+    I: Iterator<Item = Event<'a>>,
+    S: StyleSheet,
+{
+    /// Starts buffering a code block as a diagram when its language is `mermaid`.
+    ///
+    /// Returns whether the block is buffered.
+    pub fn start_mermaid_block(&mut self, info: &CodeBlockInfo) -> bool {
+        if self.mermaid_diagrams != MermaidDiagrams::Layout
+            || !info.language().eq_ignore_ascii_case("mermaid")
+        {
+            return false;
+        }
+        self.mermaid_block = Some(MermaidBlock {
+            info: info.clone(),
+            source: String::new(),
+        });
+        true
+    }
+
+    /// Adds source text to the buffered block, returning whether a block is being buffered.
+    pub fn push_mermaid_text(&mut self, text: &str) -> bool {
+        let Some(block) = &mut self.mermaid_block else {
+            return false;
+        };
+        block.source.push_str(text);
+        true
+    }
+
+    /// Renders the buffered block as a diagram, returning whether the diagram was drawn.
+    ///
+    /// When the diagram cannot be laid out, its source is written to a new code block instead,
+    /// which the caller then ends as usual.
+    pub fn end_mermaid_block(&mut self) -> bool {
+        let Some(block) = self.mermaid_block.take() else {
+            return false;
+        };
+        let prefix_width = self.line_prefixes.iter().map(Span::width).sum::<usize>()
+            + usize::from(!self.line_prefixes.is_empty());
+        let max_width = self
+            .width
+            .map(|width| usize::from(width).saturating_sub(prefix_width));
+        let Some(rows) = layout(&block.source, max_width) else {
+            self.open_code_block(block.info);
+            self.text(CowStr::from(block.source));
+            return false;
+        };
+
+        if !self.text.lines.is_empty() {
+            self.push_line(Line::default());
+        }
+        let style = self.styles.mermaid_diagram();
+        for row in rows {
+            self.push_line(Span::styled(row, style).into());
+        }
+        self.needs_newline = true;
+        true
+    }
+}
+
+/// Lays out a Mermaid diagram as rows of text no wider than `max_width`.
+///
+/// Blank lines and `%%` comments are ignored. Returns `None` for diagram types other than
+/// flowcharts and sequence diagrams, for unsupported syntax, and for diagrams that are too wide.
+fn layout(source: &str, max_width: Option<usize>) -> Option<Vec<String>> {
+    let mut lines = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("%%"));
+    let header = lines.next()?;
+    let lines: Vec<_> = lines.collect();
+    let grid = match header.split_whitespace().next()? {
+        "graph" | "flowchart" => flowchart::layout(header, &lines)?,
+        "sequenceDiagram" if header == "sequenceDiagram" => sequence::layout(&lines)?,
+        _ => return None,
+    };
+    if max_width.is_some_and(|max_width| grid.width() > max_width) {
+        return None;
+    }
+    Some(grid.into_rows())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::renderer::test_support::{with_tracing, DefaultGuard};
+    use crate::renderer::*;
+    use crate::Options;
+
+    #[rstest]
+    fn flowchart_renders_as_diagram(_with_tracing: DefaultGuard) {
+        let markdown = indoc! {"
+            Flow:
+
+            ```mermaid
+            %% A comment
+            graph TD
+              A[Start] --> B[End]
+            ```
+        "};
+        let options = Options::default().mermaid_diagrams(MermaidDiagrams::Layout);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(
+            text.to_string(),
+            indoc! {"
+                Flow:
+
+                ┌───────┐
+                │ Start │
+                └───┬───┘
+                    │
+                    │
+                    │
+                    ▼
+                 ┌─────┐
+                 │ End │
+                 └─────┘"}
+        );
+    }
+
+    #[rstest]
+    fn diagram_uses_style_sheet(_with_tracing: DefaultGuard) {
+        #[derive(Clone, Copy)]
+        struct DiagramStyle;
+
+        impl StyleSheet for DiagramStyle {
+            fn mermaid_diagram(&self) -> Style {
+                Style::new().cyan()
+            }
+        }
+
+        let options = Options::new(DiagramStyle).mermaid_diagrams(MermaidDiagrams::Layout);
+        let text = from_str_with_options("```mermaid\nsequenceDiagram\nA->>B: hi\n```", &options);
+
+        assert_eq!(text.lines[0].to_string(), "┌───┐  ┌───┐");
+        assert_eq!(text.lines[0].style, Style::default());
+        assert_eq!(text.lines[0].spans[0].style, Style::new().cyan());
+    }
+
+    #[rstest]
+    fn unsupported_syntax_falls_back_to_source(_with_tracing: DefaultGuard) {
+        let markdown = "```mermaid\npie\n\"a\" : 1\n```";
+        let options = Options::default().mermaid_diagrams(MermaidDiagrams::Layout);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(text.to_string(), "```mermaid\npie\n\"a\" : 1\n```");
+    }
+
+    #[rstest]
+    fn wide_diagram_falls_back_to_source(_with_tracing: DefaultGuard) {
+        let markdown = "```mermaid\ngraph LR\nA[Start] --> B[End]\n```";
+        let options = Options::default()
+            .mermaid_diagrams(MermaidDiagrams::Layout)
+            .width(12);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(
+            text.to_string(),
+            "```mermaid\ngraph LR\nA[Start] --> B[End]\n```"
+        );
+    }
+
+    #[rstest]
+    fn mermaid_blocks_are_code_by_default(_with_tracing: DefaultGuard) {
+        let text = from_str("```mermaid\ngraph TD\nA --> B\n```");
+
+        assert_eq!(text.to_string(), "```mermaid\ngraph TD\nA --> B\n```");
+    }
+}
@@ -0,0 +1,643 @@
+//! Layered layout for Mermaid flowcharts.
+//!
+//! Nodes are assigned to layers by their longest path from a root, so every edge leads from one
+//! layer to a later one. Edges that skip layers pass through placeholder vertices in the layers
+//! between, and each layer is ordered by the positions of its predecessors to reduce crossings.
+//! Layers run down, up, right, or left according to the declared direction.
+//!
+//! Positions are computed along two axes: the main axis, across layers, and the cross axis, within
+//! a layer. They are mapped to grid columns and rows only when drawing.
+
+use unicode_width::UnicodeWidthStr;
+
+use super::grid::Grid;
+
+/// Rows or columns between layers, before room for edge labels.
+const LAYER_GAP: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Down,
+    Up,
+    Right,
+    Left,
+}
+
+impl Direction {
+    fn parse(direction: &str) -> Option<Self> {
+        match direction {
+            "TD" | "TB" => Some(Self::Down),
+            "BT" => Some(Self::Up),
+            "LR" => Some(Self::Right),
+            "RL" => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    const fn is_vertical(self) -> bool {
+        matches!(self, Self::Down | Self::Up)
+    }
+
+    const fn arrow(self) -> char {
+        match self {
+            Self::Down => '▼',
+            Self::Up => '▲',
+            Self::Right => '▶',
+            Self::Left => '◀',
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Shape {
+    Rectangle,
+    Rounded,
+    Decision,
+}
+
+#[derive(Debug)]
+struct Node {
+    id: String,
+    label: String,
+    shape: Shape,
+}
+
+#[derive(Debug, Default)]
+struct Link {
+    label: String,
+    arrow: bool,
+    dashed: bool,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    link: Link,
+}
+
+#[derive(Debug, Default)]
+struct Flowchart {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+/// A node, or a placeholder for an edge passing through a layer.
+#[derive(Debug)]
+struct Vertex {
+    node: Option<usize>,
+    layer: usize,
+    /// Size along the main axis.
+    main: usize,
+    /// Size along the cross axis.
+    cross: usize,
+}
+
+/// The part of an edge between two adjacent layers.
+#[derive(Debug)]
+struct Segment {
+    from: usize,
+    to: usize,
+    edge: usize,
+}
+
+/// Lays out a flowchart from its header line, such as `graph LR`, and the lines after it.
+///
+/// Returns `None` for unsupported syntax, including subgraphs, `&` chains, special shapes, and
+/// cycles.
+pub fn layout(header: &str, lines: &[&str]) -> Option<Grid> {
+    let mut statements = header.split(';');
+    let mut words = statements.next()?.split_whitespace();
+    if !matches!(words.next(), Some("graph" | "flowchart")) {
+        return None;
+    }
+    let direction = words
+        .next()
+        .map_or(Some(Direction::Down), Direction::parse)?;
+    if words.next().is_some() {
+        return None;
+    }
+
+    let mut chart = Flowchart::default();
+    let statements = statements.chain(lines.iter().flat_map(|line| line.split(';')));
+    for statement in statements.map(str::trim).filter(|s| !s.is_empty()) {
+        match statement.split_whitespace().next() {
+            Some("classDef" | "class" | "style" | "linkStyle" | "click") => {}
+            Some("subgraph" | "end" | "direction") => return None,
+            _ => chart.parse_chain(statement)?,
+        }
+    }
+    if chart.nodes.is_empty() {
+        return None;
+    }
+    chart.draw(direction)
+}
+
+impl Flowchart {
+    /// Parses a node, or a chain of nodes joined by links, such as `A --> B -.-> C`.
+    fn parse_chain(&mut self, statement: &str) -> Option<()> {
+        let mut rest = statement;
+        let mut from = self.parse_node(&mut rest)?;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return Some(());
+            }
+            let link = parse_link(&mut rest)?;
+            rest = rest.trim_start();
+            let to = self.parse_node(&mut rest)?;
+            self.edges.push(Edge { from, to, link });
+            from = to;
+        }
+    }
+
+    /// Parses a node ID and optional shaped label, returning the node's index.
+    fn parse_node(&mut self, rest: &mut &str) -> Option<usize> {
+        let id_end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let (id, after_id) = rest.split_at(id_end);
+        if id.is_empty() {
+            return None;
+        }
+        *rest = after_id;
+        let shaped = parse_shape(rest)?;
+
+        let index = match self.nodes.iter().position(|node| node.id == id) {
+            Some(index) => index,
+            None => {
+                self.nodes.push(Node {
+                    id: id.to_owned(),
+                    label: id.to_owned(),
+                    shape: Shape::Rectangle,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        if let Some((label, shape)) = shaped {
+            let node = &mut self.nodes[index];
+            node.label = label;
+            node.shape = shape;
+        }
+        Some(index)
+    }
+
+    fn draw(&self, direction: Direction) -> Option<Grid> {
+        let layers = self.layers()?;
+        let vertical = direction.is_vertical();
+        let mut vertices: Vec<Vertex> = self
+            .nodes
+            .iter()
+            .zip(&layers)
+            .enumerate()
+            .map(|(index, (node, &layer))| {
+                let width = node.label.width() + 4;
+                let (main, cross) = if vertical { (3, width) } else { (width, 3) };
+                Vertex {
+                    node: Some(index),
+                    layer,
+                    main,
+                    cross,
+                }
+            })
+            .collect();
+        let mut segments = vec![];
+        for (index, edge) in self.edges.iter().enumerate() {
+            let mut from = edge.from;
+            for layer in layers[edge.from] + 1..layers[edge.to] {
+                vertices.push(Vertex {
+                    node: None,
+                    layer,
+                    main: 1,
+                    cross: 1,
+                });
+                let placeholder = vertices.len() - 1;
+                segments.push(Segment {
+                    from,
+                    to: placeholder,
+                    edge: index,
+                });
+                from = placeholder;
+            }
+            segments.push(Segment {
+                from,
+                to: edge.to,
+                edge: index,
+            });
+        }
+
+        let order = order_layers(&vertices, &segments);
+        let layer_count = order.len();
+
+        // Cross-axis positions, centering each layer on the widest.
+        let cross_gap = if vertical { 2 } else { 1 };
+        let layer_cross = |layer: &[usize]| {
+            layer
+                .iter()
+                .map(|&v| vertices[v].cross + cross_gap)
+                .sum::<usize>()
+                - cross_gap
+        };
+        let widest = order.iter().map(|layer| layer_cross(layer)).max()?;
+        let mut cross_start = vec![0; vertices.len()];
+        for layer in &order {
+            let mut cursor = (widest - layer_cross(layer)) / 2;
+            for &v in layer {
+                cross_start[v] = cursor;
+                cursor += vertices[v].cross + cross_gap;
+            }
+        }
+        let center = |v: usize| cross_start[v] + vertices[v].cross / 2;
+
+        // Main-axis positions, leaving room for labels on horizontal edges.
+        let mut layer_start = vec![0; layer_count];
+        let mut layer_end = vec![0; layer_count];
+        for (index, layer) in order.iter().enumerate() {
+            let extent = layer.iter().map(|&v| vertices[v].main).max()?;
+            layer_end[index] = layer_start[index] + extent - 1;
+            if index + 1 < layer_count {
+                let label_room = if vertical {
+                    0
+                } else {
+                    segments
+                        .iter()
+                        .filter(|segment| vertices[segment.to].layer == index + 1)
+                        .map(|segment| self.edges[segment.edge].link.label.width())
+                        .max()
+                        .unwrap_or_default()
+                };
+                layer_start[index + 1] = layer_end[index] + 1 + LAYER_GAP + label_room;
+            }
+        }
+        let total_main = layer_end[layer_count - 1] + 1;
+        let frame = Frame {
+            direction,
+            total_main,
+        };
+
+        let mut grid = Grid::default();
+        for (v, vertex) in vertices.iter().enumerate() {
+            let start = layer_start[vertex.layer];
+            match vertex.node {
+                Some(node) => {
+                    frame.node(&mut grid, &self.nodes[node], start, cross_start[v], vertex)
+                }
+                None => {
+                    let dashed = segments
+                        .iter()
+                        .find(|segment| segment.to == v)
+                        .is_some_and(|segment| self.edges[segment.edge].link.dashed);
+                    frame.main_line(&mut grid, center(v), start, layer_end[vertex.layer], dashed);
+                }
+            }
+        }
+
+        let mut labels = vec![];
+        for segment in &segments {
+            let (from, to) = (&vertices[segment.from], &vertices[segment.to]);
+            let link = &self.edges[segment.edge].link;
+            let (from_center, to_center) = (center(segment.from), center(segment.to));
+            let far = match from.node {
+                Some(_) => layer_start[from.layer] + from.main - 1,
+                None => layer_end[from.layer],
+            };
+            let bend = layer_end[from.layer] + 2;
+            let near = layer_start[to.layer];
+            frame.main_line(&mut grid, from_center, far, bend, link.dashed);
+            frame.cross_line(&mut grid, bend, from_center, to_center, link.dashed);
+            if to.node.is_some() && link.arrow {
+                frame.main_line(&mut grid, to_center, bend, near - 1, link.dashed);
+                let (x, y) = frame.point(near - 1, to_center);
+                grid.glyph(x, y, direction.arrow());
+            } else {
+                frame.main_line(&mut grid, to_center, bend, near, link.dashed);
+            }
+            if to.node.is_some() && !link.label.is_empty() {
+                labels.push((frame.label(&link.label, bend + 1, to_center), &link.label));
+            }
+        }
+        for ((x, y), label) in labels {
+            if !grid.is_empty(x, y, label.width()) {
+                return None;
+            }
+            grid.text(x, y, label);
+        }
+        Some(grid)
+    }
+
+    /// Assigns each node the length of the longest path reaching it, or `None` for cycles.
+    fn layers(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.nodes.len()];
+        for edge in &self.edges {
+            incoming[edge.to] += 1;
+        }
+        let mut layers = vec![0; self.nodes.len()];
+        let mut ready: Vec<usize> = (0..self.nodes.len())
+            .filter(|&node| incoming[node] == 0)
+            .rev()
+            .collect();
+        let mut visited = 0;
+        while let Some(node) = ready.pop() {
+            visited += 1;
+            for edge in self.edges.iter().filter(|edge| edge.from == node) {
+                layers[edge.to] = layers[edge.to].max(layers[node] + 1);
+                incoming[edge.to] -= 1;
+                if incoming[edge.to] == 0 {
+                    ready.push(edge.to);
+                }
+            }
+        }
+        (visited == self.nodes.len()).then_some(layers)
+    }
+}
+
+/// Orders the vertices of each layer by the mean position of their predecessors.
+fn order_layers(vertices: &[Vertex], segments: &[Segment]) -> Vec<Vec<usize>> {
+    let layer_count = vertices.iter().map(|v| v.layer).max().unwrap_or_default() + 1;
+    let mut order = vec![vec![]; layer_count];
+    for (index, vertex) in vertices.iter().enumerate() {
+        order[vertex.layer].push(index);
+    }
+    let mut position = vec![0; vertices.len()];
+    for layer in &mut order {
+        let barycenter = |v: usize| {
+            let (sum, count) = segments
+                .iter()
+                .filter(|segment| segment.to == v)
+                .fold((0, 0), |(sum, count), segment| {
+                    (sum + position[segment.from], count + 1)
+                });
+            (sum, count.max(1))
+        };
+        layer.sort_by(|&a, &b| {
+            let ((a_sum, a_count), (b_sum, b_count)) = (barycenter(a), barycenter(b));
+            (a_sum * b_count).cmp(&(b_sum * a_count))
+        });
+        for (index, &v) in layer.iter().enumerate() {
+            position[v] = index;
+        }
+    }
+    order
+}
+
+/// Maps main- and cross-axis positions to grid columns and rows.
+struct Frame {
+    direction: Direction,
+    total_main: usize,
+}
+
+impl Frame {
+    fn main(&self, main: usize) -> usize {
+        match self.direction {
+            Direction::Down | Direction::Right => main,
+            Direction::Up | Direction::Left => self.total_main - 1 - main,
+        }
+    }
+
+    fn point(&self, main: usize, cross: usize) -> (usize, usize) {
+        if self.direction.is_vertical() {
+            (cross, self.main(main))
+        } else {
+            (self.main(main), cross)
+        }
+    }
+
+    fn main_line(&self, grid: &mut Grid, cross: usize, from: usize, to: usize, dashed: bool) {
+        let (from, to) = (self.main(from), self.main(to));
+        if self.direction.is_vertical() {
+            grid.vertical(cross, from, to, dashed);
+        } else {
+            grid.horizontal(cross, from, to, dashed);
+        }
+    }
+
+    fn cross_line(&self, grid: &mut Grid, main: usize, from: usize, to: usize, dashed: bool) {
+        let main = self.main(main);
+        if self.direction.is_vertical() {
+            grid.horizontal(main, from, to, dashed);
+        } else {
+            grid.vertical(main, from, to, dashed);
+        }
+    }
+
+    fn node(&self, grid: &mut Grid, node: &Node, main: usize, cross: usize, vertex: &Vertex) {
+        let (x1, y1) = self.point(main, cross);
+        let (x2, y2) = self.point(main + vertex.main - 1, cross + vertex.cross - 1);
+        let (left, top, right, bottom) = (x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2));
+        grid.rectangle(left, top, right - left + 1, bottom - top + 1);
+        let corners = match node.shape {
+            Shape::Rectangle => None,
+            Shape::Rounded => Some(['╭', '╮', '╰', '╯']),
+            Shape::Decision => Some(['╱', '╲', '╲', '╱']),
+        };
+        if let Some([top_left, top_right, bottom_left, bottom_right]) = corners {
+            grid.glyph(left, top, top_left);
+            grid.glyph(right, top, top_right);
+            grid.glyph(left, bottom, bottom_left);
+            grid.glyph(right, bottom, bottom_right);
+        }
+        grid.text(left + 2, top + 1, &node.label);
+    }
+
+    /// Returns where a label for the edge entering a layer at `cross` starts.
+    ///
+    /// Labels sit beside vertical edges and above horizontal ones, after the bend at `main - 1`.
+    fn label(&self, label: &str, main: usize, cross: usize) -> (usize, usize) {
+        if self.direction.is_vertical() {
+            (cross + 2, self.main(main))
+        } else {
+            let end = main + label.width().saturating_sub(1);
+            (self.main(main).min(self.main(end)), cross - 1)
+        }
+    }
+}
+
+/// Parses an optional shaped label, such as `[text]`, `(text)`, or `{text}`.
+///
+/// Returns `None` for unsupported shapes, and `Some(None)` when no label follows the ID.
+fn parse_shape(rest: &mut &str) -> Option<Option<(String, Shape)>> {
+    const SHAPES: [(&str, &str, Shape); 5] = [
+        ("((", "))", Shape::Rounded),
+        ("([", "])", Shape::Rounded),
+        ("(", ")", Shape::Rounded),
+        ("[", "]", Shape::Rectangle),
+        ("{", "}", Shape::Decision),
+    ];
+    let Some((open, close, shape)) = SHAPES
+        .into_iter()
+        .find(|(open, _, _)| rest.starts_with(open))
+    else {
+        return match rest.chars().next() {
+            Some(c) if c.is_whitespace() || "-=.;".contains(c) => Some(None),
+            None => Some(None),
+            Some(_) => None,
+        };
+    };
+    let inner = &rest[open.len()..];
+    if inner.starts_with(['[', '(', '{', '/', '\\']) {
+        return None;
+    }
+    let end = inner.find(close)?;
+    let label = inner[..end].trim();
+    let label = label
+        .strip_prefix('"')
+        .and_then(|label| label.strip_suffix('"'))
+        .unwrap_or(label);
+    *rest = &inner[end + close.len()..];
+    Some(Some((label.to_owned(), shape)))
+}
+
+/// Parses a link, such as `-->`, `-.->`, `==>`, `---`, `-->|label|`, or `-- label -->`.
+fn parse_link(rest: &mut &str) -> Option<Link> {
+    let operator = take_operator(rest);
+    let mut link = match link_kind(operator) {
+        Some(link) => link,
+        None if matches!(operator, "--" | "==" | "-.") => {
+            let closers: &[&str] = match operator {
+                "--" => &["-->", "---"],
+                "==" => &["==>", "==="],
+                _ => &[".->", ".-"],
+            };
+            let end = closers
+                .iter()
+                .filter_map(|closer| rest.find(closer))
+                .min()?;
+            let label = rest[..end].trim().to_owned();
+            *rest = &rest[end..];
+            let closer = take_operator(rest);
+            let closer = match operator {
+                "-." => format!("-{closer}"),
+                _ => closer.to_owned(),
+            };
+            Link {
+                label,
+                ..link_kind(&closer)?
+            }
+        }
+        None => return None,
+    };
+    if let Some(after) = rest.trim_start().strip_prefix('|') {
+        let end = after.find('|')?;
+        after[..end].trim().clone_into(&mut link.label);
+        *rest = &after[end + 1..];
+    }
+    Some(link)
+}
+
+/// Splits the leading run of link characters off `rest`.
+fn take_operator<'s>(rest: &mut &'s str) -> &'s str {
+    let end = rest
+        .find(|c: char| !matches!(c, '-' | '=' | '.' | '>'))
+        .unwrap_or(rest.len());
+    let (operator, after) = rest.split_at(end);
+    *rest = after;
+    operator
+}
+
+/// Returns the kind of a complete link operator, such as `-->` or `-.-`.
+fn link_kind(operator: &str) -> Option<Link> {
+    let (body, arrow) = match operator.strip_suffix('>') {
+        Some(body) => (body, true),
+        None => (operator, false),
+    };
+    let min_len = if arrow { 2 } else { 3 };
+    let solid =
+        body.len() >= min_len && (body.chars().all(|c| c == '-') || body.chars().all(|c| c == '='));
+    let dashed = body.len() >= 3
+        && body.starts_with('-')
+        && body.ends_with('-')
+        && body[1..body.len() - 1].chars().all(|c| c == '.');
+    (solid || dashed).then(|| Link {
+        label: String::new(),
+        arrow,
+        dashed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn assert_layout(source: &str, expected: &str) {
+        let mut lines = source.lines();
+        let header = lines.next().unwrap();
+        let lines: Vec<_> = lines.collect();
+        let grid = layout(header, &lines).expect("supported flowchart");
+
+        assert_eq!(grid.into_rows().join("\n"), expected);
+    }
+
+    #[test]
+    fn top_down_chain_with_label() {
+        assert_layout(
+            indoc! {"
+                graph TD
+                A[Start] -->|go| B(Done)
+            "},
+            indoc! {"
+                ┌───────┐
+                │ Start │
+                └───┬───┘
+                    │
+                    │
+                    │ go
+                    ▼
+                ╭──────╮
+                │ Done │
+                ╰──────╯"},
+        );
+    }
+
+    #[test]
+    fn branches_share_a_bend() {
+        assert_layout(
+            indoc! {"
+                flowchart TD
+                A --> B
+                A -.-> C
+            "},
+            indoc! {"
+                   ┌───┐
+                   │ A │
+                   └─┬─┘
+                     │
+                  ┌──┴┄┄┄┐
+                  │      ┆
+                  ▼      ▼
+                ┌───┐  ┌───┐
+                │ B │  │ C │
+                └───┘  └───┘"},
+        );
+    }
+
+    #[test]
+    fn left_to_right_with_text_label() {
+        assert_layout(
+            "graph LR; A -- yes --> B",
+            indoc! {"
+                ┌───┐  yes  ┌───┐
+                │ A ├──────▶│ B │
+                └───┘       └───┘"},
+        );
+    }
+
+    #[test]
+    fn unsupported_syntax_returns_none() {
+        for source in [
+            "graph TD\nA --> B\nB --> A",
+            "graph TD\nsubgraph one\nA\nend",
+            "graph TD\nA & B --> C",
+            "graph TD\nA[[sub]] --> B",
+            "graph XY\nA --> B",
+            "graph TD\nA --x B",
+        ] {
+            let mut lines = source.lines();
+            let header = lines.next().unwrap();
+            let lines: Vec<_> = lines.collect();
+            assert!(layout(header, &lines).is_none(), "{source}");
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! A character grid for drawing diagrams.
+//!
+//! Lines are recorded as the sides of each cell they connect, so crossing and meeting lines merge
+//! into the matching box-drawing junction, such as `┬` or `┼`, when the grid is rendered. Text and
+//! arrowheads replace whatever lines a cell holds.
+
+use unicode_width::UnicodeWidthChar;
+
+pub const UP: u8 = 1;
+pub const DOWN: u8 = 2;
+pub const LEFT: u8 = 4;
+pub const RIGHT: u8 = 8;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Cell {
+    #[default]
+    Empty,
+    Line {
+        sides: u8,
+        dashed: bool,
+    },
+    Glyph(char),
+    /// The second column of a wide glyph.
+    Continuation,
+}
+
+/// A growable grid of cells, addressed by column and row.
+#[derive(Debug, Default)]
+pub struct Grid {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Grid {
+    fn cell_mut(&mut self, x: usize, y: usize) -> &mut Cell {
+        if self.rows.len() <= y {
+            self.rows.resize_with(y + 1, Vec::new);
+        }
+        let row = &mut self.rows[y];
+        if row.len() <= x {
+            row.resize(x + 1, Cell::Empty);
+        }
+        &mut row[x]
+    }
+
+    /// Returns the number of columns, including trailing empty cells.
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or_default()
+    }
+
+    /// Returns whether `width` cells starting at `x` on row `y` are empty.
+    pub fn is_empty(&self, x: usize, y: usize, width: usize) -> bool {
+        let Some(row) = self.rows.get(y) else {
+            return true;
+        };
+        (x..x + width).all(|x| row.get(x).is_none_or(|cell| *cell == Cell::Empty))
+    }
+
+    /// Connects the `sides` of a cell, merging them with any line already there.
+    pub fn connect(&mut self, x: usize, y: usize, sides: u8, dashed: bool) {
+        let cell = self.cell_mut(x, y);
+        match cell {
+            Cell::Empty => {
+                *cell = Cell::Line { sides, dashed };
+            }
+            Cell::Line {
+                sides: existing,
+                dashed: existing_dashed,
+            } => {
+                *existing |= sides;
+                *existing_dashed &= dashed;
+            }
+            Cell::Glyph(_) | Cell::Continuation => {}
+        }
+    }
+
+    /// Draws a horizontal line between two columns, inclusive.
+    pub fn horizontal(&mut self, y: usize, from: usize, to: usize, dashed: bool) {
+        let (start, end) = (from.min(to), from.max(to));
+        for x in start..=end {
+            let left = if x > start { LEFT } else { 0 };
+            let right = if x < end { RIGHT } else { 0 };
+            if left | right != 0 {
+                self.connect(x, y, left | right, dashed);
+            }
+        }
+    }
+
+    /// Draws a vertical line between two rows, inclusive.
+    pub fn vertical(&mut self, x: usize, from: usize, to: usize, dashed: bool) {
+        let (start, end) = (from.min(to), from.max(to));
+        for y in start..=end {
+            let up = if y > start { UP } else { 0 };
+            let down = if y < end { DOWN } else { 0 };
+            if up | down != 0 {
+                self.connect(x, y, up | down, dashed);
+            }
+        }
+    }
+
+    /// Draws the border of a rectangle whose top-left corner is at `x`, `y`.
+    pub fn rectangle(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        let (right, bottom) = (x + width - 1, y + height - 1);
+        self.horizontal(y, x, right, false);
+        self.horizontal(bottom, x, right, false);
+        self.vertical(x, y, bottom, false);
+        self.vertical(right, y, bottom, false);
+    }
+
+    /// Erases every cell of a rectangle whose top-left corner is at `x`, `y`.
+    pub fn clear(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for y in y..y + height {
+            for x in x..x + width {
+                *self.cell_mut(x, y) = Cell::Empty;
+            }
+        }
+    }
+
+    /// Places a glyph, replacing the cell's lines.
+    pub fn glyph(&mut self, x: usize, y: usize, glyph: char) {
+        *self.cell_mut(x, y) = Cell::Glyph(glyph);
+    }
+
+    /// Writes `text` starting at `x`, giving wide characters two cells.
+    pub fn text(&mut self, x: usize, y: usize, text: &str) {
+        let mut x = x;
+        for c in text.chars() {
+            let width = c.width().unwrap_or_default();
+            if width == 0 {
+                continue;
+            }
+            self.glyph(x, y, c);
+            if width == 2 {
+                *self.cell_mut(x + 1, y) = Cell::Continuation;
+            }
+            x += width;
+        }
+    }
+
+    /// Renders each row as a string without trailing spaces.
+    pub fn into_rows(self) -> Vec<String> {
+        self.rows
+            .into_iter()
+            .map(|row| {
+                let row: String = row.into_iter().filter_map(render).collect();
+                row.trim_end().to_owned()
+            })
+            .collect()
+    }
+}
+
+fn render(cell: Cell) -> Option<char> {
+    let glyph = match cell {
+        Cell::Empty => ' ',
+        Cell::Glyph(glyph) => glyph,
+        Cell::Continuation => return None,
+        Cell::Line { sides, dashed } => {
+            let horizontal = sides & (UP | DOWN) == 0;
+            let vertical = sides & (LEFT | RIGHT) == 0;
+            // The bits of `sides` are `RIGHT`, `LEFT`, `DOWN`, and `UP`, from the highest.
+            match sides {
+                _ if dashed && horizontal => '┄',
+                _ if dashed && vertical => '┆',
+                _ if horizontal => '─',
+                _ if vertical => '│',
+                0b1010 => '┌',
+                0b0110 => '┐',
+                0b1001 => '└',
+                0b0101 => '┘',
+                0b1011 => '├',
+                0b0111 => '┤',
+                0b1110 => '┬',
+                0b1101 => '┴',
+                _ => '┼',
+            }
+        }
+    };
+    Some(glyph)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn meeting_lines_merge_into_junctions() {
+        let mut grid = Grid::default();
+        grid.rectangle(0, 0, 5, 3);
+        grid.vertical(2, 2, 4, false);
+        grid.horizontal(4, 0, 4, true);
+        grid.text(1, 1, "界a");
+
+        assert_eq!(
+            grid.into_rows(),
+            ["┌───┐", "│界a│", "└─┬─┘", "  │", "┄┄┴┄┄"]
+        );
+    }
+}
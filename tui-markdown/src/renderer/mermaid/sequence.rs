@@ -0,0 +1,418 @@
+//! Lifeline layout for Mermaid sequence diagrams.
+//!
+//! Participants are boxed side by side, above and below the vertical lifelines between them.
+//! Messages and notes follow in source order, each on its own rows. Lifelines are spaced so every
+//! message label fits between the participants it connects.
+
+use unicode_width::UnicodeWidthStr;
+
+use super::grid::Grid;
+
+/// Rows above the first message: the participant boxes.
+const HEADER_ROWS: usize = 3;
+
+#[derive(Debug)]
+struct Participant {
+    id: String,
+    label: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Head {
+    None,
+    Filled,
+    Open,
+    Cross,
+}
+
+impl Head {
+    const fn glyph(self, rightward: bool) -> Option<char> {
+        match (self, rightward) {
+            (Self::None, _) => None,
+            (Self::Filled, true) => Some('▶'),
+            (Self::Filled, false) => Some('◀'),
+            (Self::Open, true) => Some('▷'),
+            (Self::Open, false) => Some('◁'),
+            (Self::Cross, _) => Some('×'),
+        }
+    }
+}
+
+/// Message operators, longest first, with whether they are dashed and their arrowheads.
+const ARROWS: [(&str, bool, Head); 8] = [
+    ("-->>", true, Head::Filled),
+    ("->>", false, Head::Filled),
+    ("--x", true, Head::Cross),
+    ("-x", false, Head::Cross),
+    ("--)", true, Head::Open),
+    ("-)", false, Head::Open),
+    ("-->", true, Head::None),
+    ("->", false, Head::None),
+];
+
+#[derive(Debug)]
+enum Step {
+    Message {
+        from: usize,
+        to: usize,
+        text: String,
+        dashed: bool,
+        head: Head,
+    },
+    Note {
+        placement: Placement,
+        text: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Placement {
+    LeftOf(usize),
+    RightOf(usize),
+    Over(usize, usize),
+}
+
+#[derive(Debug, Default)]
+struct SequenceDiagram {
+    participants: Vec<Participant>,
+    steps: Vec<Step>,
+}
+
+/// Lays out the lines after a `sequenceDiagram` header.
+///
+/// Returns `None` for unsupported syntax, including blocks such as `loop` and `alt`, and
+/// `autonumber`.
+pub fn layout(lines: &[&str]) -> Option<Grid> {
+    let mut diagram = SequenceDiagram::default();
+    for line in lines.iter().map(|line| line.trim()) {
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match keyword {
+            "participant" | "actor" => {
+                let rest = rest.trim();
+                let (id, label) = rest.split_once(" as ").unwrap_or((rest, rest));
+                let index = diagram.participant(id.trim());
+                label
+                    .trim()
+                    .clone_into(&mut diagram.participants[index].label);
+            }
+            "activate" | "deactivate" => {}
+            "Note" | "note" => diagram.parse_note(rest)?,
+            _ if line.contains(|c: char| c.is_whitespace())
+                && !line.contains(':')
+                && !line.contains('-') =>
+            {
+                return None;
+            }
+            _ => diagram.parse_message(line)?,
+        }
+    }
+    if diagram.participants.is_empty() {
+        return None;
+    }
+    Some(diagram.draw())
+}
+
+impl SequenceDiagram {
+    /// Returns the index of the participant named `id`, adding it when it is new.
+    fn participant(&mut self, id: &str) -> usize {
+        if let Some(index) = self.participants.iter().position(|p| p.id == id) {
+            return index;
+        }
+        self.participants.push(Participant {
+            id: id.to_owned(),
+            label: id.to_owned(),
+        });
+        self.participants.len() - 1
+    }
+
+    /// Parses a message, such as `Alice->>Bob: Hello`.
+    fn parse_message(&mut self, line: &str) -> Option<()> {
+        let (start, (operator, dashed, head)) = line.char_indices().find_map(|(index, _)| {
+            ARROWS
+                .into_iter()
+                .find(|(operator, ..)| line[index..].starts_with(operator))
+                .map(|arrow| (index, arrow))
+        })?;
+        let from = line[..start].trim();
+        let rest = line[start + operator.len()..].trim_start_matches(['+', '-']);
+        let (to, text) = rest.split_once(':').unwrap_or((rest, ""));
+        let to = to.trim();
+        if from.is_empty() || to.is_empty() || !is_id(from) || !is_id(to) {
+            return None;
+        }
+        let from = self.participant(from);
+        let to = self.participant(to);
+        self.steps.push(Step::Message {
+            from,
+            to,
+            text: text.trim().to_owned(),
+            dashed,
+            head,
+        });
+        Some(())
+    }
+
+    /// Parses a note after the `Note` keyword, such as `right of Alice: text`.
+    fn parse_note(&mut self, rest: &str) -> Option<()> {
+        let (target, text) = rest.split_once(':')?;
+        let target = target.trim();
+        let placement = if let Some(id) = target.strip_prefix("left of ") {
+            Placement::LeftOf(self.participant(id.trim()))
+        } else if let Some(id) = target.strip_prefix("right of ") {
+            Placement::RightOf(self.participant(id.trim()))
+        } else if let Some(ids) = target.strip_prefix("over ") {
+            let (first, last) = ids.split_once(',').unwrap_or((ids, ids));
+            Placement::Over(
+                self.participant(first.trim()),
+                self.participant(last.trim()),
+            )
+        } else {
+            return None;
+        };
+        self.steps.push(Step::Note {
+            placement,
+            text: text.trim().to_owned(),
+        });
+        Some(())
+    }
+
+    fn draw(&self) -> Grid {
+        let box_widths: Vec<_> = self
+            .participants
+            .iter()
+            .map(|participant| participant.label.width() + 4)
+            .collect();
+        let centers = self.centers(&box_widths);
+        let mut grid = Grid::default();
+
+        let mut y = HEADER_ROWS;
+        let mut notes = vec![];
+        for step in &self.steps {
+            match step {
+                Step::Message {
+                    from,
+                    to,
+                    text,
+                    dashed,
+                    head,
+                } => {
+                    let (from, to) = (centers[*from], centers[*to]);
+                    draw_message(&mut grid, y, from, to, text, *dashed, *head);
+                    y += 2;
+                }
+                Step::Note { placement, text } => {
+                    let (left, width) = note_extent(*placement, &centers, text.width() + 4);
+                    notes.push((left, y, width, text));
+                    y += 3;
+                }
+            }
+        }
+        let footer = y + 1;
+
+        for ((participant, &width), &center) in
+            self.participants.iter().zip(&box_widths).zip(&centers)
+        {
+            let left = center - width / 2;
+            for top in [0, footer] {
+                grid.rectangle(left, top, width, 3);
+                grid.text(left + 2, top + 1, &participant.label);
+            }
+            grid.vertical(center, 2, footer, false);
+        }
+        // Lifelines are drawn after messages so that message lines merge into them. Labels and
+        // arrowheads are glyphs, which lines never replace.
+        for (left, top, width, text) in notes {
+            let left = usize::try_from(left).unwrap_or_default();
+            grid.clear(left, top, width, 3);
+            grid.rectangle(left, top, width, 3);
+            grid.text(left + (width - text.width()) / 2, top + 1, text);
+        }
+        grid
+    }
+
+    /// Places lifelines far enough apart for the boxes, labels, and notes between them.
+    fn centers(&self, box_widths: &[usize]) -> Vec<usize> {
+        let mut gaps: Vec<usize> = box_widths
+            .windows(2)
+            .map(|pair| pair[0] - pair[0] / 2 + pair[1] / 2 + 2)
+            .collect();
+        let count = self.participants.len();
+        for step in &self.steps {
+            let (first, last, distance) = match *step {
+                Step::Message {
+                    from, to, ref text, ..
+                } if from == to => (from, from + 1, text.width() + 6),
+                Step::Message {
+                    from, to, ref text, ..
+                } => (from.min(to), from.max(to), text.width() + 4),
+                Step::Note {
+                    placement: Placement::RightOf(index),
+                    ref text,
+                } => (index, index + 1, text.width() + 7),
+                Step::Note {
+                    placement: Placement::LeftOf(index),
+                    ref text,
+                } if index > 0 => (index - 1, index, text.width() + 7),
+                Step::Note { .. } => continue,
+            };
+            if last >= count {
+                continue;
+            }
+            let current: usize = gaps[first..last].iter().sum();
+            if current < distance {
+                gaps[last - 1] += distance - current;
+            }
+        }
+
+        let mut centers = vec![box_widths[0] / 2];
+        for gap in gaps {
+            centers.push(centers[centers.len() - 1] + gap);
+        }
+        // Notes left of or over the first participants may start before the first box.
+        let overhang = self
+            .steps
+            .iter()
+            .filter_map(|step| match step {
+                Step::Note { placement, text } => {
+                    Some(-note_extent(*placement, &centers, text.width() + 4).0)
+                }
+                Step::Message { .. } => None,
+            })
+            .max()
+            .unwrap_or_default();
+        let shift = usize::try_from(overhang).unwrap_or_default();
+        centers.iter().map(|center| center + shift).collect()
+    }
+}
+
+/// Draws a message's label row at `y` and its arrow on the row below.
+fn draw_message(
+    grid: &mut Grid,
+    y: usize,
+    from: usize,
+    to: usize,
+    text: &str,
+    dashed: bool,
+    head: Head,
+) {
+    if from == to {
+        grid.horizontal(y, from, from + 2, dashed);
+        grid.vertical(from + 2, y, y + 1, dashed);
+        match head.glyph(false) {
+            Some(glyph) => {
+                grid.horizontal(y + 1, from + 1, from + 2, dashed);
+                grid.glyph(from + 1, y + 1, glyph);
+            }
+            None => grid.horizontal(y + 1, from, from + 2, dashed),
+        }
+        grid.text(from + 4, y, text);
+        return;
+    }
+
+    let rightward = from < to;
+    let (low, high) = (from.min(to), from.max(to));
+    match head.glyph(rightward) {
+        Some(glyph) => {
+            let tip = if rightward { to - 1 } else { to + 1 };
+            grid.horizontal(y + 1, from, tip, dashed);
+            grid.glyph(tip, y + 1, glyph);
+        }
+        None => grid.horizontal(y + 1, from, to, dashed),
+    }
+    let room = high - low - 1;
+    grid.text(low + 1 + room.saturating_sub(text.width()) / 2, y, text);
+}
+
+/// Returns the left column, which may be negative, and the width of a note.
+fn note_extent(placement: Placement, centers: &[usize], width: usize) -> (isize, usize) {
+    let signed = |value: usize| isize::try_from(value).unwrap_or(isize::MAX);
+    match placement {
+        Placement::LeftOf(index) => (signed(centers[index]) - 1 - signed(width), width),
+        Placement::RightOf(index) => (signed(centers[index] + 2), width),
+        Placement::Over(first, last) => {
+            let (low, high) = (
+                centers[first].min(centers[last]),
+                centers[first].max(centers[last]),
+            );
+            let width = width.max(high - low + 5);
+            (signed((low + high) / 2) - signed(width / 2), width)
+        }
+    }
+}
+
+fn is_id(id: &str) -> bool {
+    !id.contains(|c: char| c.is_whitespace() || matches!(c, '-' | '>' | ':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn assert_layout(source: &str, expected: &str) {
+        let lines: Vec<_> = source.lines().collect();
+        let grid = layout(&lines).expect("supported sequence diagram");
+
+        assert_eq!(grid.into_rows().join("\n"), expected);
+    }
+
+    #[test]
+    fn messages_between_participants() {
+        assert_layout(
+            indoc! {"
+                participant A as Alice
+                A->>B: Hello
+                B-->>A: Hi
+            "},
+            indoc! {"
+                ┌───────┐  ┌───┐
+                │ Alice │  │ B │
+                └───┬───┘  └─┬─┘
+                    │ Hello  │
+                    ├───────▶│
+                    │   Hi   │
+                    │◀┄┄┄┄┄┄┄┤
+                    │        │
+                ┌───┴───┐  ┌─┴─┐
+                │ Alice │  │ B │
+                └───────┘  └───┘"},
+        );
+    }
+
+    #[test]
+    fn self_messages_and_notes() {
+        assert_layout(
+            indoc! {"
+                A->>A: think
+                Note right of A: idea
+            "},
+            indoc! {"
+                ┌───┐
+                │ A │
+                └─┬─┘
+                  ├─┐ think
+                  │◀┘
+                  │ ┌──────┐
+                  │ │ idea │
+                  │ └──────┘
+                  │
+                ┌─┴─┐
+                │ A │
+                └───┘"},
+        );
+    }
+
+    #[test]
+    fn unsupported_syntax_returns_none() {
+        for source in [
+            "loop Every minute\nA->>B: ping\nend",
+            "autonumber\nA->>B: hi",
+            "A->>: hi",
+        ] {
+            let lines: Vec<_> = source.lines().collect();
+            assert!(layout(&lines).is_none(), "{source}");
+        }
+    }
+}
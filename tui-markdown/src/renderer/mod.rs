@@ -8,9 +8,10 @@
 //! descriptions receive those spans first, followed by active table cells, then the output line.
 //! This sink order preserves inline event ordering inside buffered constructs.
 
+use std::collections::BTreeMap;
 use std::vec;
 
-use itertools::Itertools;
+use itertools::{Itertools, MultiPeek};
 use pulldown_cmark::{CowStr, Event, Options as ParseOptions, Parser, Tag, TagEnd};
 use ratatui_core::style::Style;
 use ratatui_core::text::{Line, Span, Text};
@@ -18,12 +19,24 @@ use tracing::{debug, instrument};
 
 #[cfg(feature = "highlight-code")]
 use crate::code_theme::CodeTheme;
-use crate::options::{ImageFallback, Options};
+use crate::document::{CodeBlock, DetailsSummary, Document, Footnote, TaskCount};
+use crate::options::{
+    BigHeadings, CodeBlockBackground, CodeLineNumbers, CodeOverflow, CodeWhitespace,
+    CompletedTasks, DelimitedBlocks, DetailsState, DisplayMath, FootnoteLayout, FootnoteNumbering,
+    HeadingDecoration, HeadingNumbering, HtmlBlockMode, ImageFallback, InlineHtmlMode,
+    MermaidDiagrams, Options,
+};
+#[cfg(feature = "highlight-code")]
+use crate::options::{CodeLanguageDetection, InlineCodeHighlighting};
+use crate::style_context::{BlockKind, InlineElement};
 use crate::style_sheet::StyleSheet;
 
 mod blockquote;
 mod code;
+mod context;
 mod definition_list;
+mod delimited;
+mod details;
 mod footnote;
 mod formatting;
 mod heading;
@@ -32,9 +45,12 @@ mod image;
 mod link;
 mod list;
 mod math;
+mod mermaid;
+mod overflow;
 mod table;
 #[cfg(test)]
 mod test_support;
+mod whitespace;
 
 /// Render Markdown `input` into a [`Text`] using the default [`Options`].
 ///
@@ -70,6 +86,37 @@ pub fn from_str(input: &str) -> Text<'_> {
 /// assert_eq!(text.to_string(), "[img] diagram (diagram.png)");
 /// ```
 pub fn from_str_with_options<'a, S>(input: &'a str, options: &Options<S>) -> Text<'a>
+where
+    S: StyleSheet,
+{
+    document_from_str(input, options).into_text()
+}
+
+/// Render Markdown `input` into a [`Document`] using the supplied [`Options`].
+///
+/// The document contains the same text as [`from_str_with_options`], together with the positions
+/// of interactive elements such as `<details>` summaries and footnotes. An interactive viewer can
+/// use them to toggle an element and render the input again, or to jump to a footnote.
+///
+/// # Example
+///
+/// ```
+/// use tui_markdown::{document_from_str, DetailsState, Options};
+///
+/// let markdown = "<details>\n<summary>More</summary>\n\nHidden.\n\n</details>";
+/// let options = Options::default().details(DetailsState::Collapsed);
+/// let document = document_from_str(markdown, &options);
+///
+/// assert_eq!(document.text().to_string(), "▶ More");
+/// assert_eq!(document.details()[0].line, 0);
+///
+/// let toggled = document.details()[0].state.toggled();
+/// let options = options.details_at(document.details()[0].index, toggled);
+/// let document = document_from_str(markdown, &options);
+///
+/// assert_eq!(document.text().to_string(), "▼ More\n\nHidden.");
+/// ```
+pub fn document_from_str<'a, S>(input: &'a str, options: &Options<S>) -> Document<'a>
 where
     S: StyleSheet,
 {
@@ -87,18 +134,19 @@ where
     parse_opts.insert(ParseOptions::ENABLE_TABLES);
     let parser = Parser::new_ext(input, parse_opts);
 
-    let writer = TextWriter::new(parser, options.styles.clone(), options.image_fallback);
-    #[cfg(feature = "highlight-code")]
-    let writer = writer.with_code_theme(options.selected_code_theme());
-    writer.run()
+    TextWriter::new(parser, options).run()
 }
 
-struct TextWriter<'a, 'theme, I, S: StyleSheet> {
+struct TextWriter<'a, 'theme, I: Iterator, S: StyleSheet> {
     // Core output state.
-    /// Iterator supplying Markdown events.
-    iter: I,
+    /// Iterator supplying Markdown events, with lookahead for footnote references.
+    iter: MultiPeek<I>,
     /// Rendered terminal text.
     text: Text<'a>,
+    /// Number of terminal columns available to the text, if known.
+    width: Option<u16>,
+    /// Columns between the tab stops that tabs expand to.
+    tab_width: u16,
     /// Styles for nested inline constructs, with the active style at the top.
     inline_styles: Vec<Style>,
     /// Prefixes added to each output line, from the outermost block to the innermost.
@@ -111,8 +159,24 @@ struct TextWriter<'a, 'theme, I, S: StyleSheet> {
     needs_newline: bool,
     /// Whether raw text is inside a metadata block.
     in_metadata_block: bool,
+    /// Blocks enclosing the current event, from the outermost to the innermost.
+    blocks: Vec<BlockKind>,
 
     // Code rendering state.
+    /// Background of code blocks.
+    code_block_background: CodeBlockBackground,
+    /// Columns of padding on each side of code block panels.
+    code_block_padding: u16,
+    /// Line numbers shown beside code blocks.
+    code_line_numbers: CodeLineNumbers,
+    /// Presentation of tabs and trailing whitespace in code blocks.
+    code_whitespace: CodeWhitespace,
+    /// Handling of code lines wider than the width.
+    code_overflow: CodeOverflow,
+    /// The code block being rendered, laid out when it ends.
+    code_block: Option<code::OpenCodeBlock>,
+    /// Code blocks rendered so far, reported in the rendered [`Document`].
+    code_blocks: Vec<CodeBlock>,
     /// Active syntax highlighter while rendering a recognized fenced code block.
     #[cfg(feature = "highlight-code")]
     code_highlighter: Option<syntect::easy::HighlightLines<'theme>>,
@@ -121,11 +185,36 @@ struct TextWriter<'a, 'theme, I, S: StyleSheet> {
     /// When absent, code highlighting resolves the shared built-in default.
     #[cfg(feature = "highlight-code")]
     code_theme: Option<&'theme CodeTheme>,
+    /// Fenced languages by lowercase alias, consulted before the built-in aliases.
+    #[cfg(feature = "highlight-code")]
+    code_language_aliases: &'theme BTreeMap<String, String>,
+    /// Detection of the language of code blocks that do not name one.
+    #[cfg(feature = "highlight-code")]
+    code_language_detection: CodeLanguageDetection,
+    /// Highlighting of inline code that names its language.
+    #[cfg(feature = "highlight-code")]
+    inline_code_highlighting: InlineCodeHighlighting,
+    /// Whether the language of the current code block is detected from its first line of code.
+    #[cfg(feature = "highlight-code")]
+    code_language_pending: bool,
+    /// How the lines of the current highlighted code block are split before highlighting.
+    #[cfg(feature = "highlight-code")]
+    code_lines: code::CodeLines,
     /// Keeps the writer's shape consistent when syntax highlighting is disabled.
     #[cfg(not(feature = "highlight-code"))]
     code_theme_lifetime: std::marker::PhantomData<&'theme ()>,
 
     // Heading rendering state.
+    /// Section numbers shown before heading text.
+    heading_numbering: HeadingNumbering,
+    /// Heading levels drawn in block letters.
+    big_headings: BigHeadings,
+    /// Decoration of each heading level, starting with H1.
+    heading_decorations: [HeadingDecoration; 6],
+    /// Number of headings at each level in the current section, used for section numbers.
+    heading_counters: [usize; 6],
+    /// The heading being rendered, decorated when it ends.
+    heading: Option<heading::OpenHeading>,
     /// Heading attributes to append after heading content.
     heading_meta: Option<heading::HeadingMeta<'a>>,
 
@@ -139,11 +228,60 @@ struct TextWriter<'a, 'theme, I, S: StyleSheet> {
     /// Content to render in place of images.
     image_fallback: ImageFallback,
 
+    // HTML rendering state.
+    /// Content of the HTML block being collected, rendered when the block ends.
+    html_block: Option<String>,
+    /// Whether HTML blocks are shown, hidden, stripped of comments, or summarized.
+    html_block_mode: HtmlBlockMode,
+    /// Whether inline HTML tags are shown literally or interpreted.
+    inline_html_mode: InlineHtmlMode,
+    /// Interpreted inline HTML tags awaiting their end tags, from the outermost to the innermost.
+    inline_html_tags: Vec<html::OpenHtmlTag>,
+
+    // Details rendering state.
+    /// Open `<details>` elements, from the outermost to the innermost.
+    details: Vec<details::OpenDetails>,
+    /// Number of `<details>` elements started so far, used to index the next one.
+    details_count: usize,
+    /// State of `<details>` elements without an explicit state or `open` attribute, or `None` when
+    /// the elements render as HTML blocks.
+    details_default: Option<DetailsState>,
+    /// Explicit states of `<details>` elements by document index.
+    details_states: BTreeMap<usize, DetailsState>,
+    /// Summaries written to the output, reported in the rendered [`Document`].
+    details_summaries: Vec<DetailsSummary>,
+    /// Number of tags started in hidden content that have not ended yet.
+    hidden_depth: usize,
+
+    // Footnote rendering state.
+    /// Placement of footnote definitions.
+    footnote_layout: FootnoteLayout,
+    /// Markers used for footnote references and definitions.
+    footnote_numbering: FootnoteNumbering,
+    /// Footnotes in order of first appearance, reported in the rendered [`Document`].
+    footnotes: Vec<Footnote>,
+    /// Number of footnotes numbered so far.
+    footnote_count: usize,
+    /// The definition being rendered for the trailing footnote section.
+    footnote_capture: Option<footnote::FootnoteCapture<'a>>,
+    /// Definitions rendered for the trailing footnote section, in source order.
+    collected_footnotes: Vec<footnote::CollectedFootnote<'a>>,
+    /// References in the table being built, with the footnote index and table line offset.
+    table_footnote_references: Vec<(usize, usize)>,
+
+    // Math rendering state.
+    /// Presentation of display math.
+    display_math: DisplayMath,
+
     // List rendering state.
     /// Current list index as a stack of indices.
     list_indices: Vec<Option<u64>>,
     /// Layout of each active list item, from the outermost item to the innermost.
     list_items: Vec<list::ListItemLayout>,
+    /// Presentation of completed task-list items.
+    completed_tasks: CompletedTasks,
+    /// Task-list items seen so far, reported in the rendered [`Document`].
+    tasks: TaskCount,
 
     // Paragraph-suppression state.
     /// Whether we are inside a footnote definition.
@@ -154,6 +292,16 @@ struct TextWriter<'a, 'theme, I, S: StyleSheet> {
     // Table rendering state.
     /// Active table builder that accumulates cells during table parsing.
     table_builder: Option<table::TableBuilder<'a>>,
+    /// Presentation of `csv` and `tsv` code blocks.
+    delimited_blocks: DelimitedBlocks,
+    /// Delimited data block being buffered, rendered as a table when it ends.
+    delimited_block: Option<delimited::DelimitedBlock>,
+
+    // Diagram rendering state.
+    /// Presentation of `mermaid` code blocks.
+    mermaid_diagrams: MermaidDiagrams,
+    /// Mermaid block being buffered, laid out as a diagram when it ends.
+    mermaid_block: Option<mermaid::MermaidBlock>,
 }
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
@@ -161,44 +309,111 @@ where
     I: Iterator<Item = Event<'a>>,
     S: StyleSheet,
 {
-    fn new(iter: I, styles: S, image_fallback: ImageFallback) -> Self {
+    fn new(iter: I, options: &'theme Options<S>) -> Self {
         Self {
-            iter,
+            iter: iter.multipeek(),
             text: Text::default(),
+            width: options.width,
+            tab_width: options.tab_width,
             inline_styles: vec![],
             line_styles: vec![],
             line_prefixes: vec![],
-            styles,
+            styles: options.styles.clone(),
             needs_newline: false,
             in_metadata_block: false,
+            blocks: vec![],
+            code_block_background: options.code_block_background,
+            code_block_padding: options.code_block_padding,
+            code_line_numbers: options.code_line_numbers,
+            code_whitespace: options.code_whitespace,
+            code_overflow: options.code_overflow,
+            code_block: None,
+            code_blocks: vec![],
             #[cfg(feature = "highlight-code")]
             code_highlighter: None,
             #[cfg(feature = "highlight-code")]
-            code_theme: None,
+            code_theme: options.selected_code_theme(),
+            #[cfg(feature = "highlight-code")]
+            code_language_aliases: &options.code_language_aliases,
+            #[cfg(feature = "highlight-code")]
+            code_language_detection: options.code_language_detection,
+            #[cfg(feature = "highlight-code")]
+            inline_code_highlighting: options.inline_code_highlighting,
+            #[cfg(feature = "highlight-code")]
+            code_language_pending: false,
+            #[cfg(feature = "highlight-code")]
+            code_lines: code::CodeLines::default(),
             #[cfg(not(feature = "highlight-code"))]
             code_theme_lifetime: std::marker::PhantomData,
+            heading_numbering: options.heading_numbering,
+            big_headings: options.big_headings,
+            heading_decorations: options.heading_decorations,
+            heading_counters: [0; 6],
+            heading: None,
             heading_meta: None,
             link: None,
             images: vec![],
-            image_fallback,
+            image_fallback: options.image_fallback,
+            html_block: None,
+            html_block_mode: options.html_block_mode,
+            inline_html_mode: options.inline_html_mode,
+            inline_html_tags: vec![],
+            details: vec![],
+            details_count: 0,
+            details_default: options.details,
+            details_states: options.details_states.clone(),
+            details_summaries: vec![],
+            hidden_depth: 0,
+            footnote_layout: options.footnote_layout,
+            footnote_numbering: options.footnote_numbering,
+            footnotes: vec![],
+            footnote_count: 0,
+            footnote_capture: None,
+            collected_footnotes: vec![],
+            table_footnote_references: vec![],
+            display_math: options.display_math,
             list_indices: vec![],
             list_items: vec![],
+            completed_tasks: options.completed_tasks,
+            tasks: TaskCount::default(),
             in_footnote_definition: false,
             in_definition_description: false,
             table_builder: None,
+            delimited_blocks: options.delimited_blocks,
+            delimited_block: None,
+            mermaid_diagrams: options.mermaid_diagrams,
+            mermaid_block: None,
         }
     }
 
-    fn run(mut self) -> Text<'a> {
+    fn run(mut self) -> Document<'a> {
         debug!("Running text writer");
         while let Some(event) = self.iter.next() {
             self.handle_event(event);
         }
-        self.text
+        self.write_footnote_section();
+        let mut footnotes = self.footnotes;
+        footnotes.sort_by_key(|footnote| (footnote.number.is_none(), footnote.number));
+        Document::new(
+            self.text,
+            self.details_summaries,
+            footnotes,
+            self.tasks,
+            self.code_blocks,
+        )
     }
 
     #[instrument(level = "debug", skip(self))]
     fn handle_event(&mut self, event: Event<'a>) {
+        // `<details>` tags only arrive in HTML blocks, so those are always processed. Any other
+        // content first needs the summary of an open element, and is skipped while collapsed.
+        let is_html_block = matches!(
+            event,
+            Event::Start(Tag::HtmlBlock) | Event::Html(_) | Event::End(TagEnd::HtmlBlock)
+        );
+        if !is_html_block && self.skip_hidden_event(&event) {
+            return;
+        }
         match event {
             Event::Start(tag) => self.start_tag(tag),
             Event::End(tag) => self.end_tag(tag),
@@ -217,6 +432,7 @@ where
     }
 
     fn start_tag(&mut self, tag: Tag<'a>) {
+        self.enter_block(&tag);
         match tag {
             Tag::Paragraph => self.start_paragraph(),
             Tag::Heading {
@@ -235,11 +451,28 @@ where
             Tag::TableHead => {}
             Tag::TableRow => {}
             Tag::TableCell => self.start_table_cell(),
-            Tag::Emphasis => self.push_inline_style(Style::new().italic()),
-            Tag::Strong => self.push_inline_style(Style::new().bold()),
-            Tag::Strikethrough => self.push_inline_style(Style::new().crossed_out()),
-            Tag::Subscript => self.push_inline_style(Style::new().dim().italic()),
-            Tag::Superscript => self.push_inline_style(Style::new().dim().italic()),
+            Tag::Emphasis => {
+                let style = self.element_style(InlineElement::Emphasis, self.styles.emphasis());
+                self.push_inline_style(style);
+            }
+            Tag::Strong => {
+                let style = self.element_style(InlineElement::Strong, self.styles.strong());
+                self.push_inline_style(style);
+            }
+            Tag::Strikethrough => {
+                let style =
+                    self.element_style(InlineElement::Strikethrough, self.styles.strikethrough());
+                self.push_inline_style(style);
+            }
+            Tag::Subscript => {
+                let style = self.element_style(InlineElement::Subscript, self.styles.subscript());
+                self.push_inline_style(style);
+            }
+            Tag::Superscript => {
+                let style =
+                    self.element_style(InlineElement::Superscript, self.styles.superscript());
+                self.push_inline_style(style);
+            }
             Tag::Link { dest_url, .. } => self.push_link(dest_url),
             Tag::Image { dest_url, .. } => self.start_image(dest_url),
             Tag::MetadataBlock(_) => self.start_metadata_block(),
@@ -250,6 +483,18 @@ where
     }
 
     fn end_tag(&mut self, tag: TagEnd) {
+        let ends_inline_container = matches!(
+            tag,
+            TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::TableCell
+                | TagEnd::Item
+                | TagEnd::DefinitionListTitle
+                | TagEnd::DefinitionListDefinition
+        );
+        if ends_inline_container {
+            self.close_inline_html_tags();
+        }
         match tag {
             TagEnd::Paragraph => self.end_paragraph(),
             TagEnd::Heading(_) => self.end_heading(),
@@ -275,18 +520,14 @@ where
             TagEnd::DefinitionListTitle => self.end_definition_title(),
             TagEnd::DefinitionListDefinition => self.end_definition_description(),
         }
+        self.leave_block(tag);
     }
 
     fn start_paragraph(&mut self) {
         // Loose list items emit a paragraph start after the item handler has already written the
         // marker. Keep only that first paragraph on the marker line; later paragraphs have either
         // added content or set `needs_newline`.
-        let list_marker_line_is_open = self.list_items.last().is_some_and(|item| {
-            !self.needs_newline
-                && self.text.lines.len() == item.marker_line + 1
-                && self.text.lines[item.marker_line].spans.len() == item.marker_span_count
-        });
-        if list_marker_line_is_open {
+        if self.list_marker_line_is_open() {
             return;
         }
 
@@ -316,8 +557,17 @@ where
     }
 
     fn text(&mut self, text: CowStr<'a>) {
+        if self.missing_footnote_reference(&text) {
+            return;
+        }
+
+        if self.push_delimited_text(&text) || self.push_mermaid_text(&text) {
+            return;
+        }
+
         if self.table_builder.is_some() {
-            let style = self.inline_styles.last().copied().unwrap_or_default();
+            let style = self.inline_style();
+            let text = self.expand_tabs(text);
             self.push_span(Span::styled(text, style));
             return;
         }
@@ -335,13 +585,16 @@ where
                 self.push_line(Line::default());
             }
 
-            let style = self.inline_styles.last().copied().unwrap_or_default();
+            let style = self.inline_style();
+            let line = self.expand_tabs(CowStr::from(line.to_owned()));
 
-            let span = Span::styled(line.to_owned(), style);
+            let span = Span::styled(line, style);
 
             self.push_span(span);
         }
-        self.needs_newline = false;
+        // Code inside containers such as blockquotes arrives one line per event, each ending in a
+        // newline that `lines` drops. Start the next event on its own line.
+        self.needs_newline = self.code_block.is_some() && text.ends_with('\n');
     }
 
     fn hard_break(&mut self) {
@@ -357,14 +610,14 @@ where
             self.push_line(Line::default());
         }
         self.line_styles.push(self.styles.metadata_block());
-        self.push_line(Line::from("---"));
+        self.push_line(Line::from(self.styles.metadata_delimiter().to_owned()));
         self.push_line(Line::default());
         self.in_metadata_block = true;
     }
 
     fn end_metadata_block(&mut self) {
         if self.in_metadata_block {
-            self.push_line(Line::from("---"));
+            self.push_line(Line::from(self.styles.metadata_delimiter().to_owned()));
             self.line_styles.pop();
             self.in_metadata_block = false;
             self.needs_newline = true;
@@ -375,7 +628,11 @@ where
         if self.needs_newline {
             self.push_line(Line::default());
         }
-        self.push_line(Line::from("---"));
+        let rule = Span::styled(
+            self.styles.horizontal_rule().to_owned(),
+            self.styles.horizontal_rule_style(),
+        );
+        self.push_line(Line::from(rule));
         self.needs_newline = true;
     }
 
@@ -522,4 +779,44 @@ mod tests {
             ])
         );
     }
+
+    #[derive(Clone, Copy)]
+    struct CustomDelimiters;
+
+    impl StyleSheet for CustomDelimiters {
+        fn horizontal_rule(&self) -> &str {
+            "──────"
+        }
+
+        fn horizontal_rule_style(&self) -> Style {
+            Style::new().dark_gray()
+        }
+
+        fn metadata_delimiter(&self) -> &str {
+            "+++"
+        }
+    }
+
+    #[rstest]
+    fn style_sheet_selects_rule_and_metadata_delimiters(_with_tracing: DefaultGuard) {
+        let options = Options::new(CustomDelimiters);
+        let markdown = indoc! {"
+            ---
+            title: Demo
+            ---
+
+            ***
+        "};
+
+        assert_eq!(
+            from_str_with_options(markdown, &options),
+            Text::from_iter([
+                Line::from("+++").style(Style::new().light_yellow()),
+                Line::from("title: Demo").style(Style::new().light_yellow()),
+                Line::from("+++").style(Style::new().light_yellow()),
+                Line::default(),
+                Line::from(Span::styled("──────", Style::new().dark_gray())),
+            ])
+        );
+    }
 }
@@ -0,0 +1,299 @@
+//! Code lines wider than the available width.
+//!
+//! With [`CodeOverflow::Wrap`], a long line of code is split into rows that fit, breaking at the
+//! last space that fits when there is one. Each continuation row repeats the line's
+//! indentation and starts with `↪`. With [`CodeOverflow::Truncate`], the line is cut and ends with
+//! `…`. Both markers use [`StyleSheet::code_overflow`]. Widths are display widths, so wide
+//! characters are never split.
+
+use pulldown_cmark::Event;
+use ratatui_core::text::{Line, Span};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::TextWriter;
+use crate::{CodeOverflow, StyleSheet};
+
+/// The marker at the start of a continuation row, after the indentation.
+const WRAP_MARKER: &str = "↪ ";
+/// The marker at the end of a truncated line.
+const TRUNCATE_MARKER: &str = "…";
+
+/// A row of a laid out code block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CodeRow {
+    /// Index of the line of code the row shows, from zero.
+    pub source: usize,
+    /// Whether the row continues the previous row's line.
+    pub continuation: bool,
+}
+
+impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
+where
+    I: Iterator<Item = Event<'a>>,
+    S: StyleSheet,
+{
+    /// Wraps or truncates the lines of code from `first_line` to `limit` columns, according to
+    /// [`Options::code_overflow`](crate::Options::code_overflow).
+    ///
+    /// Code starts after `prefix_count` prefix spans, which continuation rows repeat. Returns the
+    /// rows that replace the lines, which is one row per line when nothing overflows.
+    pub fn fit_code_lines(
+        &mut self,
+        first_line: usize,
+        prefix_count: usize,
+        limit: Option<usize>,
+    ) -> Vec<CodeRow> {
+        let lines = self.text.lines.split_off(first_line);
+        let marker_style = self.styles.code_overflow();
+        let mut rows = vec![];
+        for (source, mut line) in lines.into_iter().enumerate() {
+            let code = line.spans.split_off(prefix_count);
+            let width = code.iter().map(Span::width).sum::<usize>();
+            let limit = match limit {
+                Some(limit) if width > limit && self.code_overflow != CodeOverflow::Unwrapped => {
+                    limit.max(1)
+                }
+                _ => {
+                    line.spans.extend(code);
+                    self.text.lines.push(line);
+                    rows.push(CodeRow {
+                        source,
+                        continuation: false,
+                    });
+                    continue;
+                }
+            };
+            let prefix = line.spans;
+            let mut line = Line {
+                spans: vec![],
+                ..line
+            };
+
+            if self.code_overflow == CodeOverflow::Truncate {
+                let kept_width = limit - TRUNCATE_MARKER.width().min(limit);
+                let (mut kept, _) = split_spans(code, kept_width, false);
+                // Only the first character can overflow, when it is wider than the room left.
+                if kept.iter().map(Span::width).sum::<usize>() > kept_width {
+                    kept.clear();
+                }
+                kept.push(Span::styled(TRUNCATE_MARKER, marker_style));
+                line.spans = prefix.iter().cloned().chain(kept).collect();
+                self.text.lines.push(line);
+                rows.push(CodeRow {
+                    source,
+                    continuation: false,
+                });
+                continue;
+            }
+
+            let text: String = code.iter().map(|span| span.content.as_ref()).collect();
+            let mut indent = text.len() - text.trim_start_matches(' ').len();
+            if indent + WRAP_MARKER.width() > limit / 2 {
+                indent = 0;
+            }
+            let row_limit = limit.saturating_sub(indent + WRAP_MARKER.width()).max(1);
+            let first_row = rows.len();
+            let mut rest = code;
+            while !rest.is_empty() {
+                let continuation = rows.len() > first_row;
+                let width = if continuation { row_limit } else { limit };
+                let (row, remainder) = split_spans(rest, width, true);
+                rest = remainder;
+                // Rows of only whitespace, such as the indentation of a narrow line, are dropped.
+                if row.iter().all(|span| span.content.trim().is_empty()) {
+                    continue;
+                }
+                let marker = continuation.then(|| {
+                    [
+                        Span::raw(" ".repeat(indent)),
+                        Span::styled(WRAP_MARKER, marker_style),
+                    ]
+                });
+                line.spans = prefix
+                    .iter()
+                    .cloned()
+                    .chain(marker.into_iter().flatten())
+                    .chain(row)
+                    .collect();
+                self.text.lines.push(line.clone());
+                rows.push(CodeRow {
+                    source,
+                    continuation,
+                });
+            }
+            if rows.len() == first_row {
+                line.spans = prefix;
+                self.text.lines.push(line);
+                rows.push(CodeRow {
+                    source,
+                    continuation: false,
+                });
+            }
+        }
+        rows
+    }
+}
+
+/// Splits `spans` into the spans that fit in `width` columns and the rest.
+///
+/// With `at_space`, the split moves back to the last space that fits, unless that would leave
+/// only indentation, and the spaces at the split are dropped. At least one
+/// character is kept, so splitting always progresses.
+fn split_spans(
+    spans: Vec<Span<'_>>,
+    width: usize,
+    at_space: bool,
+) -> (Vec<Span<'static>>, Vec<Span<'static>>) {
+    let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+    let mut end = 0;
+    let mut column = 0;
+    for (index, c) in text.char_indices() {
+        column += c.width().unwrap_or_default();
+        if column > width && index > 0 {
+            break;
+        }
+        end = index + c.len_utf8();
+    }
+    let mut resume = end;
+    if at_space {
+        if text[end..].starts_with(' ') {
+            resume = text.len() - text[end..].trim_start_matches(' ').len();
+        } else if end < text.len() {
+            let indent = text.len() - text.trim_start_matches(' ').len();
+            if let Some(space) = text[..end].rfind(' ').filter(|&space| space > indent) {
+                end = space;
+                resume = text.len() - text[space..].trim_start_matches(' ').len();
+            }
+        }
+    }
+
+    let mut kept = vec![];
+    let mut rest = vec![];
+    let mut start = 0;
+    for span in spans {
+        let content = span.content.as_ref();
+        let range = start..start + content.len();
+        start = range.end;
+        if range.start < end {
+            let head = &content[..end.min(range.end) - range.start];
+            kept.push(Span::styled(head.to_owned(), span.style));
+        }
+        if range.end > resume {
+            let tail = &content[resume.max(range.start) - range.start..];
+            rest.push(Span::styled(tail.to_owned(), span.style));
+        }
+    }
+    (kept, rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::renderer::test_support::{with_tracing, DefaultGuard};
+    use crate::renderer::*;
+    use crate::{CodeBlockBackground, CodeLineNumbers, CodeOverflow, Options};
+
+    #[rstest]
+    fn wrapped_rows_keep_indentation(_with_tracing: DefaultGuard) {
+        let markdown = "```\nif ready {\n    call(first, second, third);\n}\n```";
+        let options = Options::default()
+            .width(20)
+            .code_overflow(CodeOverflow::Wrap);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(
+            text.to_string(),
+            indoc! {"
+                ```
+                if ready {
+                    call(first,
+                    ↪ second,
+                    ↪ third);
+                }
+                ```"}
+        );
+        assert_eq!(
+            text.lines[3].spans[1],
+            Span::styled("↪ ", Style::new().dim())
+        );
+    }
+
+    #[rstest]
+    fn words_longer_than_the_width_break_anywhere(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .width(6)
+            .code_overflow(CodeOverflow::Wrap);
+        let text = from_str_with_options("```\nabcdefghij\n```", &options);
+
+        assert_eq!(text.to_string(), "```\nabcdef\n↪ ghij\n```");
+    }
+
+    #[rstest]
+    fn wrapped_rows_repeat_prefixes_and_share_line_numbers(_with_tracing: DefaultGuard) {
+        let markdown = "> ```\n> one two three\n> ```";
+        let options = Options::default()
+            .width(14)
+            .code_overflow(CodeOverflow::Wrap)
+            .code_line_numbers(CodeLineNumbers::Shown)
+            .code_block_background(CodeBlockBackground::Panel)
+            .code_block_padding(0);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(text.lines[1].to_string(), "> 1 │ one two ");
+        assert_eq!(text.lines[2].to_string(), ">   │ ↪ three ");
+        assert_eq!(text.lines[3].to_string(), "> ```");
+    }
+
+    #[rstest]
+    fn truncated_lines_end_with_ellipsis(_with_tracing: DefaultGuard) {
+        let markdown = "```\nshort\nlet 名前 = value;\n```";
+        let options = Options::default()
+            .width(8)
+            .code_overflow(CodeOverflow::Truncate);
+        let text = from_str_with_options(markdown, &options);
+
+        assert_eq!(text.to_string(), "```\nshort\nlet 名…\n```");
+        assert_eq!(text.lines[2].width(), 7);
+    }
+
+    #[rstest]
+    #[case::one_column("abcd", 1, "…")]
+    #[case::wide_character("名前", 2, "…")]
+    #[case::narrow("abcd", 3, "ab…")]
+    fn truncated_lines_fit_the_limit(
+        _with_tracing: DefaultGuard,
+        #[case] code: &str,
+        #[case] width: u16,
+        #[case] expected: &str,
+    ) {
+        let markdown = format!("```\n{code}\n```");
+        let options = Options::default()
+            .width(width)
+            .code_overflow(CodeOverflow::Truncate);
+        let text = from_str_with_options(&markdown, &options);
+
+        assert_eq!(text.lines[1].to_string(), expected);
+        assert!(text.lines[1].width() <= usize::from(width));
+    }
+
+    #[rstest]
+    fn wrapping_skips_rows_of_only_whitespace(_with_tracing: DefaultGuard) {
+        let options = Options::default()
+            .width(3)
+            .code_overflow(CodeOverflow::Wrap);
+        let text = from_str_with_options("```\n    abc\n```", &options);
+
+        assert_eq!(text.to_string(), "```\nabc\n```");
+    }
+
+    #[rstest]
+    fn long_lines_are_whole_by_default(_with_tracing: DefaultGuard) {
+        let markdown = "```\nlet total = first + second;\n```";
+        let text = from_str_with_options(markdown, &Options::default().width(12));
+
+        assert_eq!(text.lines[1].to_string(), "let total = first + second;");
+    }
+}
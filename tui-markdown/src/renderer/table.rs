@@ -12,13 +12,7 @@ use ratatui_core::style::Style;
 use ratatui_core::text::{Line, Span};
 
 use super::TextWriter;
-use crate::StyleSheet;
-
-const HORIZONTAL_BORDER: char = '─';
-const VERTICAL_BORDER: &str = "│";
-const TOP_BORDER: BorderGlyphs = BorderGlyphs::new('┌', '┬', '┐');
-const HEADER_SEPARATOR: BorderGlyphs = BorderGlyphs::new('├', '┼', '┤');
-const BOTTOM_BORDER: BorderGlyphs = BorderGlyphs::new('└', '┴', '┘');
+use crate::{StyleSheet, TableBorders};
 
 impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
 where
@@ -60,7 +54,14 @@ where
     pub fn end_table(&mut self) {
         if let Some(builder) = self.table_builder.take() {
             let lines = builder.render(&self.styles);
+            let line_count = lines.len();
             self.push_table_lines(lines);
+            // A table that starts on a list marker line adds one line fewer, so this is the index
+            // of the table's first line either way.
+            let first_line = self.text.lines.len().saturating_sub(line_count);
+            for (footnote, offset) in std::mem::take(&mut self.table_footnote_references) {
+                self.add_footnote_reference(footnote, first_line + offset);
+            }
             self.needs_newline = true;
         }
     }
@@ -136,6 +137,11 @@ impl<'a> TableBuilder<'a> {
         self.current_cell.push(span);
     }
 
+    /// Returns the display width of the text already pushed to the cell being built.
+    pub fn current_cell_width(&self) -> usize {
+        self.current_cell.width()
+    }
+
     pub fn finish_cell(&mut self) {
         let cell = std::mem::take(&mut self.current_cell);
         self.current_row.cells.push(cell);
@@ -149,6 +155,17 @@ impl<'a> TableBuilder<'a> {
         self.rows.push(std::mem::take(&mut self.current_row));
     }
 
+    /// Returns the offset of the row being built among the rendered table lines.
+    pub fn current_line(&self) -> usize {
+        if self.header.cells.is_empty() {
+            // The header follows the top border.
+            1
+        } else {
+            // Body rows follow the top border, header, and header separator.
+            3 + self.rows.len()
+        }
+    }
+
     pub fn render<S: StyleSheet>(self, styles: &S) -> Vec<Line<'a>> {
         let column_count = self.column_count();
         if column_count == 0 {
@@ -157,15 +174,16 @@ impl<'a> TableBuilder<'a> {
 
         let column_widths = self.column_widths(column_count);
         let border_style = styles.table_border();
+        let borders = styles.table_borders();
 
-        let top_border = TOP_BORDER.render(&column_widths, border_style);
+        let top_border = BorderGlyphs::top(borders).render(&column_widths, border_style);
         let header = self.header.render(&column_widths, &self.alignments, styles);
-        let header_separator = HEADER_SEPARATOR.render(&column_widths, border_style);
+        let header_separator = BorderGlyphs::middle(borders).render(&column_widths, border_style);
         let body = self
             .rows
             .iter()
             .map(|row| row.render(&column_widths, &self.alignments, styles));
-        let bottom_border = BOTTOM_BORDER.render(&column_widths, border_style);
+        let bottom_border = BorderGlyphs::bottom(borders).render(&column_widths, border_style);
 
         let mut lines = vec![top_border, header, header_separator];
         lines.extend(body);
@@ -217,7 +235,7 @@ impl<'a> TableHeader<'a> {
             column_widths,
             alignments,
             styles.table_header(),
-            styles.table_border(),
+            styles,
         )
     }
 }
@@ -239,7 +257,7 @@ impl<'a> TableRow<'a> {
             column_widths,
             alignments,
             styles.table_cell(),
-            styles.table_border(),
+            styles,
         )
     }
 }
@@ -279,17 +297,37 @@ impl<'a> TableCell<'a> {
 
 #[derive(Clone, Copy)]
 struct BorderGlyphs {
+    horizontal: char,
     left: char,
     intersection: char,
     right: char,
 }
 
 impl BorderGlyphs {
-    const fn new(left: char, intersection: char, right: char) -> Self {
+    const fn top(borders: TableBorders) -> Self {
+        Self {
+            horizontal: borders.horizontal,
+            left: borders.top_left,
+            intersection: borders.top_junction,
+            right: borders.top_right,
+        }
+    }
+
+    const fn middle(borders: TableBorders) -> Self {
+        Self {
+            horizontal: borders.horizontal,
+            left: borders.middle_left,
+            intersection: borders.middle_junction,
+            right: borders.middle_right,
+        }
+    }
+
+    const fn bottom(borders: TableBorders) -> Self {
         Self {
-            left,
-            intersection,
-            right,
+            horizontal: borders.horizontal,
+            left: borders.bottom_left,
+            intersection: borders.bottom_junction,
+            right: borders.bottom_right,
         }
     }
 
@@ -298,7 +336,7 @@ impl BorderGlyphs {
         border.push(self.left);
         for (index, width) in column_widths.iter().enumerate() {
             for _ in 0..(width + 2) {
-                border.push(HORIZONTAL_BORDER);
+                border.push(self.horizontal);
             }
             if index + 1 < column_widths.len() {
                 border.push(self.intersection);
@@ -309,14 +347,16 @@ impl BorderGlyphs {
     }
 }
 
-fn render_line<'a>(
+fn render_line<'a, S: StyleSheet>(
     cells: &[TableCell<'a>],
     column_widths: &[usize],
     alignments: &[Alignment],
     content_style: Style,
-    border_style: Style,
+    styles: &S,
 ) -> Line<'a> {
-    let mut spans = vec![Span::styled(VERTICAL_BORDER, border_style)];
+    let border_style = styles.table_border();
+    let vertical_border = styles.table_borders().vertical.to_string();
+    let mut spans = vec![Span::styled(vertical_border.clone(), border_style)];
     let empty_cell = TableCell::default();
     for (column_index, &column_width) in column_widths.iter().enumerate() {
         let cell = cells.get(column_index).unwrap_or(&empty_cell);
@@ -325,7 +365,7 @@ fn render_line<'a>(
             .copied()
             .unwrap_or(Alignment::None);
         spans.extend(cell.render_spans(column_width, alignment, content_style));
-        spans.push(Span::styled(VERTICAL_BORDER, border_style));
+        spans.push(Span::styled(vertical_border.clone(), border_style));
     }
     Line::from(spans)
 }
@@ -595,6 +635,38 @@ mod tests {
         );
     }
 
+    #[derive(Clone)]
+    struct AsciiTableStyleSheet;
+
+    impl StyleSheet for AsciiTableStyleSheet {
+        fn table_borders(&self) -> TableBorders {
+            TableBorders::ASCII
+        }
+    }
+
+    #[test]
+    fn style_sheet_selects_border_glyphs() {
+        let options = Options::new(AsciiTableStyleSheet);
+        let text = from_str_with_options(
+            indoc! {"
+                | A | B |
+                |---|---|
+                | a | b |
+            "},
+            &options,
+        );
+
+        assert_eq!(
+            text.to_string(),
+            indoc! {"
+                +---+---+
+                | A | B |
+                +---+---+
+                | a | b |
+                +---+---+"}
+        );
+    }
+
     #[test]
     fn custom_cell_style_composes_with_inline_formatting() {
         let options = Options::new(CustomTableStyleSheet);
@@ -0,0 +1,205 @@
+//! Tab expansion and visible whitespace.
+//!
+//! Tabs become spaces up to the next multiple of [`Options::tab_width`](crate::Options::tab_width)
+//! columns, so the width of every span matches what the terminal displays and wrapping and
+//! alignment stay correct. Columns are counted from the start of a line's content after its line
+//! prefixes, or from the start of the text in a table cell. Code block lines are expanded when the
+//! block ends, after highlighting, and [`CodeWhitespace::Visible`] then also marks their tabs and
+//! trailing spaces.
+
+use pulldown_cmark::{CowStr, Event};
+use ratatui_core::style::Style;
+use ratatui_core::text::Span;
+use unicode_width::UnicodeWidthChar;
+
+use super::TextWriter;
+use crate::{CodeWhitespace, StyleSheet};
+
+/// The marker at the start of an expanded tab.
+const TAB_MARKER: &str = "→";
+/// The marker that replaces a trailing space.
+const SPACE_MARKER: &str = "·";
+
+impl<'a, 'theme, I, S> TextWriter<'a, 'theme, I, S>
+where
+    I: Iterator<Item = Event<'a>>,
+    S: StyleSheet,
+{
+    /// Expands the tabs in `text`, which continues the current line.
+    ///
+    /// Text in code blocks is returned unchanged, because whole lines are expanded when the block
+    /// ends.
+    pub fn expand_tabs(&self, text: CowStr<'a>) -> CowStr<'a> {
+        if self.code_block.is_some() || !text.contains('\t') {
+            return text;
+        }
+        let column = if let Some(table_builder) = &self.table_builder {
+            table_builder.current_cell_width()
+        } else {
+            let has_prefixes = !self.line_prefixes.is_empty();
+            let prefix_width = self.line_prefixes.iter().map(Span::width).sum::<usize>()
+                + usize::from(has_prefixes);
+            let line_width = self.text.lines.last().map_or(0, |line| line.width());
+            line_width.saturating_sub(prefix_width)
+        };
+        let mut expanded = String::new();
+        let mut column = column;
+        for c in text.chars() {
+            if c == '\t' {
+                let spaces = self.tab_stop_distance(column);
+                expanded.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            } else {
+                expanded.push(c);
+                column += c.width().unwrap_or_default();
+            }
+        }
+        CowStr::from(expanded)
+    }
+
+    /// Expands the tabs in the lines of a finished code block, marking them and trailing spaces
+    /// with [`StyleSheet::code_whitespace`] when whitespace is visible.
+    ///
+    /// Lines start at `first_line`, and their code starts after `prefix_count` prefix spans.
+    pub fn expand_code_whitespace(&mut self, first_line: usize, prefix_count: usize) {
+        let marker = (self.code_whitespace == CodeWhitespace::Visible)
+            .then(|| self.styles.code_whitespace());
+        for index in first_line..self.text.lines.len() {
+            let content: String = self.text.lines[index].spans[prefix_count..]
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect();
+            let trailing = if marker.is_some() {
+                content.trim_end_matches([' ', '\t']).len()
+            } else {
+                content.len()
+            };
+            if trailing == content.len() && !content.contains('\t') {
+                continue;
+            }
+
+            let spans = self.text.lines[index].spans.split_off(prefix_count);
+            let mut expanded = vec![];
+            let (mut offset, mut column) = (0, 0);
+            for span in spans {
+                for c in span.content.chars() {
+                    let is_trailing = offset >= trailing;
+                    offset += c.len_utf8();
+                    match (c, marker) {
+                        ('\t', Some(marker)) => {
+                            let spaces = self.tab_stop_distance(column);
+                            let style = span.style.patch(marker);
+                            push_text(&mut expanded, TAB_MARKER, style);
+                            push_text(&mut expanded, &" ".repeat(spaces - 1), style);
+                            column += spaces;
+                        }
+                        ('\t', None) => {
+                            let spaces = self.tab_stop_distance(column);
+                            push_text(&mut expanded, &" ".repeat(spaces), span.style);
+                            column += spaces;
+                        }
+                        (' ', Some(marker)) if is_trailing => {
+                            push_text(&mut expanded, SPACE_MARKER, span.style.patch(marker));
+                            column += 1;
+                        }
+                        _ => {
+                            push_text(&mut expanded, c.encode_utf8(&mut [0; 4]), span.style);
+                            column += c.width().unwrap_or_default();
+                        }
+                    }
+                }
+            }
+            self.text.lines[index].spans.extend(expanded);
+        }
+    }
+
+    /// Returns the columns from `column` to the next tab stop.
+    fn tab_stop_distance(&self, column: usize) -> usize {
+        let tab_width = usize::from(self.tab_width.max(1));
+        tab_width - column % tab_width
+    }
+}
+
+/// Appends `text` to the last span when it has the same style, or as a new span.
+fn push_text(spans: &mut Vec<Span<'_>>, text: &str, style: Style) {
+    match spans.last_mut() {
+        Some(last) if last.style == style => last.content.to_mut().push_str(text),
+        _ => spans.push(Span::styled(text.to_owned(), style)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::renderer::test_support::{with_tracing, DefaultGuard};
+    use crate::renderer::*;
+    use crate::{CodeWhitespace, Options};
+
+    #[rstest]
+    fn tabs_in_code_expand_to_tab_stops(_with_tracing: DefaultGuard) {
+        let markdown = "```\nfn main() {\n\tif x {\n\t\tgo();\n\t}\n}\nab\tc\n```";
+        let text = from_str(markdown);
+
+        assert_eq!(
+            text.to_string(),
+            indoc! {"
+                ```
+                fn main() {
+                    if x {
+                        go();
+                    }
+                }
+                ab  c
+                ```"}
+        );
+    }
+
+    #[rstest]
+    fn tab_width_is_configurable(_with_tracing: DefaultGuard) {
+        let options = Options::default().tab_width(2);
+        let text = from_str_with_options("```\n\tx\n```\n\n-\ta\tb", &options);
+
+        assert_eq!(text.lines[1].to_string(), "  x");
+        assert_eq!(text.lines[4].to_string(), "- a b");
+    }
+
+    #[rstest]
+    fn tabs_in_text_and_inline_code_expand(_with_tracing: DefaultGuard) {
+        let text = from_str("> a\tb `c\td`");
+
+        assert_eq!(text.to_string(), "> a   b c d");
+        assert!(text.lines[0]
+            .spans
+            .iter()
+            .all(|span| !span.content.contains('\t')));
+    }
+
+    #[rstest]
+    fn tabs_in_table_cells_expand(_with_tracing: DefaultGuard) {
+        let text = from_str("| a\tb |\n| --- |\n| c |");
+
+        assert_eq!(text.lines[1].to_string(), "│ a   b │");
+    }
+
+    #[rstest]
+    fn tabs_in_table_cells_count_from_earlier_cell_text(_with_tracing: DefaultGuard) {
+        let text = from_str("| abcde *f*\tg |\n| --- |\n| c |");
+
+        assert_eq!(text.lines[1].to_string(), "│ abcde f g │");
+    }
+
+    #[rstest]
+    fn visible_whitespace_marks_tabs_and_trailing_spaces(_with_tracing: DefaultGuard) {
+        let options = Options::default().code_whitespace(CodeWhitespace::Visible);
+        let text = from_str_with_options("```\n\tx = 1;  \n  y\t\n```", &options);
+        let marker = Style::new().dark_gray();
+
+        assert_eq!(text.lines[1].to_string(), "→   x = 1;··");
+        assert_eq!(text.lines[1].spans[0], Span::styled("→   ", marker));
+        assert_eq!(text.lines[2].to_string(), "  y→");
+        assert_eq!(text.lines[2].spans[1], Span::styled("→", marker));
+    }
+}
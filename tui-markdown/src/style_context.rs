@@ -0,0 +1,179 @@
+//! Element ancestry for context-aware styling.
+//!
+//! [`StyleContext`] describes the blocks that enclose an inline element while it is rendered. The
+//! renderer passes it to [`crate::StyleSheet::in_context`], whose style is patched over the
+//! element's usual style. This lets a style sheet, for example, color links differently inside
+//! warning alerts or bold inline code inside headings.
+
+use crate::AlertKind;
+
+/// A block-level Markdown element that can enclose inline content.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BlockKind {
+    /// A paragraph.
+    Paragraph,
+    /// A heading with its one-based level.
+    Heading(u8),
+    /// A plain blockquote.
+    BlockQuote,
+    /// A GitHub Flavored Markdown alert.
+    Alert(AlertKind),
+    /// A list, ordered or not.
+    List {
+        /// Whether the list is numbered.
+        ordered: bool,
+    },
+    /// A list item.
+    ListItem,
+    /// A table.
+    Table,
+    /// The header row of a table.
+    TableHead,
+    /// A table cell, in the header or the body.
+    TableCell,
+    /// A footnote definition.
+    FootnoteDefinition,
+    /// A definition list.
+    DefinitionList,
+    /// A term in a definition list.
+    DefinitionTerm,
+    /// A description in a definition list.
+    DefinitionDescription,
+}
+
+/// An inline element that a style sheet can style by context.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InlineElement {
+    /// Emphasized text.
+    Emphasis,
+    /// Strong text.
+    Strong,
+    /// Struck-through text.
+    Strikethrough,
+    /// Subscript text.
+    Subscript,
+    /// Superscript text.
+    Superscript,
+    /// Inline code.
+    Code,
+    /// Link text and the destination shown after it.
+    Link,
+}
+
+/// The blocks enclosing an inline element, from the outermost to the innermost.
+///
+/// # Example
+///
+/// ```
+/// use ratatui_core::style::{Style, Stylize};
+/// use tui_markdown::{
+///     from_str_with_options, AlertKind, InlineElement, Options, StyleContext, StyleSheet,
+/// };
+///
+/// #[derive(Clone, Copy)]
+/// struct WarningLinks;
+///
+/// impl StyleSheet for WarningLinks {
+///     fn in_context(&self, element: InlineElement, context: &StyleContext<'_>) -> Style {
+///         match (element, context.alert()) {
+///             (InlineElement::Link, Some(AlertKind::Warning)) => Style::new().red(),
+///             _ => Style::default(),
+///         }
+///     }
+/// }
+///
+/// let options = Options::new(WarningLinks);
+/// let text = from_str_with_options("> [!WARNING]\n> See [docs](https://example.com)", &options);
+///
+/// let link = text.iter().flatten().find(|span| span.content == "docs").unwrap();
+///
+/// assert_eq!(link.style, Style::new().blue().underlined().red());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StyleContext<'a> {
+    blocks: &'a [BlockKind],
+}
+
+impl<'a> StyleContext<'a> {
+    pub(crate) fn new(blocks: &'a [BlockKind]) -> Self {
+        Self { blocks }
+    }
+
+    /// Returns the enclosing blocks, from the outermost to the innermost.
+    #[must_use]
+    pub fn blocks(&self) -> &'a [BlockKind] {
+        self.blocks
+    }
+
+    /// Returns the number of enclosing blocks.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns whether `block` encloses the element.
+    #[must_use]
+    pub fn contains(&self, block: BlockKind) -> bool {
+        self.blocks.contains(&block)
+    }
+
+    /// Returns the kind of the innermost enclosing alert.
+    #[must_use]
+    pub fn alert(&self) -> Option<AlertKind> {
+        self.blocks.iter().rev().find_map(|block| match block {
+            BlockKind::Alert(kind) => Some(*kind),
+            _ => None,
+        })
+    }
+
+    /// Returns the level of the enclosing heading.
+    #[must_use]
+    pub fn heading_level(&self) -> Option<u8> {
+        self.blocks.iter().rev().find_map(|block| match block {
+            BlockKind::Heading(level) => Some(*level),
+            _ => None,
+        })
+    }
+
+    /// Returns the one-based nesting depth of the enclosing lists, or zero outside lists.
+    #[must_use]
+    pub fn list_level(&self) -> usize {
+        self.blocks
+            .iter()
+            .filter(|block| matches!(block, BlockKind::List { .. }))
+            .count()
+    }
+
+    /// Returns whether the element is in the header row of a table.
+    #[must_use]
+    pub fn in_table_header(&self) -> bool {
+        self.contains(BlockKind::TableHead)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_enclosing_blocks() {
+        let blocks = [
+            BlockKind::Alert(AlertKind::Note),
+            BlockKind::List { ordered: false },
+            BlockKind::ListItem,
+            BlockKind::List { ordered: true },
+            BlockKind::ListItem,
+            BlockKind::Paragraph,
+        ];
+        let context = StyleContext::new(&blocks);
+
+        assert_eq!(context.depth(), 6);
+        assert_eq!(context.alert(), Some(AlertKind::Note));
+        assert_eq!(context.list_level(), 2);
+        assert_eq!(context.heading_level(), None);
+        assert!(context.contains(BlockKind::Paragraph));
+        assert!(!context.in_table_header());
+    }
+}
@@ -1,13 +1,16 @@
 //! Style sheet abstraction for tui-markdown.
 //!
-//! [`StyleSheet`] supplies the styles and alert text used while Markdown events are rendered.
+//! [`StyleSheet`] supplies the styles, symbols, and alert text used while Markdown events are
+//! rendered.
 //! Every choice has a default. Implementations only need to override the styles or text they want
 //! to customize.
 //!
 //! [`DefaultStyleSheet`] is used by [`crate::Options::default`]. Applications can pass another
 //! implementation to [`crate::Options::new`].
 
-use ratatui_core::style::Style;
+use ratatui_core::style::{Color, Style};
+
+use crate::{CodeBlockInfo, DetailsState, InlineElement, StyleContext};
 
 /// The kind of a GitHub Flavored Markdown alert.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -37,6 +40,100 @@ impl AlertKind {
     }
 }
 
+/// The numbering scheme of an ordered list.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ListNumbering {
+    /// `1`, `2`, `3`, …
+    #[default]
+    Decimal,
+    /// `a`, `b`, … `z`, `aa`, …
+    LowerAlpha,
+    /// `A`, `B`, … `Z`, `AA`, …
+    UpperAlpha,
+    /// `i`, `ii`, `iii`, …
+    LowerRoman,
+    /// `I`, `II`, `III`, …
+    UpperRoman,
+}
+
+impl ListNumbering {
+    /// Formats `number` in this scheme.
+    ///
+    /// Alphabetic and Roman schemes cannot represent `0`, and Roman numerals stop at `3999`. Those
+    /// numbers are formatted as decimals instead.
+    pub fn format(self, number: u64) -> String {
+        match self {
+            Self::LowerAlpha if number > 0 => alphabetic(number),
+            Self::UpperAlpha if number > 0 => alphabetic(number).to_ascii_uppercase(),
+            Self::LowerRoman if (1..4000).contains(&number) => roman(number),
+            Self::UpperRoman if (1..4000).contains(&number) => roman(number).to_ascii_uppercase(),
+            _ => number.to_string(),
+        }
+    }
+}
+
+fn alphabetic(mut number: u64) -> String {
+    let mut letters = Vec::new();
+    while number > 0 {
+        number -= 1;
+        letters.push(b'a' + (number % 26) as u8);
+        number /= 26;
+    }
+    letters
+        .iter()
+        .rev()
+        .map(|&letter| char::from(letter))
+        .collect()
+}
+
+fn roman(mut number: u64) -> String {
+    const NUMERALS: [(u64, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut numeral = String::new();
+    for (value, digits) in NUMERALS {
+        while number >= value {
+            numeral.push_str(digits);
+            number -= value;
+        }
+    }
+    numeral
+}
+
+/// The punctuation after the number of an ordered list item.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ListDelimiter {
+    /// `1.`
+    #[default]
+    Period,
+    /// `1)`
+    Parenthesis,
+}
+
+impl ListDelimiter {
+    /// The delimiter text.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Period => ".",
+            Self::Parenthesis => ")",
+        }
+    }
+}
+
 /// Visual styles and symbols consumed by the renderer.
 ///
 /// Every method has a default, which [`DefaultStyleSheet`] uses unchanged. Implementations only
@@ -71,16 +168,81 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
         Style::new().green()
     }
 
+    /// Style for the rules and box borders of decorated headings.
+    ///
+    /// The level is one-based (`1` for `# H1`, …). See
+    /// [`HeadingDecoration`](crate::HeadingDecoration).
+    fn heading_decoration(&self, _level: u8) -> Style {
+        Style::new().cyan()
+    }
+
     /// Style for heading attribute metadata appended to the heading text.
     fn heading_meta(&self) -> Style {
         Style::new().dim()
     }
 
+    /// Style for body text, such as paragraphs, list items, blockquotes, and definition-list
+    /// descriptions.
+    ///
+    /// Headings, table cells, code blocks, and metadata blocks are not body text. Inline formatting
+    /// such as emphasis and links is patched over this style.
+    fn paragraph(&self) -> Style {
+        Style::default()
+    }
+
+    /// Style for emphasized text (`*text*` and `<em>`).
+    fn emphasis(&self) -> Style {
+        Style::new().italic()
+    }
+
+    /// Style for strong text (`**text**` and `<strong>`).
+    fn strong(&self) -> Style {
+        Style::new().bold()
+    }
+
+    /// Style for struck-through text (`~~text~~` and `<del>`).
+    fn strikethrough(&self) -> Style {
+        Style::new().crossed_out()
+    }
+
+    /// Style for subscript text (`~text~` and `<sub>`).
+    fn subscript(&self) -> Style {
+        Style::new().dim().italic()
+    }
+
+    /// Style for superscript text (`^text^` and `<sup>`).
+    fn superscript(&self) -> Style {
+        Style::new().dim().italic()
+    }
+
+    /// Line displayed for a horizontal rule (thematic break).
+    fn horizontal_rule(&self) -> &str {
+        "---"
+    }
+
+    /// Style for horizontal rules.
+    fn horizontal_rule_style(&self) -> Style {
+        Style::default()
+    }
+
+    /// Additional style for an inline element in its enclosing blocks.
+    ///
+    /// The returned style is patched over the element's usual style, such as [`Self::link`] for
+    /// links. See [`StyleContext`] for the available ancestry.
+    fn in_context(&self, _element: InlineElement, _context: &StyleContext<'_>) -> Style {
+        Style::default()
+    }
+
     /// Style for metadata blocks (front matter).
     fn metadata_block(&self) -> Style {
         Style::new().light_yellow()
     }
 
+    /// Delimiter displayed above and below a metadata block.
+    fn metadata_delimiter(&self) -> &str {
+        "---"
+    }
+
     /// Marker displayed before a Markdown heading.
     ///
     /// `level` is one-based (`1` for an H1, …). The renderer adds one separating space after a
@@ -98,17 +260,184 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
 
     /// Delimiter displayed above and below block code.
     ///
-    /// The renderer appends fenced-code info to the opening delimiter. Return an empty string to
-    /// omit both delimiter lines.
+    /// The renderer appends the language of fenced code to the opening delimiter. Return an empty
+    /// string to omit both delimiter lines.
     fn code_block_fence(&self) -> &str {
         "```"
     }
 
+    /// Title displayed on its own line between the opening fence and the code.
+    ///
+    /// The default is the [`CodeBlockInfo::title`] attribute, such as `title="src/main.rs"`.
+    /// Return `None` to omit the line, or derive a header from other attributes.
+    fn code_block_title<'i>(&'i self, info: &'i CodeBlockInfo) -> Option<&'i str> {
+        info.title()
+    }
+
+    /// Style of the code block title line.
+    fn code_block_title_style(&self) -> Style {
+        Style::new().bold()
+    }
+
+    /// Style of line numbers in the code block gutter and of the separator after them.
+    fn code_line_number(&self) -> Style {
+        Style::new().dim()
+    }
+
+    /// Separator displayed between the code block gutter and the code.
+    ///
+    /// The renderer adds one space after each line number, before the separator.
+    fn code_line_number_separator(&self) -> &str {
+        "│ "
+    }
+
+    /// Style patched over code block lines emphasized by the info string, such as `{3,5-7}`.
+    fn code_highlighted_line(&self) -> Style {
+        Style::new().on_dark_gray()
+    }
+
+    /// Style patched over the tab and trailing space markers of [`CodeWhitespace::Visible`].
+    ///
+    /// [`CodeWhitespace::Visible`]: crate::CodeWhitespace::Visible
+    fn code_whitespace(&self) -> Style {
+        Style::new().dark_gray()
+    }
+
+    /// Style patched over the `↪` and `…` markers of wrapped and truncated code lines.
+    fn code_overflow(&self) -> Style {
+        Style::new().dim()
+    }
+
+    /// Style of the prompts, such as `$ `, before commands in shell session code blocks.
+    ///
+    /// Shell sessions are fenced as `console`, `shell-session`, or `terminal`. Their commands are
+    /// highlighted as Bash when syntax highlighting is enabled.
+    fn shell_prompt(&self) -> Style {
+        Style::new().dim()
+    }
+
+    /// Style of the output lines in shell session code blocks.
+    fn shell_output(&self) -> Style {
+        Style::default()
+    }
+
+    /// Style patched over added lines of `diff` and `patch` code blocks.
+    ///
+    /// Backgrounds extend to the width of the block. Use `diff-rust` and similar languages to also
+    /// highlight the code after each line's marker.
+    fn diff_added(&self) -> Style {
+        Style::new().bg(Color::Indexed(22))
+    }
+
+    /// Style patched over removed lines of `diff` and `patch` code blocks.
+    fn diff_removed(&self) -> Style {
+        Style::new().bg(Color::Indexed(52))
+    }
+
+    /// Style patched over `@@` hunk headers of `diff` and `patch` code blocks.
+    fn diff_hunk_header(&self) -> Style {
+        Style::new().cyan()
+    }
+
+    /// Style patched over the `diff`, `index`, `---`, and `+++` lines naming the changed files.
+    fn diff_file_header(&self) -> Style {
+        Style::new().bold()
+    }
+
+    /// Style for the rows of Mermaid diagrams drawn with [`MermaidDiagrams::Layout`].
+    ///
+    /// [`MermaidDiagrams::Layout`]: crate::MermaidDiagrams::Layout
+    fn mermaid_diagram(&self) -> Style {
+        Style::default()
+    }
+
+    /// Bullet displayed before an unordered list item.
+    ///
+    /// The level is the one-based nesting depth of the list, counting both ordered and unordered
+    /// lists. The renderer adds one separating space after the bullet. Return a different glyph per
+    /// level, such as `•`, `◦`, and `▪`, to distinguish nested lists.
+    fn list_bullet(&self, _level: usize) -> &str {
+        "-"
+    }
+
+    /// Numbering scheme for ordered list items at the one-based nesting level.
+    fn list_numbering(&self, _level: usize) -> ListNumbering {
+        ListNumbering::Decimal
+    }
+
+    /// Delimiter after the numbers of ordered list items at the one-based nesting level.
+    fn list_delimiter(&self, _level: usize) -> ListDelimiter {
+        ListDelimiter::Period
+    }
+
+    /// Checkbox displayed after the list marker of a task item.
+    ///
+    /// The renderer adds one separating space after a non-empty checkbox. Return an empty string to
+    /// omit the checkbox and its separator.
+    fn task_marker(&self, checked: bool) -> &str {
+        if checked {
+            "[x]"
+        } else {
+            "[ ]"
+        }
+    }
+
+    /// Style for the checkbox of a task item.
+    fn task_marker_style(&self, _checked: bool) -> Style {
+        Style::default()
+    }
+
+    /// Style for list bullets and numbers.
+    fn list_marker(&self, ordered: bool) -> Style {
+        if ordered {
+            Style::new().light_blue()
+        } else {
+            Style::default()
+        }
+    }
+
     /// Style for raw HTML blocks and inline HTML tags.
     fn html(&self) -> Style {
         Style::new().dim()
     }
 
+    /// Style for keyboard keys written as `<kbd>` when inline HTML is interpreted.
+    ///
+    /// See [`InlineHtmlMode::Interpret`](crate::InlineHtmlMode::Interpret).
+    fn keyboard_key(&self) -> Style {
+        Style::new().reversed()
+    }
+
+    /// Style for underlined text written as `<u>` or `<ins>` when inline HTML is interpreted.
+    ///
+    /// See [`InlineHtmlMode::Interpret`](crate::InlineHtmlMode::Interpret).
+    fn underline(&self) -> Style {
+        Style::new().underlined()
+    }
+
+    /// Style for highlighted text written as `<mark>` when inline HTML is interpreted.
+    ///
+    /// See [`InlineHtmlMode::Interpret`](crate::InlineHtmlMode::Interpret).
+    fn mark(&self) -> Style {
+        Style::new().black().on_yellow()
+    }
+
+    /// Style for the summary line of a `<details>` element, including its marker.
+    fn details_summary(&self) -> Style {
+        Style::new().bold()
+    }
+
+    /// Disclosure marker displayed before a `<details>` summary.
+    ///
+    /// The renderer adds one separating space after a non-empty marker. Return an empty string to
+    /// omit the marker and its separator.
+    fn details_marker(&self, state: DetailsState) -> &str {
+        match state {
+            DetailsState::Collapsed => "▶",
+            DetailsState::Expanded => "▼",
+        }
+    }
+
     /// Style for inline math (`$...$`).
     fn math_inline(&self) -> Style {
         Style::new().italic().magenta()
@@ -129,6 +458,23 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
         Style::new().dim()
     }
 
+    /// Style patched over [`footnote_ref`](Self::footnote_ref) for a reference such as `[^label]`
+    /// that has no matching definition.
+    ///
+    /// Missing references are only flagged when footnotes are collected or numbered. See
+    /// [`FootnoteLayout`](crate::FootnoteLayout) and
+    /// [`FootnoteNumbering`](crate::FootnoteNumbering).
+    fn footnote_missing(&self) -> Style {
+        Style::new().red()
+    }
+
+    /// Style for the "Footnotes" title of collected footnote definitions.
+    ///
+    /// See [`FootnoteLayout::Collected`](crate::FootnoteLayout::Collected).
+    fn footnote_heading(&self) -> Style {
+        Style::new().bold()
+    }
+
     /// Style for definition list terms.
     fn definition_term(&self) -> Style {
         Style::new().bold()
@@ -142,8 +488,6 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
     ///
     /// The generated icon and label are bold in addition to this base style.
     fn alert(&self, kind: AlertKind) -> Style {
-        use ratatui_core::style::Color;
-
         match kind {
             AlertKind::Note => Style::new().fg(Color::Blue),
             AlertKind::Tip => Style::new().fg(Color::Green),
@@ -191,11 +535,17 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
 
     /// Style for the Unicode box-drawing characters around table cells.
     ///
-    /// This changes the presentation of the borders, not the box-drawing characters themselves.
+    /// This changes the presentation of the borders. [`Self::table_borders`] selects the
+    /// characters themselves.
     fn table_border(&self) -> Style {
         Style::new().dark_gray()
     }
 
+    /// Characters used to draw the borders around table cells.
+    fn table_borders(&self) -> TableBorders {
+        TableBorders::LIGHT
+    }
+
     /// Style for the `[img]` marker and text used to represent Markdown images.
     ///
     /// The default is dim and italic. The renderer patches this over any enclosing inline style.
@@ -204,6 +554,75 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
     }
 }
 
+/// Characters used to draw table borders.
+///
+/// The constants cover common box-drawing sets. Construct a value directly for other glyphs.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TableBorders {
+    /// Horizontal line in the top, header separator, and bottom borders.
+    pub horizontal: char,
+    /// Vertical line at the table edges and between cells.
+    pub vertical: char,
+    /// Top-left corner.
+    pub top_left: char,
+    /// Junction of the top border and a column separator.
+    pub top_junction: char,
+    /// Top-right corner.
+    pub top_right: char,
+    /// Left end of the header separator.
+    pub middle_left: char,
+    /// Junction of the header separator and a column separator.
+    pub middle_junction: char,
+    /// Right end of the header separator.
+    pub middle_right: char,
+    /// Bottom-left corner.
+    pub bottom_left: char,
+    /// Junction of the bottom border and a column separator.
+    pub bottom_junction: char,
+    /// Bottom-right corner.
+    pub bottom_right: char,
+}
+
+impl TableBorders {
+    /// Light box-drawing lines, such as `┌─┬─┐`.
+    pub const LIGHT: Self =
+        Self::box_drawing(['─', '│', '┌', '┬', '┐', '├', '┼', '┤', '└', '┴', '┘']);
+    /// Light lines with rounded corners, such as `╭─┬─╮`.
+    pub const ROUNDED: Self =
+        Self::box_drawing(['─', '│', '╭', '┬', '╮', '├', '┼', '┤', '╰', '┴', '╯']);
+    /// Heavy box-drawing lines, such as `┏━┳━┓`.
+    pub const HEAVY: Self =
+        Self::box_drawing(['━', '┃', '┏', '┳', '┓', '┣', '╋', '┫', '┗', '┻', '┛']);
+    /// Double box-drawing lines, such as `╔═╦═╗`.
+    pub const DOUBLE: Self =
+        Self::box_drawing(['═', '║', '╔', '╦', '╗', '╠', '╬', '╣', '╚', '╩', '╝']);
+    /// ASCII characters, such as `+-+-+`.
+    pub const ASCII: Self =
+        Self::box_drawing(['-', '|', '+', '+', '+', '+', '+', '+', '+', '+', '+']);
+
+    const fn box_drawing(glyphs: [char; 11]) -> Self {
+        Self {
+            horizontal: glyphs[0],
+            vertical: glyphs[1],
+            top_left: glyphs[2],
+            top_junction: glyphs[3],
+            top_right: glyphs[4],
+            middle_left: glyphs[5],
+            middle_junction: glyphs[6],
+            middle_right: glyphs[7],
+            bottom_left: glyphs[8],
+            bottom_junction: glyphs[9],
+            bottom_right: glyphs[10],
+        }
+    }
+}
+
+impl Default for TableBorders {
+    fn default() -> Self {
+        Self::LIGHT
+    }
+}
+
 /// The default style set
 ///
 /// This style sheet will be used by default if the user does not provide their own implementation.
@@ -216,14 +635,40 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
 /// - code: white on black
 /// - link: blue, underlined
 /// - blockquote: green
-/// - metadata block: light yellow
+/// - paragraphs: the surrounding style
+/// - emphasis: italic
+/// - strong text: bold
+/// - strikethrough: crossed out
+/// - subscript and superscript: dim, italic
+/// - horizontal rules: `---`, in the surrounding style
+/// - context-specific styles: none
+/// - metadata block: light yellow, between `---` delimiters
 /// - heading markers: one to six `#` characters
+/// - heading rules and boxes: cyan
 /// - code block fences: three backticks
+/// - code block titles: the `title`, `filename`, or `file` attribute, bold
+/// - code line numbers: dim, followed by `│ `
+/// - emphasized code lines: on dark gray
+/// - visible code whitespace: dark gray
+/// - wrapped and truncated code line markers: dim
+/// - shell session prompts: dim
+/// - shell session output: the surrounding style
+/// - added and removed diff lines: on dark green and dark red, from the 256-color palette
+/// - diff hunk headers: cyan
+/// - diff file headers: bold
+/// - Mermaid diagrams: the surrounding style
+/// - list bullets: `-` at every level, in the surrounding style
+/// - ordered list numbers: light blue decimals followed by `.`
+/// - task checkboxes: `[x]` and `[ ]`, in the surrounding style
 /// - raw HTML: dim
+/// - keyboard keys: reversed
+/// - details summaries: bold, after `▶` when collapsed or `▼` when expanded
 /// - inline math: magenta, italic
 /// - display math: magenta
 /// - footnote references: dim, italic
 /// - footnote definitions: dim
+/// - footnote references without a definition: red, over the reference style
+/// - collected footnotes title: bold
 /// - definition list terms: bold
 /// - definition list descriptions: the surrounding style
 /// - note alerts: blue
@@ -233,7 +678,7 @@ pub trait StyleSheet: Clone + Send + Sync + 'static {
 /// - caution alerts: red
 /// - table headers: bold cyan
 /// - table cells: the surrounding style
-/// - table borders: dark gray
+/// - table borders: dark gray light box-drawing lines
 /// - image fallback text: dim and italic
 #[derive(Clone, Copy, Debug, Default)]
 pub struct DefaultStyleSheet;
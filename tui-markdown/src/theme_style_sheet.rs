@@ -0,0 +1,1021 @@
+//! Style sheets loaded from TOML and JSON theme files.
+//!
+//! [`ThemeStyleSheet`] reads styles, markers, alert text, and table glyphs from a theme file and
+//! implements [`StyleSheet`] with them. [`StyleSheetLoadError`] reports unreadable files and
+//! invalid themes.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use ratatui_core::style::{Color, Modifier, Style};
+use serde_json::{Map, Value};
+
+use crate::{
+    AlertKind, DefaultStyleSheet, DetailsState, ListDelimiter, ListNumbering, StyleSheet,
+    TableBorders,
+};
+
+/// Reads the default style of a theme entry.
+type DefaultStyle = fn(&DefaultStyleSheet) -> Style;
+
+/// Reads the default marker of a theme entry.
+type DefaultMarker = fn(&DefaultStyleSheet) -> &str;
+
+/// Style keys with their [`DefaultStyleSheet`] values.
+const STYLES: &[(&str, DefaultStyle)] = &[
+    ("heading_1", |styles| styles.heading(1)),
+    ("heading_2", |styles| styles.heading(2)),
+    ("heading_3", |styles| styles.heading(3)),
+    ("heading_4", |styles| styles.heading(4)),
+    ("heading_5", |styles| styles.heading(5)),
+    ("heading_6", |styles| styles.heading(6)),
+    ("heading_decoration", |styles| styles.heading_decoration(1)),
+    ("heading_meta", StyleSheet::heading_meta),
+    ("code", StyleSheet::code),
+    ("code_block_title", StyleSheet::code_block_title_style),
+    ("code_line_number", StyleSheet::code_line_number),
+    ("code_highlighted_line", StyleSheet::code_highlighted_line),
+    ("code_whitespace", StyleSheet::code_whitespace),
+    ("code_overflow", StyleSheet::code_overflow),
+    ("shell_prompt", StyleSheet::shell_prompt),
+    ("shell_output", StyleSheet::shell_output),
+    ("diff_added", StyleSheet::diff_added),
+    ("diff_removed", StyleSheet::diff_removed),
+    ("diff_hunk_header", StyleSheet::diff_hunk_header),
+    ("diff_file_header", StyleSheet::diff_file_header),
+    ("mermaid_diagram", StyleSheet::mermaid_diagram),
+    ("link", StyleSheet::link),
+    ("blockquote", StyleSheet::blockquote),
+    ("paragraph", StyleSheet::paragraph),
+    ("emphasis", StyleSheet::emphasis),
+    ("strong", StyleSheet::strong),
+    ("strikethrough", StyleSheet::strikethrough),
+    ("subscript", StyleSheet::subscript),
+    ("superscript", StyleSheet::superscript),
+    ("horizontal_rule", StyleSheet::horizontal_rule_style),
+    ("metadata_block", StyleSheet::metadata_block),
+    ("list_marker", |styles| styles.list_marker(false)),
+    ("ordered_list_marker", |styles| styles.list_marker(true)),
+    ("task_checked", |styles| styles.task_marker_style(true)),
+    ("task_unchecked", |styles| styles.task_marker_style(false)),
+    ("html", StyleSheet::html),
+    ("keyboard_key", StyleSheet::keyboard_key),
+    ("underline", StyleSheet::underline),
+    ("mark", StyleSheet::mark),
+    ("details_summary", StyleSheet::details_summary),
+    ("math_inline", StyleSheet::math_inline),
+    ("math_display", StyleSheet::math_display),
+    ("footnote_ref", StyleSheet::footnote_ref),
+    ("footnote_def", StyleSheet::footnote_def),
+    ("footnote_missing", StyleSheet::footnote_missing),
+    ("footnote_heading", StyleSheet::footnote_heading),
+    ("definition_term", StyleSheet::definition_term),
+    ("definition_description", StyleSheet::definition_description),
+    ("table_header", StyleSheet::table_header),
+    ("table_cell", StyleSheet::table_cell),
+    ("table_border", StyleSheet::table_border),
+    ("image_alt", StyleSheet::image_alt),
+];
+
+/// Single-string marker keys with their [`DefaultStyleSheet`] values.
+const MARKERS: &[(&str, DefaultMarker)] = &[
+    ("code_block_fence", StyleSheet::code_block_fence),
+    (
+        "code_line_number_separator",
+        StyleSheet::code_line_number_separator,
+    ),
+    ("task_checked", |styles| styles.task_marker(true)),
+    ("task_unchecked", |styles| styles.task_marker(false)),
+    ("horizontal_rule", StyleSheet::horizontal_rule),
+    ("metadata_delimiter", StyleSheet::metadata_delimiter),
+    ("details_collapsed", |styles| {
+        styles.details_marker(DetailsState::Collapsed)
+    }),
+    ("details_expanded", |styles| {
+        styles.details_marker(DetailsState::Expanded)
+    }),
+];
+
+const ALERT_KINDS: [(&str, AlertKind); 5] = [
+    ("note", AlertKind::Note),
+    ("tip", AlertKind::Tip),
+    ("important", AlertKind::Important),
+    ("warning", AlertKind::Warning),
+    ("caution", AlertKind::Caution),
+];
+
+/// A [`StyleSheet`] read from a TOML or JSON theme file.
+///
+/// Every value in a theme file is optional; omitted values keep the [`DefaultStyleSheet`] choice.
+/// A TOML theme looks like this, and a JSON theme uses the same keys as nested objects:
+///
+/// ```toml
+/// [styles]
+/// heading_1 = { fg = "black", bg = "light-cyan", modifiers = ["bold"] }
+/// link = { fg = "#5f87ff", modifiers = ["underlined"] }
+/// emphasis = { fg = "yellow" }
+///
+/// [markers]
+/// heading = ["", "", "###"]
+/// code_block_fence = "~~~"
+/// list_bullets = ["•", "◦", "▪"]
+/// list_numbering = ["decimal", "lower-alpha"]
+/// list_delimiter = ")"
+/// task_checked = "☑"
+/// task_unchecked = "☐"
+///
+/// [alerts.warning]
+/// style = { fg = "light-red" }
+/// icon = "⚠"
+/// label = "Careful"
+///
+/// [table]
+/// borders = "rounded"
+/// ```
+///
+/// Style values accept `fg` and `bg` colors as names, `#rrggbb` hex codes, or ANSI indices, and
+/// `modifiers` from `bold`, `dim`, `italic`, `underlined`, `slow_blink`, `rapid_blink`,
+/// `reversed`, `hidden`, and `crossed_out`. A style from the file replaces the default style
+/// instead of patching it.
+///
+/// The style keys are `heading_1` to `heading_6`, `heading_decoration`, `heading_meta`, `code`,
+/// `code_block_title`, `code_line_number`, `code_highlighted_line`, `code_whitespace`,
+/// `code_overflow`, `shell_prompt`, `shell_output`, `diff_added`, `diff_removed`,
+/// `diff_hunk_header`, `diff_file_header`, `mermaid_diagram`, `link`, `blockquote`, `paragraph`,
+/// `emphasis`, `strong`, `strikethrough`, `subscript`, `superscript`, `horizontal_rule`,
+/// `metadata_block`, `list_marker`, `ordered_list_marker`, `task_checked`, `task_unchecked`,
+/// `html`, `keyboard_key`, `underline`, `mark`, `details_summary`, `math_inline`, `math_display`,
+/// `footnote_ref`, `footnote_def`, `footnote_missing`, `footnote_heading`, `definition_term`,
+/// `definition_description`, `table_header`, `table_cell`, `table_border`, and `image_alt`.
+///
+/// Marker keys are `heading` (one entry per level, with the last repeated for deeper levels),
+/// `code_block_fence`, `code_line_number_separator`, `list_bullets` and `list_numbering` (cycled
+/// by nesting level), `list_delimiter` (`.` or `)`), `task_checked`, `task_unchecked`,
+/// `horizontal_rule`, `metadata_delimiter`, `details_collapsed`, and `details_expanded`.
+///
+/// Alerts are configured per kind (`note`, `tip`, `important`, `warning`, `caution`) with `style`,
+/// `icon`, and `label`. Table `borders` are `light`, `rounded`, `heavy`, `double`, `ascii`, or a
+/// table of single characters named like the fields of [`TableBorders`].
+///
+/// Unknown keys and invalid values are rejected with a [`StyleSheetLoadError`] that names the
+/// offending key, such as `styles.link.fg`.
+///
+/// [`ThemeStyleSheet::default`] renders like the default style sheet.
+///
+/// # Example
+///
+/// ```
+/// use ratatui_core::style::{Style, Stylize};
+/// use tui_markdown::{from_str_with_options, Options, ThemeStyleSheet};
+///
+/// let styles = ThemeStyleSheet::from_toml(
+///     r#"
+///     [styles]
+///     emphasis = { fg = "yellow" }
+///     "#,
+/// )?;
+/// let text = from_str_with_options("*note*", &Options::new(styles));
+///
+/// assert_eq!(text.lines[0].spans[0].style, Style::new().yellow());
+/// # Ok::<(), tui_markdown::StyleSheetLoadError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeStyleSheet {
+    styles: BTreeMap<&'static str, Style>,
+    markers: BTreeMap<&'static str, String>,
+    heading_markers: Vec<String>,
+    list_bullets: Vec<String>,
+    list_numbering: Vec<ListNumbering>,
+    list_delimiter: ListDelimiter,
+    alerts: [AlertTheme; 5],
+    table_borders: TableBorders,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AlertTheme {
+    style: Style,
+    icon: String,
+    label: String,
+}
+
+impl Default for ThemeStyleSheet {
+    fn default() -> Self {
+        let defaults = DefaultStyleSheet;
+        Self {
+            styles: STYLES
+                .iter()
+                .map(|(key, style)| (*key, style(&defaults)))
+                .collect(),
+            markers: MARKERS
+                .iter()
+                .map(|(key, marker)| (*key, marker(&defaults).to_owned()))
+                .collect(),
+            heading_markers: (1..=6)
+                .map(|level| defaults.heading_marker(level).to_owned())
+                .collect(),
+            list_bullets: vec![defaults.list_bullet(1).to_owned()],
+            list_numbering: vec![defaults.list_numbering(1)],
+            list_delimiter: defaults.list_delimiter(1),
+            alerts: ALERT_KINDS.map(|(_, kind)| AlertTheme {
+                style: defaults.alert(kind),
+                icon: defaults.alert_icon(kind).to_owned(),
+                label: defaults.alert_label(kind).to_owned(),
+            }),
+            table_borders: defaults.table_borders(),
+        }
+    }
+}
+
+impl ThemeStyleSheet {
+    /// Parses a TOML theme.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StyleSheetLoadError`] when `source` is not valid TOML or contains an unknown key
+    /// or invalid value.
+    pub fn from_toml(source: &str) -> Result<Self, StyleSheetLoadError> {
+        let value = toml::from_str(source).map_err(|error| ErrorKind::Toml(Box::new(error)))?;
+        Ok(Self::from_value(&value)?)
+    }
+
+    /// Parses a JSON theme.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StyleSheetLoadError`] when `source` is not valid JSON or contains an unknown key
+    /// or invalid value.
+    pub fn from_json(source: &str) -> Result<Self, StyleSheetLoadError> {
+        let value = serde_json::from_str(source).map_err(ErrorKind::Json)?;
+        Ok(Self::from_value(&value)?)
+    }
+
+    /// Loads a theme from disk.
+    ///
+    /// The file is parsed as TOML or JSON according to its `.toml` or `.json` extension. The
+    /// returned style sheet owns its data, so rendering does not access the file again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StyleSheetLoadError`] when the file cannot be read, has another extension, or is
+    /// not a valid theme. The error message includes the requested path.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tui_markdown::{Options, ThemeStyleSheet};
+    ///
+    /// let styles = ThemeStyleSheet::from_file("themes/markdown.toml")?;
+    /// let options = Options::new(styles);
+    /// # Ok::<(), tui_markdown::StyleSheetLoadError>(())
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, StyleSheetLoadError> {
+        let path = path.as_ref();
+        let with_path = |kind| StyleSheetLoadError {
+            path: Some(path.to_owned()),
+            kind,
+        };
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        let is_toml = match extension {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => true,
+            Some(extension) if extension.eq_ignore_ascii_case("json") => false,
+            _ => return Err(with_path(ErrorKind::UnsupportedFormat)),
+        };
+        let source =
+            std::fs::read_to_string(path).map_err(|error| with_path(ErrorKind::Io(error)))?;
+        let result = if is_toml {
+            Self::from_toml(&source)
+        } else {
+            Self::from_json(&source)
+        };
+        result.map_err(|error| with_path(error.kind))
+    }
+
+    fn from_value(value: &Value) -> Result<Self, ErrorKind> {
+        let mut theme = Self::default();
+        for (key, value) in table(value, "")? {
+            match key.as_str() {
+                "styles" => theme.read_styles(value)?,
+                "markers" => theme.read_markers(value)?,
+                "alerts" => theme.read_alerts(value)?,
+                "table" => theme.read_table(value)?,
+                _ => return Err(ErrorKind::unknown_key(key)),
+            }
+        }
+        Ok(theme)
+    }
+
+    fn read_styles(&mut self, value: &Value) -> Result<(), ErrorKind> {
+        for (key, value) in table(value, "styles")? {
+            let path = format!("styles.{key}");
+            let Some((name, _)) = STYLES.iter().find(|(name, _)| name == key) else {
+                return Err(ErrorKind::unknown_key(&path));
+            };
+            self.styles.insert(name, style(value, &path)?);
+        }
+        Ok(())
+    }
+
+    fn read_markers(&mut self, value: &Value) -> Result<(), ErrorKind> {
+        for (key, value) in table(value, "markers")? {
+            let path = format!("markers.{key}");
+            match key.as_str() {
+                "heading" => {
+                    self.heading_markers = non_empty(strings(value, &path)?, &path)?;
+                }
+                "list_bullets" => self.list_bullets = non_empty(strings(value, &path)?, &path)?,
+                "list_numbering" => {
+                    let numbering = strings(value, &path)?
+                        .iter()
+                        .map(|name| list_numbering(name, &path))
+                        .collect::<Result<_, _>>()?;
+                    self.list_numbering = non_empty(numbering, &path)?;
+                }
+                "list_delimiter" => {
+                    self.list_delimiter = match string(value, &path)? {
+                        "." => ListDelimiter::Period,
+                        ")" => ListDelimiter::Parenthesis,
+                        other => {
+                            let message = format!("unknown list delimiter `{other}`");
+                            return Err(ErrorKind::invalid(&path, message));
+                        }
+                    };
+                }
+                _ => {
+                    let Some((name, _)) = MARKERS.iter().find(|(name, _)| name == key) else {
+                        return Err(ErrorKind::unknown_key(&path));
+                    };
+                    self.markers.insert(name, string(value, &path)?.to_owned());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_alerts(&mut self, value: &Value) -> Result<(), ErrorKind> {
+        for (key, value) in table(value, "alerts")? {
+            let path = format!("alerts.{key}");
+            let Some(index) = ALERT_KINDS.iter().position(|(name, _)| name == key) else {
+                return Err(ErrorKind::unknown_key(&path));
+            };
+            let alert = &mut self.alerts[index];
+            for (key, value) in table(value, &path)? {
+                let path = format!("{path}.{key}");
+                match key.as_str() {
+                    "style" => alert.style = style(value, &path)?,
+                    "icon" => string(value, &path)?.clone_into(&mut alert.icon),
+                    "label" => string(value, &path)?.clone_into(&mut alert.label),
+                    _ => return Err(ErrorKind::unknown_key(&path)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_table(&mut self, value: &Value) -> Result<(), ErrorKind> {
+        for (key, value) in table(value, "table")? {
+            let path = format!("table.{key}");
+            match key.as_str() {
+                "borders" => self.table_borders = table_borders(value, &path)?,
+                _ => return Err(ErrorKind::unknown_key(&path)),
+            }
+        }
+        Ok(())
+    }
+
+    fn style(&self, key: &str) -> Style {
+        self.styles.get(key).copied().unwrap_or_default()
+    }
+
+    fn marker(&self, key: &str) -> &str {
+        self.markers.get(key).map_or("", String::as_str)
+    }
+
+    fn alert_theme(&self, kind: AlertKind) -> &AlertTheme {
+        let index = ALERT_KINDS
+            .iter()
+            .position(|(_, alert)| *alert == kind)
+            .unwrap_or_default();
+        &self.alerts[index]
+    }
+}
+
+impl StyleSheet for ThemeStyleSheet {
+    fn heading(&self, level: u8) -> Style {
+        match level {
+            1 => self.style("heading_1"),
+            2 => self.style("heading_2"),
+            3 => self.style("heading_3"),
+            4 => self.style("heading_4"),
+            5 => self.style("heading_5"),
+            _ => self.style("heading_6"),
+        }
+    }
+
+    fn code(&self) -> Style {
+        self.style("code")
+    }
+
+    fn link(&self) -> Style {
+        self.style("link")
+    }
+
+    fn blockquote(&self) -> Style {
+        self.style("blockquote")
+    }
+
+    fn heading_decoration(&self, _level: u8) -> Style {
+        self.style("heading_decoration")
+    }
+
+    fn heading_meta(&self) -> Style {
+        self.style("heading_meta")
+    }
+
+    fn paragraph(&self) -> Style {
+        self.style("paragraph")
+    }
+
+    fn emphasis(&self) -> Style {
+        self.style("emphasis")
+    }
+
+    fn strong(&self) -> Style {
+        self.style("strong")
+    }
+
+    fn strikethrough(&self) -> Style {
+        self.style("strikethrough")
+    }
+
+    fn subscript(&self) -> Style {
+        self.style("subscript")
+    }
+
+    fn superscript(&self) -> Style {
+        self.style("superscript")
+    }
+
+    fn horizontal_rule(&self) -> &str {
+        self.marker("horizontal_rule")
+    }
+
+    fn horizontal_rule_style(&self) -> Style {
+        self.style("horizontal_rule")
+    }
+
+    fn metadata_block(&self) -> Style {
+        self.style("metadata_block")
+    }
+
+    fn metadata_delimiter(&self) -> &str {
+        self.marker("metadata_delimiter")
+    }
+
+    fn heading_marker(&self, level: u8) -> &str {
+        let index = usize::from(level.max(1) - 1).min(self.heading_markers.len() - 1);
+        &self.heading_markers[index]
+    }
+
+    fn code_block_fence(&self) -> &str {
+        self.marker("code_block_fence")
+    }
+
+    fn code_block_title_style(&self) -> Style {
+        self.style("code_block_title")
+    }
+
+    fn code_line_number(&self) -> Style {
+        self.style("code_line_number")
+    }
+
+    fn code_line_number_separator(&self) -> &str {
+        self.marker("code_line_number_separator")
+    }
+
+    fn code_highlighted_line(&self) -> Style {
+        self.style("code_highlighted_line")
+    }
+
+    fn code_whitespace(&self) -> Style {
+        self.style("code_whitespace")
+    }
+
+    fn code_overflow(&self) -> Style {
+        self.style("code_overflow")
+    }
+
+    fn shell_prompt(&self) -> Style {
+        self.style("shell_prompt")
+    }
+
+    fn shell_output(&self) -> Style {
+        self.style("shell_output")
+    }
+
+    fn diff_added(&self) -> Style {
+        self.style("diff_added")
+    }
+
+    fn diff_removed(&self) -> Style {
+        self.style("diff_removed")
+    }
+
+    fn diff_hunk_header(&self) -> Style {
+        self.style("diff_hunk_header")
+    }
+
+    fn diff_file_header(&self) -> Style {
+        self.style("diff_file_header")
+    }
+
+    fn mermaid_diagram(&self) -> Style {
+        self.style("mermaid_diagram")
+    }
+
+    fn list_bullet(&self, level: usize) -> &str {
+        &self.list_bullets[level.saturating_sub(1) % self.list_bullets.len()]
+    }
+
+    fn list_numbering(&self, level: usize) -> ListNumbering {
+        self.list_numbering[level.saturating_sub(1) % self.list_numbering.len()]
+    }
+
+    fn list_delimiter(&self, _level: usize) -> ListDelimiter {
+        self.list_delimiter
+    }
+
+    fn task_marker(&self, checked: bool) -> &str {
+        self.marker(if checked {
+            "task_checked"
+        } else {
+            "task_unchecked"
+        })
+    }
+
+    fn task_marker_style(&self, checked: bool) -> Style {
+        self.style(if checked {
+            "task_checked"
+        } else {
+            "task_unchecked"
+        })
+    }
+
+    fn list_marker(&self, ordered: bool) -> Style {
+        self.style(if ordered {
+            "ordered_list_marker"
+        } else {
+            "list_marker"
+        })
+    }
+
+    fn html(&self) -> Style {
+        self.style("html")
+    }
+
+    fn keyboard_key(&self) -> Style {
+        self.style("keyboard_key")
+    }
+
+    fn underline(&self) -> Style {
+        self.style("underline")
+    }
+
+    fn mark(&self) -> Style {
+        self.style("mark")
+    }
+
+    fn details_summary(&self) -> Style {
+        self.style("details_summary")
+    }
+
+    fn details_marker(&self, state: DetailsState) -> &str {
+        match state {
+            DetailsState::Collapsed => self.marker("details_collapsed"),
+            DetailsState::Expanded => self.marker("details_expanded"),
+        }
+    }
+
+    fn math_inline(&self) -> Style {
+        self.style("math_inline")
+    }
+
+    fn math_display(&self) -> Style {
+        self.style("math_display")
+    }
+
+    fn footnote_ref(&self) -> Style {
+        self.style("footnote_ref")
+    }
+
+    fn footnote_def(&self) -> Style {
+        self.style("footnote_def")
+    }
+
+    fn footnote_missing(&self) -> Style {
+        self.style("footnote_missing")
+    }
+
+    fn footnote_heading(&self) -> Style {
+        self.style("footnote_heading")
+    }
+
+    fn definition_term(&self) -> Style {
+        self.style("definition_term")
+    }
+
+    fn definition_description(&self) -> Style {
+        self.style("definition_description")
+    }
+
+    fn alert(&self, kind: AlertKind) -> Style {
+        self.alert_theme(kind).style
+    }
+
+    fn alert_icon(&self, kind: AlertKind) -> &str {
+        &self.alert_theme(kind).icon
+    }
+
+    fn alert_label(&self, kind: AlertKind) -> &str {
+        &self.alert_theme(kind).label
+    }
+
+    fn table_header(&self) -> Style {
+        self.style("table_header")
+    }
+
+    fn table_cell(&self) -> Style {
+        self.style("table_cell")
+    }
+
+    fn table_border(&self) -> Style {
+        self.style("table_border")
+    }
+
+    fn table_borders(&self) -> TableBorders {
+        self.table_borders
+    }
+
+    fn image_alt(&self) -> Style {
+        self.style("image_alt")
+    }
+}
+
+fn table<'v>(value: &'v Value, path: &str) -> Result<&'v Map<String, Value>, ErrorKind> {
+    value
+        .as_object()
+        .ok_or_else(|| ErrorKind::invalid(path, "expected a table"))
+}
+
+fn string<'v>(value: &'v Value, path: &str) -> Result<&'v str, ErrorKind> {
+    value
+        .as_str()
+        .ok_or_else(|| ErrorKind::invalid(path, "expected a string"))
+}
+
+fn strings(value: &Value, path: &str) -> Result<Vec<String>, ErrorKind> {
+    let Some(values) = value.as_array() else {
+        return Err(ErrorKind::invalid(path, "expected an array of strings"));
+    };
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| string(value, &format!("{path}[{index}]")).map(str::to_owned))
+        .collect()
+}
+
+fn non_empty<T>(values: Vec<T>, path: &str) -> Result<Vec<T>, ErrorKind> {
+    if values.is_empty() {
+        return Err(ErrorKind::invalid(path, "expected at least one entry"));
+    }
+    Ok(values)
+}
+
+fn style(value: &Value, path: &str) -> Result<Style, ErrorKind> {
+    let mut style = Style::new();
+    for (key, value) in table(value, path)? {
+        let path = format!("{path}.{key}");
+        match key.as_str() {
+            "fg" => style = style.fg(color(value, &path)?),
+            "bg" => style = style.bg(color(value, &path)?),
+            "modifiers" => {
+                for (index, name) in strings(value, &path)?.iter().enumerate() {
+                    let path = format!("{path}[{index}]");
+                    style = style.add_modifier(modifier(name, &path)?);
+                }
+            }
+            _ => return Err(ErrorKind::unknown_key(&path)),
+        }
+    }
+    Ok(style)
+}
+
+fn color(value: &Value, path: &str) -> Result<Color, ErrorKind> {
+    let name = string(value, path)?;
+    Color::from_str(name).map_err(|_| ErrorKind::invalid(path, format!("unknown color `{name}`")))
+}
+
+fn modifier(name: &str, path: &str) -> Result<Modifier, ErrorKind> {
+    let modifier = match name {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" => Modifier::UNDERLINED,
+        "slow_blink" => Modifier::SLOW_BLINK,
+        "rapid_blink" => Modifier::RAPID_BLINK,
+        "reversed" => Modifier::REVERSED,
+        "hidden" => Modifier::HIDDEN,
+        "crossed_out" => Modifier::CROSSED_OUT,
+        _ => {
+            return Err(ErrorKind::invalid(
+                path,
+                format!("unknown modifier `{name}`"),
+            ))
+        }
+    };
+    Ok(modifier)
+}
+
+fn list_numbering(name: &str, path: &str) -> Result<ListNumbering, ErrorKind> {
+    let numbering = match name {
+        "decimal" => ListNumbering::Decimal,
+        "lower-alpha" => ListNumbering::LowerAlpha,
+        "upper-alpha" => ListNumbering::UpperAlpha,
+        "lower-roman" => ListNumbering::LowerRoman,
+        "upper-roman" => ListNumbering::UpperRoman,
+        _ => {
+            let message = format!("unknown list numbering `{name}`");
+            return Err(ErrorKind::invalid(path, message));
+        }
+    };
+    Ok(numbering)
+}
+
+fn table_borders(value: &Value, path: &str) -> Result<TableBorders, ErrorKind> {
+    if let Some(name) = value.as_str() {
+        return match name {
+            "light" => Ok(TableBorders::LIGHT),
+            "rounded" => Ok(TableBorders::ROUNDED),
+            "heavy" => Ok(TableBorders::HEAVY),
+            "double" => Ok(TableBorders::DOUBLE),
+            "ascii" => Ok(TableBorders::ASCII),
+            _ => Err(ErrorKind::invalid(
+                path,
+                format!("unknown border set `{name}`"),
+            )),
+        };
+    }
+    let mut borders = TableBorders::default();
+    for (key, value) in table(value, path)? {
+        let path = format!("{path}.{key}");
+        let glyph = match key.as_str() {
+            "horizontal" => &mut borders.horizontal,
+            "vertical" => &mut borders.vertical,
+            "top_left" => &mut borders.top_left,
+            "top_junction" => &mut borders.top_junction,
+            "top_right" => &mut borders.top_right,
+            "middle_left" => &mut borders.middle_left,
+            "middle_junction" => &mut borders.middle_junction,
+            "middle_right" => &mut borders.middle_right,
+            "bottom_left" => &mut borders.bottom_left,
+            "bottom_junction" => &mut borders.bottom_junction,
+            "bottom_right" => &mut borders.bottom_right,
+            _ => return Err(ErrorKind::unknown_key(&path)),
+        };
+        let mut chars = string(value, &path)?.chars();
+        *glyph = match (chars.next(), chars.next()) {
+            (Some(char), None) => char,
+            _ => return Err(ErrorKind::invalid(&path, "expected a single character")),
+        };
+    }
+    Ok(borders)
+}
+
+/// An error returned when a theme style sheet cannot be parsed or loaded.
+///
+/// Errors from [`ThemeStyleSheet::from_file`] include the requested path. Invalid themes name the
+/// offending key, which is also available from [`StyleSheetLoadError::key`]. [`Error::source`]
+/// provides the underlying I/O or parsing error without making the parsers part of tui-markdown's
+/// public API.
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct StyleSheetLoadError {
+    path: Option<PathBuf>,
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Io(io::Error),
+    UnsupportedFormat,
+    Toml(Box<toml::de::Error>),
+    Json(serde_json::Error),
+    Invalid { key: String, message: String },
+}
+
+impl ErrorKind {
+    fn invalid(key: &str, message: impl Into<String>) -> Self {
+        Self::Invalid {
+            key: key.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    fn unknown_key(key: &str) -> Self {
+        Self::invalid(key, "unknown key")
+    }
+}
+
+impl From<ErrorKind> for StyleSheetLoadError {
+    fn from(kind: ErrorKind) -> Self {
+        Self { path: None, kind }
+    }
+}
+
+impl StyleSheetLoadError {
+    /// Returns the dotted path of the key that failed validation, such as `styles.link.fg`.
+    ///
+    /// Returns `None` when the file could not be read or parsed.
+    #[must_use]
+    pub fn key(&self) -> Option<&str> {
+        match &self.kind {
+            ErrorKind::Invalid { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StyleSheetLoadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(
+                formatter,
+                "failed to load style sheet from `{}`: ",
+                path.display()
+            )?;
+        } else {
+            write!(formatter, "failed to parse style sheet: ")?;
+        }
+        match &self.kind {
+            ErrorKind::Io(error) => write!(formatter, "{error}"),
+            ErrorKind::UnsupportedFormat => {
+                write!(formatter, "expected a `.toml` or `.json` extension")
+            }
+            ErrorKind::Toml(error) => write!(formatter, "{}", error.message()),
+            ErrorKind::Json(error) => write!(formatter, "{error}"),
+            ErrorKind::Invalid { key, message } => {
+                write!(formatter, "invalid value for `{key}`: {message}")
+            }
+        }
+    }
+}
+
+impl Error for StyleSheetLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(error) => Some(error),
+            ErrorKind::Toml(error) => Some(error),
+            ErrorKind::Json(error) => Some(error),
+            ErrorKind::UnsupportedFormat | ErrorKind::Invalid { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{from_str, from_str_with_options, Options};
+
+    #[test]
+    fn default_theme_matches_default_style_sheet() {
+        let markdown = indoc! {"
+            # Title
+
+            > [!WARNING]
+            > *Careful* with `code` and [links](x).
+
+            - [x] Done
+              1. Nested
+
+            | A |
+            |---|
+            | b |
+        "};
+        let options = Options::new(ThemeStyleSheet::default());
+
+        assert_eq!(
+            from_str_with_options(markdown, &options),
+            from_str(markdown)
+        );
+    }
+
+    #[test]
+    fn reads_toml_theme() {
+        let styles = ThemeStyleSheet::from_toml(indoc! {r##"
+            [styles]
+            heading_2 = { fg = "black", bg = "#ffaf00", modifiers = ["bold", "italic"] }
+
+            [markers]
+            heading = ["", ""]
+            list_bullets = ["•", "◦"]
+            list_numbering = ["upper-roman"]
+            list_delimiter = ")"
+
+            [alerts.warning]
+            icon = "⚠"
+            label = "Careful"
+
+            [table]
+            borders = "ascii"
+        "##})
+        .unwrap();
+
+        assert_eq!(
+            styles.heading(2),
+            Style::new()
+                .black()
+                .bg(Color::Rgb(0xff, 0xaf, 0x00))
+                .bold()
+                .italic()
+        );
+        assert_eq!(styles.heading_marker(4), "");
+        assert_eq!(styles.list_bullet(3), "•");
+        assert_eq!(styles.list_numbering(2), ListNumbering::UpperRoman);
+        assert_eq!(styles.list_delimiter(1), ListDelimiter::Parenthesis);
+        assert_eq!(styles.alert_icon(AlertKind::Warning), "⚠");
+        assert_eq!(styles.alert_label(AlertKind::Warning), "Careful");
+        assert_eq!(styles.alert(AlertKind::Warning), Style::new().yellow());
+        assert_eq!(styles.table_borders(), TableBorders::ASCII);
+    }
+
+    #[test]
+    fn reads_json_theme() {
+        let styles = ThemeStyleSheet::from_json(
+            r#"{
+                "styles": { "link": { "fg": "light-green" } },
+                "markers": { "task_checked": "☑" },
+                "table": { "borders": { "vertical": "┃" } }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(styles.link(), Style::new().light_green());
+        assert_eq!(styles.task_marker(true), "☑");
+        assert_eq!(styles.task_marker(false), "[ ]");
+        assert_eq!(styles.table_borders().vertical, '┃');
+        assert_eq!(styles.table_borders().horizontal, '─');
+    }
+
+    #[test]
+    fn errors_name_the_offending_key() {
+        let cases = [
+            ("[styles]\nlink = { fg = \"bleu\" }", "styles.link.fg"),
+            ("[styles]\nlinks = {}", "styles.links"),
+            (
+                "[styles.code]\nmodifiers = [\"bold\", \"shiny\"]",
+                "styles.code.modifiers[1]",
+            ),
+            ("[markers]\nlist_bullets = []", "markers.list_bullets"),
+            ("[alerts.danger]\nicon = \"!\"", "alerts.danger"),
+            (
+                "[table.borders]\ntop_left = \"++\"",
+                "table.borders.top_left",
+            ),
+            ("colors = {}", "colors"),
+        ];
+        for (source, key) in cases {
+            let error = ThemeStyleSheet::from_toml(source).unwrap_err();
+            assert_eq!(error.key(), Some(key), "{source}");
+        }
+
+        let error = ThemeStyleSheet::from_toml("[styles]\nlink = { fg = \"bleu\" }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse style sheet: invalid value for `styles.link.fg`: unknown color `bleu`"
+        );
+    }
+
+    #[test]
+    fn syntax_errors_have_no_key() {
+        let error = ThemeStyleSheet::from_json("{").unwrap_err();
+
+        assert_eq!(error.key(), None);
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn file_errors_include_the_path() {
+        let error = ThemeStyleSheet::from_file("theme.yaml").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "failed to load style sheet from `theme.yaml`: expected a `.toml` or `.json` extension"
+        );
+    }
+}